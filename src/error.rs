@@ -7,6 +7,12 @@ pub enum MusicXmlError {
     UnknownElement(String),
     UnknownAttribute(String),
     TextfieldEmpty(String),
+    UnknownRoot(String),
+    Io(#[from] std::io::Error),
+    Xml(#[from] roxmltree::Error),
+    Deserialize(#[from] serde_xml_rs::Error),
+    Zip(#[from] zip::result::ZipError),
+    Midi(#[from] midly::Error),
 }
 
 impl std::fmt::Display for MusicXmlError {
@@ -18,6 +24,12 @@ impl std::fmt::Display for MusicXmlError {
                 write!(f, "MusicXmlError::UnknownAttribute: {}", s)
             }
             MusicXmlError::TextfieldEmpty(s) => write!(f, "MusicXmlError::TextfieldEmpty: {}", s),
+            MusicXmlError::UnknownRoot(s) => write!(f, "MusicXmlError::UnknownRoot: {}", s),
+            MusicXmlError::Io(e) => write!(f, "MusicXmlError::Io: {}", e),
+            MusicXmlError::Xml(e) => write!(f, "MusicXmlError::Xml: {}", e),
+            MusicXmlError::Deserialize(e) => write!(f, "MusicXmlError::Deserialize: {}", e),
+            MusicXmlError::Zip(e) => write!(f, "MusicXmlError::Zip: {}", e),
+            MusicXmlError::Midi(e) => write!(f, "MusicXmlError::Midi: {}", e),
         }
     }
 }
@@ -0,0 +1,11 @@
+#![allow(dead_code, unused)]
+
+pub mod error;
+pub mod musicxml;
+mod prelude;
+
+pub use error::MusicXmlError;
+pub use musicxml::{
+    midi::read_midi, midi::write_midi, mxl::read_mxl, mxl::write_mxl, parse_file, parse_reader,
+    parse_str, Score,
+};
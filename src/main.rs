@@ -1,12 +1,31 @@
 #![allow(dead_code, unused)]
 
-use prelude::*;
-use std::fs;
-
-mod error;
-mod musicxml;
-mod prelude;
+use anyhow::Result;
+use musicxml_rs::musicxml::{
+    midi::{read_midi, MidiImportOptions, MidiOptions},
+    round_trip::Coverage,
+};
+use std::env;
 
 fn main() -> Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.as_slice() {
+        // Round-trips every file of a directory and prints what the model drops.
+        [flag, dir] if flag == "--round-trip" => print!("{}", Coverage::of_dir(dir)?),
+        // Renders a score as a Standard MIDI File.
+        [flag, input, output] if flag == "--midi" => {
+            let score = musicxml_rs::parse_file(input)?.into_partwise();
+            std::fs::write(output, score.to_midi(&MidiOptions::default()))?;
+        }
+        // Quantizes a Standard MIDI File into a MusicXML draft in 4/4.
+        [flag, input, output] if flag == "--from-midi" => {
+            let score = read_midi(&std::fs::read(input)?, &MidiImportOptions::default())?;
+            std::fs::write(output, score.to_xml())?;
+        }
+        // Reads a score and prints it as it would be saved.
+        [path] => print!("{}", musicxml_rs::parse_file(path)?.to_xml()),
+        _ => {}
+    }
     Ok(())
 }
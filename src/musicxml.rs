@@ -1,12 +1,20 @@
 use self::score_partwise::ScorePartwise;
+use self::score_timewise::ScoreTimewise;
+use crate::error::MusicXmlError;
 use crate::prelude::*;
+use std::{fs, io::Read, path::Path};
 
 pub mod accidental;
+pub mod accordion_registration;
 pub mod articulations;
+pub mod attribute_cursor;
 pub mod attributes;
 pub mod backup;
 pub mod barline;
 pub mod bass;
+pub mod beam;
+pub mod chord_diagram;
+pub mod chord_symbol;
 pub mod core;
 pub mod credit;
 pub mod defaults;
@@ -14,42 +22,257 @@ pub mod degree;
 pub mod direction;
 pub mod dynamics;
 pub mod forward;
+pub mod fermata;
+pub mod figured_bass;
 pub mod frame;
+pub mod grace;
 pub mod group_barline;
 pub mod group_symbol;
+pub mod grouping;
 pub mod harmony;
+pub mod harp_pedals;
 pub mod identification;
+pub mod interval;
 pub mod left_right_middle;
 pub mod level;
 pub mod lyric;
 pub mod measure;
 pub mod measure_layout;
 pub mod measure_numbering_value;
+pub mod metronome;
+pub mod midi;
 pub mod midi_device;
 pub mod midi_instrument;
+pub mod mxl;
 pub mod notations;
 pub mod note;
+pub mod notehead;
 pub mod numeral;
 pub mod numeral_key;
+pub mod offset;
+pub mod ornaments;
 pub mod page_layout;
 pub mod page_margins;
 pub mod part;
 pub mod part_display;
 pub mod part_group;
 pub mod part_list;
+pub mod percussion;
+pub mod performance;
 pub mod pitch;
 pub mod print;
 pub mod printable_value;
 pub mod root;
+pub mod round_trip;
+pub mod scordatura;
 pub mod score_instrument;
 pub mod score_part;
 pub mod score_partwise;
+pub mod score_timewise;
+pub mod sound;
+pub mod staff_details;
 pub mod staff_layout;
+pub mod staff_tuning;
 pub mod start_stop_continue;
 pub mod start_stop_single;
 pub mod stem;
 pub mod symbol_size;
 pub mod system_divider;
 pub mod system_layout;
+pub mod tablature;
+pub mod technical;
+pub mod theory;
+pub mod tie;
+pub mod tied_type;
+pub mod time_modification;
+pub mod timeline;
+pub mod transpose;
+pub mod tuplet;
+pub mod unpitched;
+pub mod voicing;
 pub mod work;
+pub mod writer;
 pub mod yes_no;
+
+/// A parsed MusicXML document, tagged by its root element.
+#[derive(Debug)]
+pub enum Score {
+    Partwise(ScorePartwise),
+    Timewise(ScoreTimewise),
+}
+
+impl Score {
+    /// Returns the score in partwise form, converting timewise documents.
+    pub fn into_partwise(self) -> ScorePartwise {
+        match self {
+            Score::Partwise(s) => s,
+            Score::Timewise(s) => s.to_partwise(),
+        }
+    }
+
+    /// Returns the score in timewise form, converting partwise documents.
+    pub fn into_timewise(self) -> ScoreTimewise {
+        match self {
+            Score::Partwise(s) => s.to_timewise(),
+            Score::Timewise(s) => s,
+        }
+    }
+
+    /// Writes the score as a MusicXML 4.0 document of the same kind it was read as.
+    pub fn to_xml(&self) -> String {
+        match self {
+            Score::Partwise(s) => s.to_xml(),
+            Score::Timewise(s) => s.to_xml(),
+        }
+    }
+}
+
+/// Parses a MusicXML document from a string. The root element decides which score
+/// type the document is deserialized into.
+pub fn parse_str(xml: &str) -> std::result::Result<Score, MusicXmlError> {
+    let doc = parse_document(xml)?;
+    let root = doc.root_element().tag_name().name();
+
+    match root {
+        "score-partwise" => Ok(Score::Partwise(serde_xml_rs::from_str(xml)?)),
+        "score-timewise" => Ok(Score::Timewise(serde_xml_rs::from_str(xml)?)),
+        _ => Err(MusicXmlError::UnknownRoot(root.to_string())),
+    }
+}
+
+/// Parses `xml` into a DOM. MusicXML files usually carry a DOCTYPE, so DTDs are allowed.
+pub(crate) fn parse_document(xml: &str) -> std::result::Result<Document<'_>, roxmltree::Error> {
+    let options = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    Document::parse_with_options(xml, options)
+}
+
+/// Reads a complete MusicXML document from `reader` and parses it.
+pub fn parse_reader<R: Read>(mut reader: R) -> std::result::Result<Score, MusicXmlError> {
+    let mut xml = String::new();
+    reader.read_to_string(&mut xml)?;
+    parse_str(&xml)
+}
+
+/// Reads the MusicXML file at `path` and parses it. Files with an `.mxl` extension
+/// are read as compressed MusicXML archives.
+pub fn parse_file<P: AsRef<Path>>(path: P) -> std::result::Result<Score, MusicXmlError> {
+    let is_mxl = path
+        .as_ref()
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("mxl"));
+    if is_mxl {
+        return mxl::read_mxl_file(path);
+    }
+
+    let xml = fs::read_to_string(path)?;
+    parse_str(&xml)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{measure::MeasureContent, parse_file, parse_reader, parse_str, Score};
+    use crate::error::MusicXmlError;
+    use std::fs;
+
+    #[test]
+    fn parse_hello_world() {
+        let score = parse_file("xml-files/hello-world.xml").unwrap();
+        match score {
+            Score::Partwise(s) => {
+                assert_eq!(s.version, "4.0".to_string());
+                assert_eq!(s.parts.len(), 1);
+                assert_eq!(s.parts[0].id, "P1".to_string());
+            }
+            _ => panic!("Expected a partwise score."),
+        }
+    }
+
+    #[test]
+    fn parse_timewise() {
+        let score = parse_file("xml-files/hello-world-timewise.xml").unwrap();
+        assert!(matches!(score, Score::Timewise(_)));
+
+        let partwise = score.into_partwise();
+        assert_eq!(partwise.parts.len(), 1);
+        assert_eq!(partwise.parts[0].measures[0].number, "1".to_string());
+    }
+
+    #[test]
+    fn parse_from_reader() {
+        let file = fs::File::open("resources/xml-test-files/01a-Pitches-Pitches.xml").unwrap();
+        let score = parse_reader(file).unwrap();
+        match score {
+            Score::Partwise(s) => {
+                assert_eq!(
+                    s.movement_title.unwrap(),
+                    "Pitches and accidentals".to_string()
+                );
+            }
+            _ => panic!("Expected a partwise score."),
+        }
+    }
+
+    #[test]
+    fn unknown_root() {
+        let err = parse_str("<opus/>").unwrap_err();
+        match err {
+            MusicXmlError::UnknownRoot(r) => assert_eq!(r, "opus".to_string()),
+            _ => panic!("Expected an unknown root error."),
+        }
+    }
+
+    #[test]
+    fn missing_file() {
+        let err = parse_file("xml-files/does-not-exist.xml").unwrap_err();
+        assert!(matches!(err, MusicXmlError::Io(_)));
+    }
+
+    #[test]
+    fn write_hello_world() {
+        let score = parse_file("xml-files/hello-world.xml").unwrap();
+        let xml = score.to_xml();
+
+        assert!(xml.starts_with(r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>"#));
+        assert!(xml.contains("<!DOCTYPE score-partwise PUBLIC"));
+        assert!(xml.contains(r#"<score-partwise version="4.0">"#));
+        assert!(xml.contains(r#"<measure number="1">"#));
+
+        let reparsed = parse_str(&xml).unwrap().into_partwise();
+        assert_eq!(reparsed.parts[0].id, "P1".to_string());
+        assert_eq!(reparsed.parts[0].measures[0].content.len(), 2);
+    }
+
+    #[test]
+    fn write_timewise() {
+        let score = parse_file("xml-files/hello-world-timewise.xml").unwrap();
+        let xml = score.to_xml();
+
+        assert!(xml.contains("<!DOCTYPE score-timewise PUBLIC"));
+        assert!(xml.contains(r#"<part id="P1">"#));
+        assert!(matches!(parse_str(&xml).unwrap(), Score::Timewise(_)));
+    }
+
+    #[test]
+    fn write_rests_and_chords() {
+        let score = parse_file("resources/xml-test-files/41a-MultiParts-Partorder.xml").unwrap();
+        let xml = score.to_xml();
+        assert!(xml.contains("<rest/>"));
+
+        let reparsed = parse_str(&xml).unwrap().into_partwise();
+        let rests = reparsed.parts[0].measures[0]
+            .content
+            .iter()
+            .filter(|c| matches!(c, MeasureContent::Note(n) if n.rest))
+            .count();
+        assert_eq!(rests, 2);
+    }
+
+    #[test]
+    fn malformed_xml() {
+        let err = parse_str("<score-partwise>").unwrap_err();
+        assert!(matches!(err, MusicXmlError::Xml(_)));
+    }
+}
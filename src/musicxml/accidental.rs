@@ -1,5 +1,10 @@
 use serde::{Deserialize, Serialize};
 
+use super::printable_value::{FontStyle, FontWeight};
+use super::symbol_size::SymbolSize;
+use super::writer::{Attrs, WriteXml, XmlWriter};
+use super::yes_no::YesNo;
+
 #[derive(Debug, Serialize, Deserialize, Default, PartialEq, PartialOrd, Clone)]
 pub enum Accidental {
     #[default]
@@ -134,3 +139,92 @@ pub enum Accidental {
     #[serde(rename = "other")]
     Other,
 }
+
+impl Accidental {
+    /// The plain accidental for a whole number of semitones, up to two either way.
+    pub fn for_alter(alter: f32) -> Option<Accidental> {
+        match alter {
+            -2.0 => Some(Accidental::FlatFlat),
+            -1.0 => Some(Accidental::Flat),
+            0.0 => Some(Accidental::Natural),
+            1.0 => Some(Accidental::Sharp),
+            2.0 => Some(Accidental::DoubleSharp),
+            _ => None,
+        }
+    }
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/accidental/
+/// The accidental shown on a note, which need not follow from its `<alter>`.
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, PartialOrd, Clone)]
+pub struct NoteAccidental {
+    #[serde(rename = "$value", default = "Accidental::default")]
+    pub content: Accidental,
+
+    #[serde(default = "Option::default")]
+    pub cautionary: Option<YesNo>,
+
+    #[serde(default = "Option::default")]
+    pub editorial: Option<YesNo>,
+
+    #[serde(default = "Option::default")]
+    pub parentheses: Option<YesNo>,
+
+    #[serde(default = "Option::default")]
+    pub bracket: Option<YesNo>,
+
+    #[serde(default = "Option::default")]
+    pub size: Option<SymbolSize>,
+
+    #[serde(default = "Option::default")]
+    pub smufl: Option<String>,
+
+    #[serde(default = "Option::default")]
+    pub color: Option<String>,
+
+    #[serde(rename = "default-x", default = "Option::default")]
+    pub default_x: Option<f32>,
+
+    #[serde(rename = "default-y", default = "Option::default")]
+    pub default_y: Option<f32>,
+
+    #[serde(rename = "relative-x", default = "Option::default")]
+    pub relative_x: Option<f32>,
+
+    #[serde(rename = "relative-y", default = "Option::default")]
+    pub relative_y: Option<f32>,
+
+    #[serde(rename = "font-family", default = "Option::default")]
+    pub font_family: Option<String>,
+
+    #[serde(rename = "font-size", default = "Option::default")]
+    pub font_size: Option<f32>,
+
+    #[serde(rename = "font-style", default = "Option::default")]
+    pub font_style: Option<FontStyle>,
+
+    #[serde(rename = "font-weight", default = "Option::default")]
+    pub font_weight: Option<FontWeight>,
+}
+
+impl WriteXml for NoteAccidental {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .opt("cautionary", &self.cautionary)
+            .opt("editorial", &self.editorial)
+            .opt("parentheses", &self.parentheses)
+            .opt("bracket", &self.bracket)
+            .opt("size", &self.size)
+            .opt("smufl", &self.smufl)
+            .opt("color", &self.color)
+            .opt("default-x", &self.default_x)
+            .opt("default-y", &self.default_y)
+            .opt("relative-x", &self.relative_x)
+            .opt("relative-y", &self.relative_y)
+            .opt("font-family", &self.font_family)
+            .opt("font-size", &self.font_size)
+            .opt("font-style", &self.font_style)
+            .opt("font-weight", &self.font_weight);
+        w.text_element_with(name, attrs, &self.content);
+    }
+}
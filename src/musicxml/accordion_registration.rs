@@ -0,0 +1,58 @@
+use serde::{de::IgnoredAny, Deserialize, Serialize};
+
+use super::writer::{Attrs, WriteXml, XmlWriter};
+use crate::prelude::*;
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/accordion-registration/
+/// Which accordion reeds sound: the high and low rows, and 0 to 3 dots in the middle.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone)]
+#[serde(from = "AccordionRegistrationXml")]
+pub struct AccordionRegistration {
+    pub high: bool,
+    pub middle: Option<u8>,
+    pub low: bool,
+}
+
+#[derive(Deserialize)]
+enum AccordionRegistrationChild {
+    #[serde(rename = "accordion-high")]
+    High(IgnoredAny),
+    #[serde(rename = "accordion-middle")]
+    Middle(u8),
+    #[serde(rename = "accordion-low")]
+    Low(IgnoredAny),
+}
+
+#[derive(Deserialize)]
+struct AccordionRegistrationXml {
+    #[serde(rename = "$value", default = "Vec::default")]
+    children: Vec<AccordionRegistrationChild>,
+}
+
+impl From<AccordionRegistrationXml> for AccordionRegistration {
+    fn from(xml: AccordionRegistrationXml) -> Self {
+        let mut item = AccordionRegistration::default();
+        for child in xml.children {
+            match child {
+                AccordionRegistrationChild::High(_) => item.high = true,
+                AccordionRegistrationChild::Middle(m) => item.middle = Some(m),
+                AccordionRegistrationChild::Low(_) => item.low = true,
+            }
+        }
+        item
+    }
+}
+
+impl WriteXml for AccordionRegistration {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        if self.high {
+            w.empty("accordion-high", Attrs::new());
+        }
+        w.opt_text_element("accordion-middle", &self.middle);
+        if self.low {
+            w.empty("accordion-low", Attrs::new());
+        }
+        w.end(name);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::musicxml::core::Placement;
+use crate::musicxml::writer::{Attrs, WriteXml, XmlWriter};
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
@@ -117,6 +118,56 @@ pub struct Articulations {
     pub articulations: Vec<ArticulationType>,
 }
 
+impl WriteXml for ArticulationMeta {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .opt("type", &self.r#type)
+            .opt("color", &self.color)
+            .opt("default-x", &self.default_x)
+            .opt("default-y", &self.default_y)
+            .opt("font-family", &self.font_family)
+            .opt("font-size", &self.font_size)
+            .opt("font-style", &self.font_style)
+            .opt("font-weight", &self.font_weigth)
+            .opt("placement", &self.placement)
+            .opt("relative-x", &self.relative_x)
+            .opt("relative-y", &self.relative_y);
+        w.empty(name, attrs);
+    }
+}
+
+impl WriteXml for ArticulationType {
+    fn write_xml(&self, w: &mut XmlWriter, _name: &str) {
+        match self {
+            ArticulationType::Accent(m) => w.element("accent", m),
+            ArticulationType::StrongAccent(m) => w.element("strong-accent", m),
+            ArticulationType::Staccato(m) => w.element("staccato", m),
+            ArticulationType::Tenuto(m) => w.element("tenuto", m),
+            ArticulationType::DetachedLegato(m) => w.element("detached-legato", m),
+            ArticulationType::Staccatissimo(m) => w.element("staccatissimo", m),
+            ArticulationType::Spiccato(m) => w.element("spiccato", m),
+            ArticulationType::Scoop(m) => w.element("scoop", m),
+            ArticulationType::Plop(m) => w.element("plop", m),
+            ArticulationType::Doit(m) => w.element("doit", m),
+            ArticulationType::Falloff(m) => w.element("falloff", m),
+            ArticulationType::BreathMark(m) => w.element("breath-mark", m),
+            ArticulationType::Caesura(m) => w.element("caesura", m),
+            ArticulationType::Stress(m) => w.element("stress", m),
+            ArticulationType::Unstress(m) => w.element("unstress", m),
+            ArticulationType::SoftAccent(m) => w.element("soft-accent", m),
+            ArticulationType::OtherArticulation(m) => w.element("other-articulation", m),
+        }
+    }
+}
+
+impl WriteXml for Articulations {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.elements("", &self.articulations);
+        w.end(name);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{ArticulationType, Articulations};
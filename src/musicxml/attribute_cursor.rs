@@ -0,0 +1,226 @@
+use super::{
+    attributes::{Attributes, Clef, Key, Time},
+    part::Part,
+    staff_details::StaffDetails,
+    staff_tuning::StaffTuning,
+    timeline::Rational,
+    transpose::Transpose,
+};
+use std::collections::BTreeMap;
+
+/// The attributes in effect at one point of a part, carried forward from every
+/// `<attributes>` before it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeState {
+    /// Divisions per quarter note, 1 until the first `<divisions>`.
+    pub divisions: usize,
+
+    pub staves: u8,
+
+    /// Times by staff number, where 0 holds the time of all staves.
+    pub times: BTreeMap<u8, Time>,
+
+    /// Keys by staff number, where 0 holds the key of all staves.
+    pub keys: BTreeMap<u8, Key>,
+
+    /// Clefs by staff number.
+    pub clefs: BTreeMap<u8, Clef>,
+
+    /// Staff details by staff number, merged from every `<staff-details>` so far.
+    pub staff_details: BTreeMap<u8, StaffDetails>,
+
+    /// Transpositions by staff number, where 0 holds that of all staves.
+    pub transposes: BTreeMap<u8, Transpose>,
+}
+
+impl Default for AttributeState {
+    fn default() -> Self {
+        AttributeState {
+            divisions: 1,
+            staves: 1,
+            times: BTreeMap::new(),
+            keys: BTreeMap::new(),
+            clefs: BTreeMap::new(),
+            staff_details: BTreeMap::new(),
+            transposes: BTreeMap::new(),
+        }
+    }
+}
+
+impl AttributeState {
+    /// Takes over the elements present in `attributes` and keeps the others. A key
+    /// time or transpose without a staff number replaces those of all staves.
+    pub fn apply(&mut self, attributes: &Attributes) {
+        if let Some(divisions) = attributes.divisions.filter(|d| *d > 0) {
+            self.divisions = divisions;
+        }
+        if let Some(staves) = attributes.staves {
+            self.staves = staves;
+        }
+        for time in &attributes.times {
+            replace(&mut self.times, time.number, time.clone());
+        }
+        for key in &attributes.keys {
+            replace(&mut self.keys, key.number, key.clone());
+        }
+        for clef in &attributes.clefs {
+            self.clefs.insert(clef.number.max(1) as u8, clef.clone());
+        }
+        for details in &attributes.staff_details {
+            self.staff_details
+                .entry(details.number.max(1))
+                .or_default()
+                .update(details);
+        }
+        for transpose in &attributes.transposes {
+            replace(&mut self.transposes, transpose.number, transpose.clone());
+        }
+    }
+
+    /// The time of a staff, counted from 1.
+    pub fn time(&self, staff: u8) -> Option<&Time> {
+        self.times.get(&staff).or_else(|| self.times.get(&0))
+    }
+
+    /// The key of a staff, counted from 1.
+    pub fn key(&self, staff: u8) -> Option<&Key> {
+        self.keys.get(&staff).or_else(|| self.keys.get(&0))
+    }
+
+    /// The transposition of a staff, counted from 1.
+    pub fn transpose(&self, staff: u8) -> Option<&Transpose> {
+        self.transposes
+            .get(&staff)
+            .or_else(|| self.transposes.get(&0))
+    }
+
+    /// The clef of a staff, counted from 1.
+    pub fn clef(&self, staff: u8) -> Option<&Clef> {
+        self.clefs.get(&staff)
+    }
+
+    /// The staff details of a staff, counted from 1.
+    pub fn staff_details(&self, staff: u8) -> Option<&StaffDetails> {
+        self.staff_details.get(&staff)
+    }
+
+    /// The tuning of a tablature staff, counted from 1, from the lowest string up.
+    pub fn tuning(&self, staff: u8) -> Option<&[StaffTuning]> {
+        self.staff_details(staff)
+            .map(|d| d.staff_tunings.as_slice())
+            .filter(|t| !t.is_empty())
+    }
+}
+
+/// Sets the value of one staff, or of all staves for staff number 0.
+fn replace<T>(values: &mut BTreeMap<u8, T>, number: i8, value: T) {
+    let staff = number.max(0) as u8;
+    if staff == 0 {
+        values.clear();
+    }
+    values.insert(staff, value);
+}
+
+/// Answers which attributes are in effect at a measure and offset of a part. Queries
+/// moving forward continue from the previous one, moving back starts over.
+pub struct AttributeCursor<'a> {
+    /// Measure index, offset into the measure in whole notes and the attributes of
+    /// each change, in document order.
+    changes: Vec<(usize, Rational, &'a Attributes)>,
+    next: usize,
+    state: AttributeState,
+}
+
+impl<'a> AttributeCursor<'a> {
+    pub fn new(part: &'a Part) -> AttributeCursor<'a> {
+        let timeline = part.timeline();
+        let changes = timeline
+            .attributes
+            .iter()
+            .map(|t| {
+                let offset = t.onset - timeline.measures[t.measure].onset;
+                (t.measure, offset, t.event)
+            })
+            .collect();
+
+        AttributeCursor {
+            changes,
+            next: 0,
+            state: AttributeState::default(),
+        }
+    }
+
+    /// The attributes in effect `offset` whole notes into measure `measure`. Changes
+    /// at exactly that offset are included, as they apply to the notes there.
+    pub fn at(&mut self, measure: usize, offset: Rational) -> &AttributeState {
+        let position = (measure, offset);
+        let passed = self
+            .next
+            .checked_sub(1)
+            .map(|last| (self.changes[last].0, self.changes[last].1));
+        if passed.is_some_and(|last| last > position) {
+            self.next = 0;
+            self.state = AttributeState::default();
+        }
+
+        while let Some((m, o, attributes)) = self.changes.get(self.next) {
+            if (*m, *o) > position {
+                break;
+            }
+            self.state.apply(attributes);
+            self.next += 1;
+        }
+
+        &self.state
+    }
+}
+
+impl Part {
+    pub fn attribute_cursor(&self) -> AttributeCursor<'_> {
+        AttributeCursor::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::musicxml::attributes::ClefSign;
+    use crate::musicxml::score_partwise::ScorePartwise;
+    use crate::musicxml::timeline::Rational;
+    use serde_xml_rs::from_str;
+    use std::fs;
+
+    #[test]
+    fn key_changes() {
+        let xml =
+            fs::read_to_string("resources/xml-test-files/13e-KeySignatures-MidMeasure-Change.xml")
+                .unwrap();
+        let item: ScorePartwise = from_str(&xml).unwrap();
+        let mut cursor = item.parts[0].attribute_cursor();
+
+        let fifths: Vec<i8> = (0..4)
+            .map(|beat| cursor.at(0, Rational::new(beat, 4)).key(1).unwrap().fifths)
+            .collect();
+        assert_eq!(fifths, vec![2, -2, 0, 7]);
+
+        let state = cursor.at(0, Rational::from_integer(0));
+        assert_eq!(state.key(1).unwrap().fifths, 2);
+        assert_eq!(state.divisions, 1);
+        assert_eq!(state.time(1).unwrap().fraction(), Some((4, 4)));
+    }
+
+    #[test]
+    fn clef_changes() {
+        let xml = fs::read_to_string("resources/xml-test-files/46c-Midmeasure-Clef.xml").unwrap();
+        let item: ScorePartwise = from_str(&xml).unwrap();
+        let mut cursor = item.parts[0].attribute_cursor();
+
+        let sign = |cursor: &mut super::AttributeCursor, measure, offset| {
+            cursor.at(measure, offset).clef(1).unwrap().sign.clone()
+        };
+        assert_eq!(sign(&mut cursor, 1, Rational::new(1, 4)), ClefSign::G);
+        assert_eq!(sign(&mut cursor, 2, Rational::from_integer(0)), ClefSign::C);
+        assert_eq!(sign(&mut cursor, 3, Rational::new(1, 4)), ClefSign::C);
+        assert_eq!(sign(&mut cursor, 3, Rational::new(1, 2)), ClefSign::G);
+        assert_eq!(sign(&mut cursor, 0, Rational::from_integer(0)), ClefSign::G);
+    }
+}
@@ -1,22 +1,85 @@
+use crate::musicxml::accidental::Accidental;
+use crate::musicxml::harmony::Step;
+use crate::musicxml::interval::Interval;
+use crate::musicxml::staff_details::StaffDetails;
+use crate::musicxml::timeline::Rational;
+use crate::musicxml::transpose::Transpose;
+use crate::musicxml::writer::{Attrs, WriteXml, XmlWriter};
+use crate::musicxml::yes_no::YesNo;
 use crate::prelude::*;
-use serde::{Deserialize, Serialize};
-
-#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Clone)]
+use serde::{de::IgnoredAny, Deserialize, Serialize};
+use strum_macros::EnumString;
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/attributes/
+/// Keys, times, clefs and staff details may appear once per staff, told apart by
+/// their `number`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(from = "AttributesXml")]
 pub struct Attributes {
-    #[serde(default = "Option::default")]
     pub divisions: Option<usize>,
-
-    #[serde(default = "Option::default")]
+    pub keys: Vec<Key>,
+    pub times: Vec<Time>,
     pub staves: Option<u8>,
+    pub clefs: Vec<Clef>,
+    pub staff_details: Vec<StaffDetails>,
+    pub transposes: Vec<Transpose>,
+}
 
-    #[serde(default = "Option::default")]
-    pub key: Option<Key>,
+#[derive(Deserialize)]
+enum AttributesChild {
+    #[serde(rename = "divisions")]
+    Divisions(usize),
+    #[serde(rename = "key")]
+    Key(Key),
+    #[serde(rename = "time")]
+    Time(Time),
+    #[serde(rename = "staves")]
+    Staves(u8),
+    #[serde(rename = "clef")]
+    Clef(Clef),
+    #[serde(rename = "footnote")]
+    Footnote(IgnoredAny),
+    #[serde(rename = "level")]
+    Level(IgnoredAny),
+    #[serde(rename = "part-symbol")]
+    PartSymbol(IgnoredAny),
+    #[serde(rename = "instruments")]
+    Instruments(IgnoredAny),
+    #[serde(rename = "staff-details")]
+    StaffDetails(StaffDetails),
+    #[serde(rename = "transpose")]
+    Transpose(Transpose),
+    #[serde(rename = "for-part")]
+    ForPart(IgnoredAny),
+    #[serde(rename = "directive")]
+    Directive(IgnoredAny),
+    #[serde(rename = "measure-style")]
+    MeasureStyle(IgnoredAny),
+}
 
-    #[serde(default = "Option::default")]
-    pub time: Option<Time>,
+#[derive(Deserialize)]
+struct AttributesXml {
+    #[serde(rename = "$value", default = "Vec::default")]
+    children: Vec<AttributesChild>,
+}
 
-    #[serde(default = "Option::default")]
-    pub clef: Option<Clef>,
+impl From<AttributesXml> for Attributes {
+    fn from(xml: AttributesXml) -> Self {
+        let mut item = Attributes::empty();
+        for child in xml.children {
+            match child {
+                AttributesChild::Divisions(d) => item.divisions = Some(d),
+                AttributesChild::Key(key) => item.keys.push(key),
+                AttributesChild::Time(time) => item.times.push(time),
+                AttributesChild::Staves(s) => item.staves = Some(s),
+                AttributesChild::Clef(clef) => item.clefs.push(clef),
+                AttributesChild::StaffDetails(details) => item.staff_details.push(details),
+                AttributesChild::Transpose(transpose) => item.transposes.push(transpose),
+                _ => {}
+            }
+        }
+        item
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Default, Clone)]
@@ -53,35 +116,381 @@ pub enum KeyMode {
     Locrian,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Clone)]
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/data-types/cancel-location/
+#[derive(Debug, EnumString, Serialize, Deserialize, PartialEq, Clone)]
+pub enum CancelLocation {
+    #[strum(serialize = "left")]
+    #[serde(rename = "left")]
+    Left,
+
+    #[strum(serialize = "right")]
+    #[serde(rename = "right")]
+    Right,
+
+    #[strum(serialize = "before-barline")]
+    #[serde(rename = "before-barline")]
+    BeforeBarline,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/cancel/
+/// Naturals cancelling the previous key signature of `fifths` sharps or flats.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct Cancel {
+    #[serde(rename = "$value")]
+    pub fifths: i8,
+
+    #[serde(default = "Option::default")]
+    pub location: Option<CancelLocation>,
+}
+
+/// One altered step of a non-traditional key signature.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct KeyAlteration {
+    pub step: Step,
+
+    /// Semitones, like -1 for flat or 0.5 for quarter-tone sharp.
+    pub alter: f32,
+
+    pub accidental: Option<Accidental>,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/key-octave/
+/// The octave an accidental of the key signature is displayed in. `number` counts
+/// the accidentals from 1.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct KeyOctave {
+    #[serde(rename = "$value")]
+    pub octave: u8,
+
+    pub number: u8,
+
+    #[serde(default = "Option::default")]
+    pub cancel: Option<YesNo>,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/key/
+/// A traditional key of `fifths` sharps or flats, or a non-traditional key listing
+/// its `alterations`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Clone)]
+#[serde(from = "KeyXml")]
 pub struct Key {
-    #[serde(default = "i8::default")]
+    /// The staff the key applies to, or 0 for all staves.
     pub number: i8,
 
-    #[serde(default = "i8::default")]
+    pub cancel: Option<Cancel>,
+
     pub fifths: i8,
 
-    #[serde(default = "KeyMode::default")]
     pub mode: KeyMode,
+
+    /// Empty for traditional keys.
+    pub alterations: Vec<KeyAlteration>,
+
+    pub octaves: Vec<KeyOctave>,
+}
+
+#[derive(Deserialize)]
+enum KeyChild {
+    #[serde(rename = "cancel")]
+    Cancel(Cancel),
+    #[serde(rename = "fifths")]
+    Fifths(i8),
+    #[serde(rename = "mode")]
+    Mode(KeyMode),
+    #[serde(rename = "key-step")]
+    KeyStep(Step),
+    #[serde(rename = "key-alter")]
+    KeyAlter(f32),
+    #[serde(rename = "key-accidental")]
+    KeyAccidental(Accidental),
+    #[serde(rename = "key-octave")]
+    KeyOctave(KeyOctave),
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Clone)]
+#[derive(Deserialize)]
+struct KeyXml {
+    #[serde(default = "Option::default")]
+    number: Option<i8>,
+
+    #[serde(rename = "$value", default = "Vec::default")]
+    children: Vec<KeyChild>,
+}
+
+impl From<KeyXml> for Key {
+    fn from(xml: KeyXml) -> Self {
+        let mut key = Key {
+            number: xml.number.unwrap_or(0),
+            ..Key::default()
+        };
+        for child in xml.children {
+            match child {
+                KeyChild::Cancel(cancel) => key.cancel = Some(cancel),
+                KeyChild::Fifths(fifths) => key.fifths = fifths,
+                KeyChild::Mode(mode) => key.mode = mode,
+                KeyChild::KeyStep(step) => key.alterations.push(KeyAlteration {
+                    step,
+                    alter: 0.0,
+                    accidental: None,
+                }),
+                KeyChild::KeyAlter(alter) => {
+                    if let Some(last) = key.alterations.last_mut() {
+                        last.alter = alter;
+                    }
+                }
+                KeyChild::KeyAccidental(accidental) => {
+                    if let Some(last) = key.alterations.last_mut() {
+                        last.accidental = Some(accidental);
+                    }
+                }
+                KeyChild::KeyOctave(octave) => key.octaves.push(octave),
+            }
+        }
+        key
+    }
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/data-types/time-symbol/
+#[derive(Debug, EnumString, Serialize, Deserialize, PartialEq, Clone)]
+pub enum TimeSymbol {
+    #[strum(serialize = "common")]
+    #[serde(rename = "common")]
+    Common,
+
+    #[strum(serialize = "cut")]
+    #[serde(rename = "cut")]
+    Cut,
+
+    #[strum(serialize = "single-number")]
+    #[serde(rename = "single-number")]
+    SingleNumber,
+
+    /// The beat type is shown as a note.
+    #[strum(serialize = "note")]
+    #[serde(rename = "note")]
+    Note,
+
+    #[strum(serialize = "dotted-note")]
+    #[serde(rename = "dotted-note")]
+    DottedNote,
+
+    #[strum(serialize = "normal")]
+    #[serde(rename = "normal")]
+    Normal,
+}
+
+/// One fraction of a time signature. Composite signatures like 3+2/8 keep the sum
+/// in `beats` as written.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct TimeSignature {
+    pub beats: String,
+    pub beat_type: u32,
+}
+
+impl TimeSignature {
+    /// The numerator with the parts of a composite signature added up.
+    pub fn beats(&self) -> u32 {
+        self.beats
+            .split('+')
+            .filter_map(|b| b.trim().parse::<u32>().ok())
+            .sum()
+    }
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/interchangeable/
+/// A second time signature shown alongside the first, like 6/8 (3/4).
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct Interchangeable {
+    pub symbol: Option<TimeSymbol>,
+
+    /// Parentheses, bracket, equals, slash, space or hyphen.
+    pub relation: Option<String>,
+
+    pub signatures: Vec<TimeSignature>,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/time/
+/// A time signature of one or more fractions, or senza misura for unmeasured music.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(from = "TimeXml")]
 pub struct Time {
-    #[serde(default = "u8::default")]
-    pub beats: u8,
+    /// The staff the time applies to, or 0 for all staves.
+    pub number: i8,
+
+    pub symbol: Option<TimeSymbol>,
 
-    #[serde(default = "u8::default", rename = "beat-type")]
-    pub beat_type: u8,
+    pub signatures: Vec<TimeSignature>,
+
+    pub interchangeable: Option<Interchangeable>,
+
+    /// The symbol shown for unmeasured music, usually empty.
+    pub senza_misura: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Clone)]
+impl Time {
+    pub fn new(beats: u32, beat_type: u32) -> Time {
+        Time {
+            signatures: vec![TimeSignature {
+                beats: beats.to_string(),
+                beat_type,
+            }],
+            ..Time::default()
+        }
+    }
+
+    /// Beats and beat type of a signature with a single fraction, with the beats of
+    /// a composite signature added up.
+    pub fn fraction(&self) -> Option<(u32, u32)> {
+        match self.signatures.as_slice() {
+            [signature] => Some((signature.beats(), signature.beat_type)),
+            _ => None,
+        }
+    }
+
+    /// Length of a measure in whole notes, the sum of all fractions. `None` for senza
+    /// misura.
+    pub fn measure_duration(&self) -> Option<Rational> {
+        if self.senza_misura.is_some() || self.signatures.is_empty() {
+            return None;
+        }
+        self.signatures
+            .iter()
+            .map(|s| (s.beat_type > 0).then(|| Rational::new(s.beats() as i64, s.beat_type as i64)))
+            .sum()
+    }
+}
+
+#[derive(Deserialize)]
+enum TimeChild {
+    #[serde(rename = "beats")]
+    Beats(String),
+    #[serde(rename = "beat-type")]
+    BeatType(u32),
+    #[serde(rename = "interchangeable")]
+    Interchangeable(InterchangeableXml),
+    #[serde(rename = "senza-misura")]
+    SenzaMisura(Option<String>),
+}
+
+#[derive(Deserialize)]
+enum InterchangeableChild {
+    #[serde(rename = "time-relation")]
+    TimeRelation(String),
+    #[serde(rename = "beats")]
+    Beats(String),
+    #[serde(rename = "beat-type")]
+    BeatType(u32),
+}
+
+#[derive(Deserialize)]
+struct InterchangeableXml {
+    #[serde(default = "Option::default")]
+    symbol: Option<TimeSymbol>,
+
+    #[serde(rename = "$value", default = "Vec::default")]
+    children: Vec<InterchangeableChild>,
+}
+
+#[derive(Deserialize)]
+struct TimeXml {
+    #[serde(default = "Option::default")]
+    number: Option<i8>,
+
+    #[serde(default = "Option::default")]
+    symbol: Option<TimeSymbol>,
+
+    #[serde(rename = "$value", default = "Vec::default")]
+    children: Vec<TimeChild>,
+}
+
+fn push_beats(signatures: &mut Vec<TimeSignature>, beats: String) {
+    signatures.push(TimeSignature {
+        beats,
+        beat_type: 0,
+    });
+}
+
+/// A `<beat-type>` completes the fraction its `<beats>` started.
+fn set_beat_type(signatures: &mut [TimeSignature], beat_type: u32) {
+    if let Some(last) = signatures.last_mut() {
+        last.beat_type = beat_type;
+    }
+}
+
+impl From<TimeXml> for Time {
+    fn from(xml: TimeXml) -> Self {
+        let mut time = Time {
+            number: xml.number.unwrap_or(0),
+            symbol: xml.symbol,
+            ..Time::default()
+        };
+        for child in xml.children {
+            match child {
+                TimeChild::Beats(b) => push_beats(&mut time.signatures, b),
+                TimeChild::BeatType(t) => set_beat_type(&mut time.signatures, t),
+                TimeChild::Interchangeable(xml) => {
+                    let mut interchangeable = Interchangeable {
+                        symbol: xml.symbol,
+                        ..Interchangeable::default()
+                    };
+                    for child in xml.children {
+                        let signatures = &mut interchangeable.signatures;
+                        match child {
+                            InterchangeableChild::TimeRelation(r) => {
+                                interchangeable.relation = Some(r)
+                            }
+                            InterchangeableChild::Beats(b) => push_beats(signatures, b),
+                            InterchangeableChild::BeatType(t) => set_beat_type(signatures, t),
+                        }
+                    }
+                    time.interchangeable = Some(interchangeable);
+                }
+                TimeChild::SenzaMisura(s) => time.senza_misura = Some(s.unwrap_or_default()),
+            }
+        }
+        time
+    }
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/data-types/clef-sign/
+#[derive(Debug, EnumString, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub enum ClefSign {
+    #[default]
+    G,
+    F,
+    C,
+
+    #[strum(serialize = "percussion")]
+    #[serde(rename = "percussion")]
+    Percussion,
+
+    #[strum(serialize = "TAB")]
+    #[serde(rename = "TAB")]
+    Tab,
+
+    #[strum(serialize = "jianpu")]
+    #[serde(rename = "jianpu")]
+    Jianpu,
+
+    #[strum(serialize = "none")]
+    #[serde(rename = "none")]
+    None,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/clef/
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct Clef {
-    #[serde(default = "String::default")]
-    pub sign: String,
+    #[serde(default = "ClefSign::default")]
+    pub sign: ClefSign,
 
     #[serde(default = "i8::default")]
     pub line: i8,
 
+    /// Octaves the notes sound above or below the written pitch, like -1 for the
+    /// tenor G clef.
+    #[serde(rename = "clef-octave-change", default = "i8::default")]
+    pub octave_change: i8,
+
+    /// The staff the clef applies to, or 0 for the first staff.
     #[serde(default = "i8::default")]
     pub number: i8,
 }
@@ -90,20 +499,204 @@ impl Attributes {
     pub fn empty() -> Attributes {
         Attributes {
             divisions: None,
+            keys: vec![],
+            times: vec![],
             staves: None,
-            key: None,
-            time: None,
-            clef: None,
+            clefs: vec![],
+            staff_details: vec![],
+            transposes: vec![],
+        }
+    }
+}
+
+impl Key {
+    /// Estimates the key of a passage from MIDI note numbers weighted by duration, by
+    /// correlating its pitch class distribution with the Krumhansl-Kessler profiles.
+    pub fn estimate<I: IntoIterator<Item = (u8, f32)>>(notes: I) -> Key {
+        const MAJOR: [f32; 12] = [
+            6.35, 2.23, 3.48, 2.33, 4.38, 4.09, 2.52, 5.19, 2.39, 3.66, 2.29, 2.88,
+        ];
+        const MINOR: [f32; 12] = [
+            6.33, 2.68, 3.52, 5.38, 2.60, 3.53, 2.54, 4.75, 3.98, 2.69, 3.34, 3.17,
+        ];
+
+        let mut histogram = [0.0; 12];
+        for (number, weight) in notes {
+            histogram[(number % 12) as usize] += weight;
+        }
+
+        let mut best = (f32::MIN, 0, KeyMode::Mayjor);
+        for tonic in 0..12 {
+            let rotated: Vec<f32> = (0..12).map(|i| histogram[(tonic + i) % 12]).collect();
+            for (profile, mode) in [(&MAJOR, KeyMode::Mayjor), (&MINOR, KeyMode::Minor)] {
+                let r = correlation(&rotated, profile);
+                if r > best.0 {
+                    // Minor keys share the signature of their relative major.
+                    let major = if mode == KeyMode::Minor {
+                        tonic + 3
+                    } else {
+                        tonic
+                    };
+                    let fifths = (major * 7 % 12) as i8;
+                    best = (r, if fifths > 6 { fifths - 12 } else { fifths }, mode);
+                }
+            }
+        }
+
+        Key {
+            fifths: best.1,
+            mode: best.2,
+            ..Key::default()
+        }
+    }
+
+    /// The key `interval` higher, along with the key it cancels. Keys beyond seven
+    /// sharps or flats stay theoretical, see `Interval::transpose_fifths`.
+    /// Non-traditional keys are kept as they are.
+    pub fn transpose(&self, interval: &Interval) -> Key {
+        let mut key = self.clone();
+        if self.alterations.is_empty() {
+            key.fifths = interval.transpose_fifths(self.fifths);
+            if let Some(cancel) = &mut key.cancel {
+                cancel.fifths = interval.transpose_fifths(cancel.fifths);
+            }
+        }
+        key
+    }
+
+    /// The alteration in semitones the key gives a step.
+    pub fn alter(&self, step: &Step) -> f32 {
+        if self.alterations.is_empty() {
+            return step.key_signature_alter(self.fifths) as f32;
+        }
+        self.alterations
+            .iter()
+            .find(|a| a.step == *step)
+            .map_or(0.0, |a| a.alter)
+    }
+}
+
+fn correlation(x: &[f32], y: &[f32]) -> f32 {
+    let mean = |v: &[f32]| v.iter().sum::<f32>() / v.len() as f32;
+    let (mx, my) = (mean(x), mean(y));
+
+    let mut cov = 0.0;
+    let mut vx = 0.0;
+    let mut vy = 0.0;
+    for (a, b) in x.iter().zip(y) {
+        cov += (a - mx) * (b - my);
+        vx += (a - mx) * (a - mx);
+        vy += (b - my) * (b - my);
+    }
+    cov / (vx * vy).sqrt()
+}
+
+impl WriteXml for Key {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(
+            name,
+            Attrs::new().add_if(self.number != 0, "number", &self.number),
+        );
+        if let Some(cancel) = &self.cancel {
+            let attrs = Attrs::new().opt("location", &cancel.location);
+            w.text_element_with("cancel", attrs, &cancel.fifths);
+        }
+        if self.alterations.is_empty() {
+            w.text_element("fifths", &self.fifths);
+            if self.mode != KeyMode::None {
+                w.text_element("mode", &self.mode);
+            }
+        }
+        for alteration in &self.alterations {
+            w.text_element("key-step", &alteration.step);
+            w.text_element("key-alter", &alteration.alter);
+            w.opt_text_element("key-accidental", &alteration.accidental);
+        }
+        for octave in &self.octaves {
+            let attrs = Attrs::new()
+                .add("number", &octave.number)
+                .opt("cancel", &octave.cancel);
+            w.text_element_with("key-octave", attrs, &octave.octave);
+        }
+        w.end(name);
+    }
+}
+
+fn write_signatures(w: &mut XmlWriter, signatures: &[TimeSignature]) {
+    for signature in signatures {
+        w.text_element("beats", &signature.beats);
+        w.text_element("beat-type", &signature.beat_type);
+    }
+}
+
+impl WriteXml for Time {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .add_if(self.number != 0, "number", &self.number)
+            .opt("symbol", &self.symbol);
+        w.start(name, attrs);
+        write_signatures(w, &self.signatures);
+        if let Some(interchangeable) = &self.interchangeable {
+            w.start(
+                "interchangeable",
+                Attrs::new().opt("symbol", &interchangeable.symbol),
+            );
+            w.opt_text_element("time-relation", &interchangeable.relation);
+            write_signatures(w, &interchangeable.signatures);
+            w.end("interchangeable");
+        }
+        match self.senza_misura.as_deref() {
+            Some("") => w.empty("senza-misura", Attrs::new()),
+            Some(symbol) => w.text_element("senza-misura", symbol),
+            None => {}
         }
+        w.end(name);
+    }
+}
+
+impl WriteXml for Clef {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(
+            name,
+            Attrs::new().add_if(self.number != 0, "number", &self.number),
+        );
+        w.text_element("sign", &self.sign);
+        if self.line != 0 {
+            w.text_element("line", &self.line);
+        }
+        if self.octave_change != 0 {
+            w.text_element("clef-octave-change", &self.octave_change);
+        }
+        w.end(name);
+    }
+}
+
+impl WriteXml for Attributes {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.opt_text_element("divisions", &self.divisions);
+        w.elements("key", &self.keys);
+        w.elements("time", &self.times);
+        w.opt_text_element("staves", &self.staves);
+        w.elements("clef", &self.clefs);
+        w.elements("staff-details", &self.staff_details);
+        w.elements("transpose", &self.transposes);
+        w.end(name);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::musicxml::attributes::KeyMode;
+    use crate::musicxml::harmony::Step;
+    use crate::musicxml::interval::Interval;
+    use crate::musicxml::measure::MeasureContent;
+    use crate::musicxml::score_partwise::ScorePartwise;
+    use crate::musicxml::timeline::Rational;
     use serde_xml_rs::from_str;
+    use std::fs;
 
-    use super::Attributes;
+    use super::{Attributes, CancelLocation, Clef, ClefSign, Key, Time, TimeSymbol};
 
     #[test]
     fn attributes() {
@@ -130,18 +723,133 @@ mod tests {
         assert_eq!(attribs.divisions, Some(4));
         assert_eq!(attribs.staves, Some(1));
 
-        let key = attribs.key.unwrap();
+        let key = &attribs.keys[0];
         assert_eq!(key.number, 1);
         assert_eq!(key.fifths, 0);
         assert_eq!(key.mode, KeyMode::None);
 
-        let time = attribs.time.unwrap();
-        assert_eq!(time.beats, 4);
-        assert_eq!(time.beat_type, 4);
+        assert_eq!(attribs.times[0].fraction(), Some((4, 4)));
 
-        let clef = attribs.clef.unwrap();
-        assert_eq!(clef.sign, "G");
+        let clef = &attribs.clefs[0];
+        assert_eq!(clef.sign, ClefSign::G);
         assert_eq!(clef.line, 2);
         assert_eq!(clef.number, 1);
     }
+
+    fn first_attributes(file: &str) -> Attributes {
+        let xml = fs::read_to_string(format!("resources/xml-test-files/{file}")).unwrap();
+        let item: ScorePartwise = from_str(&xml).unwrap();
+        item.parts[0].measures[0]
+            .content
+            .iter()
+            .find_map(|c| match c {
+                MeasureContent::Attributes(a) => Some(a.clone()),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn multi_staff_keys() {
+        let attribs = first_attributes("43b-MultiStaff-DifferentKeys.xml");
+
+        let keys: Vec<(i8, i8)> = attribs.keys.iter().map(|k| (k.number, k.fifths)).collect();
+        assert_eq!(keys, vec![(1, 0), (2, 2)]);
+
+        let clefs: Vec<(i8, ClefSign)> = attribs
+            .clefs
+            .iter()
+            .map(|c| (c.number, c.sign.clone()))
+            .collect();
+        assert_eq!(clefs, vec![(1, ClefSign::G), (2, ClefSign::F)]);
+    }
+
+    #[test]
+    fn non_traditional_keys() {
+        let key = first_attributes("13c-KeySignatures-NonTraditional.xml").keys[0].clone();
+        assert_eq!(key.alterations.len(), 3);
+        assert_eq!(key.alter(&Step::F), 1.0);
+        assert_eq!(key.alter(&Step::B), -1.0);
+        assert_eq!(key.alter(&Step::C), 0.0);
+
+        let key = first_attributes("13ac-KeySignatures-Octaves.xml").keys[0].clone();
+        assert_eq!(key.alter(&Step::G), 1.0);
+        let octaves: Vec<(u8, u8)> = key.octaves.iter().map(|o| (o.number, o.octave)).collect();
+        assert_eq!(octaves, vec![(1, 4), (3, 4)]);
+
+        let xml = r#"
+            <key>
+                <cancel location="before-barline">-2</cancel>
+                <fifths>3</fifths>
+            </key>"#;
+        let key: Key = from_str(xml).unwrap();
+        let cancel = key.cancel.unwrap();
+        assert_eq!(cancel.fifths, -2);
+        assert_eq!(cancel.location, Some(CancelLocation::BeforeBarline));
+    }
+
+    #[test]
+    fn compound_times() {
+        let time = first_attributes("11d-TimeSignatures-CompoundMultiple.xml").times[0].clone();
+        assert_eq!(time.fraction(), None);
+        assert_eq!(time.measure_duration(), Some(Rational::new(11, 8)));
+
+        let time = first_attributes("11e-TimeSignatures-CompoundMixed.xml").times[0].clone();
+        assert_eq!(time.signatures[0].beats, "3+2");
+        assert_eq!(time.signatures[0].beats(), 5);
+        assert_eq!(time.measure_duration(), Some(Rational::new(11, 8)));
+
+        let time = first_attributes("11h-TimeSignatures-SenzaMisura.xml").times[0].clone();
+        assert_eq!(time.senza_misura, Some(String::new()));
+        assert_eq!(time.measure_duration(), None);
+
+        let xml = r#"
+            <time symbol="cut">
+                <beats>2</beats>
+                <beat-type>2</beat-type>
+                <interchangeable>
+                    <time-relation>parentheses</time-relation>
+                    <beats>4</beats>
+                    <beat-type>4</beat-type>
+                </interchangeable>
+            </time>"#;
+        let time: Time = from_str(xml).unwrap();
+        assert_eq!(time.symbol, Some(TimeSymbol::Cut));
+        let interchangeable = time.interchangeable.unwrap();
+        assert_eq!(interchangeable.relation, Some("parentheses".to_string()));
+        assert_eq!(interchangeable.signatures[0].beats(), 4);
+    }
+
+    #[test]
+    fn clef_octave_change() {
+        let xml = r#"
+            <clef>
+                <sign>G</sign>
+                <line>2</line>
+                <clef-octave-change>-1</clef-octave-change>
+            </clef>"#;
+        let clef: Clef = from_str(xml).unwrap();
+        assert_eq!(clef.sign, ClefSign::G);
+        assert_eq!(clef.octave_change, -1);
+
+        let clef: Clef = from_str("<clef><sign>TAB</sign></clef>").unwrap();
+        assert_eq!(clef.sign, ClefSign::Tab);
+    }
+
+    #[test]
+    fn estimate_key() {
+        let scale = |notes: &[u8]| notes.iter().map(|n| (*n, 1.0)).collect::<Vec<_>>();
+
+        let key = Key::estimate(scale(&[60, 62, 64, 65, 67, 69, 71, 72, 67, 60]));
+        assert_eq!((key.fifths, key.mode), (0, KeyMode::Mayjor));
+
+        let key = Key::estimate(scale(&[62, 64, 66, 67, 69, 71, 73, 74, 69, 62]));
+        assert_eq!((key.fifths, key.mode), (2, KeyMode::Mayjor));
+
+        let key = Key::estimate(scale(&[57, 59, 60, 62, 64, 65, 68, 69, 64, 57]));
+        assert_eq!((key.fifths, key.mode), (0, KeyMode::Minor));
+
+        let key = Key::estimate(scale(&[63, 65, 67, 68, 70, 72, 74, 75, 70, 63]));
+        assert_eq!((key.fifths, key.mode), (-3, KeyMode::Mayjor));
+    }
 }
@@ -1,16 +1,31 @@
-use super::{core::Duration, level::Level, printable_value::PrintableValue};
+use super::{
+    core::Duration,
+    level::Level,
+    printable_value::PrintableValue,
+    writer::{Attrs, WriteXml, XmlWriter},
+};
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Backup {
     #[serde(default = "Duration::default")]
-    duration: Duration,
+    pub duration: Duration,
 
     #[serde(default = "Option::default")]
-    footnote: Option<PrintableValue<String>>,
+    pub footnote: Option<PrintableValue<String>>,
 
-    level: Option<Level>,
+    pub level: Option<Level>,
+}
+
+impl WriteXml for Backup {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.text_element("duration", &self.duration);
+        w.opt_element("footnote", &self.footnote);
+        w.opt_element("level", &self.level);
+        w.end(name);
+    }
 }
 
 #[cfg(test)]
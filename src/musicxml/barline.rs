@@ -4,6 +4,8 @@ use strum_macros::EnumString;
 use super::core::RepeatDirection;
 use super::left_right_middle::LeftRightMiddle;
 use super::printable_value::PrintableValue;
+use super::writer::{Attrs, WriteXml, XmlWriter};
+use super::yes_no::YesNo;
 use crate::prelude::*;
 use std::str::FromStr;
 
@@ -59,6 +61,63 @@ pub enum BarStyle {
     Tick,
 }
 
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/repeat/
+#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd)]
+pub struct Repeat {
+    pub direction: RepeatDirection,
+
+    /// How often the repeated section is played in total, for backward repeats.
+    #[serde(default = "Option::default")]
+    pub times: Option<u32>,
+
+    /// Whether the repeat is still taken after a da capo or dal segno jump.
+    #[serde(rename = "after-jump", default = "Option::default")]
+    pub after_jump: Option<YesNo>,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/data-types/start-stop-discontinue/
+#[derive(Debug, EnumString, Serialize, Deserialize, PartialEq, PartialOrd, Clone, Copy)]
+pub enum EndingType {
+    #[strum(serialize = "start")]
+    #[serde(rename = "start")]
+    Start,
+
+    #[strum(serialize = "stop")]
+    #[serde(rename = "stop")]
+    Stop,
+
+    /// Ends the volta without a downward jog, usually on the last ending.
+    #[strum(serialize = "discontinue")]
+    #[serde(rename = "discontinue")]
+    Discontinue,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/ending/
+/// A volta bracket. It starts on a left barline and stops or discontinues on a
+/// right barline, possibly of a later measure.
+#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd)]
+pub struct Ending {
+    /// The passes the ending is played on, like "1" or "1, 2".
+    pub number: String,
+
+    pub r#type: EndingType,
+
+    /// Displayed text, when it differs from the number.
+    #[serde(rename = "$value", default = "Option::default")]
+    pub text: Option<String>,
+}
+
+impl Ending {
+    /// The pass numbers in `number`, which may be separated by commas and spaces.
+    /// Invalid entries are skipped.
+    pub fn passes(&self) -> Vec<u32> {
+        self.number
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter_map(|n| n.parse().ok())
+            .collect()
+    }
+}
+
 // https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/barline/
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Barline {
@@ -69,13 +128,44 @@ pub struct Barline {
     pub footnote: Option<PrintableValue<String>>,
 
     pub location: LeftRightMiddle,
-    pub repeatdirection: Option<RepeatDirection>,
+
+    #[serde(default = "Option::default")]
+    pub ending: Option<Ending>,
+
+    #[serde(default = "Option::default")]
+    pub repeat: Option<Repeat>,
+}
+
+impl WriteXml for Barline {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new().add("location", &self.location));
+        w.opt_text_element("bar-style", &self.barstyle);
+        w.opt_element("footnote", &self.footnote);
+        if let Some(ending) = &self.ending {
+            let attrs = Attrs::new()
+                .add("number", &ending.number)
+                .add("type", &ending.r#type);
+            match &ending.text {
+                Some(text) => w.text_element_with("ending", attrs, text),
+                None => w.empty("ending", attrs),
+            }
+        }
+        if let Some(repeat) = &self.repeat {
+            let attrs = Attrs::new()
+                .add("direction", &repeat.direction)
+                .opt("times", &repeat.times)
+                .opt("after-jump", &repeat.after_jump);
+            w.empty("repeat", attrs);
+        }
+        w.end(name);
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Barline;
+    use super::{Barline, EndingType, Repeat};
     use crate::musicxml::barline::{BarStyle, LeftRightMiddle};
+    use crate::musicxml::core::RepeatDirection;
     use serde_xml_rs::from_str;
 
     #[test]
@@ -97,5 +187,50 @@ mod tests {
 
         assert_eq!(barline.location, LeftRightMiddle::Right);
         assert_eq!(barline.barstyle.unwrap(), BarStyle::LightLight);
+        assert_eq!(barline.repeat, None);
+    }
+
+    #[test]
+    fn repeat() {
+        let xml = r#"
+            <barline location="right">
+                <bar-style>light-heavy</bar-style>
+                <repeat direction="backward" times="3"/>
+            </barline>"#;
+        let barline: Barline = from_str(xml).unwrap();
+
+        assert_eq!(
+            barline.repeat,
+            Some(Repeat {
+                direction: RepeatDirection::Backward,
+                times: Some(3),
+                after_jump: None,
+            })
+        );
+    }
+
+    #[test]
+    fn ending() {
+        let xml = r#"
+            <barline location="left">
+                <ending number="1, 2" type="start">1.-2.</ending>
+            </barline>"#;
+        let barline: Barline = from_str(xml).unwrap();
+        let ending = barline.ending.unwrap();
+
+        assert_eq!(ending.r#type, EndingType::Start);
+        assert_eq!(ending.text, Some("1.-2.".to_string()));
+        assert_eq!(ending.passes(), vec![1, 2]);
+
+        let xml = r#"
+            <barline location="right">
+                <ending number="2" type="discontinue"/>
+            </barline>"#;
+        let barline: Barline = from_str(xml).unwrap();
+        let ending = barline.ending.unwrap();
+
+        assert_eq!(ending.r#type, EndingType::Discontinue);
+        assert_eq!(ending.text, None);
+        assert_eq!(ending.passes(), vec![2]);
     }
 }
@@ -1,6 +1,8 @@
 use super::{
     harmony::{HarmonyArrangement, Step},
+    interval::Interval,
     printable_value::PrintableValue,
+    writer::{Attrs, WriteXml, XmlWriter},
 };
 use serde::{Deserialize, Serialize};
 
@@ -18,3 +20,27 @@ pub struct Bass {
     #[serde(rename = "arrangement", default = "Option::default")]
     pub arrangement: Option<HarmonyArrangement>,
 }
+
+impl Bass {
+    /// Moves the bass by `interval`, keeping its spelling correct and its formatting.
+    pub fn transpose(&mut self, interval: &Interval) {
+        let alter = self.alter.as_ref().map_or(0.0, |a| a.content);
+        let (step, alter) = self.step.content.transpose(alter, interval);
+        self.step.content = step;
+        if alter != 0.0 || self.alter.is_some() {
+            self.alter
+                .get_or_insert_with(PrintableValue::default)
+                .content = alter;
+        }
+    }
+}
+
+impl WriteXml for Bass {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new().opt("arrangement", &self.arrangement));
+        w.opt_element("bass-separator", &self.separator);
+        w.element("bass-step", &self.step);
+        w.opt_element("bass-alter", &self.alter);
+        w.end(name);
+    }
+}
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumString;
+
+use super::writer::{Attrs, WriteXml, XmlWriter};
+use crate::prelude::*;
+use std::str::FromStr;
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/data-types/beam-value/
+#[derive(Debug, EnumString, PartialEq, Serialize, Deserialize, Default, Clone)]
+pub enum BeamValue {
+    #[strum(serialize = "begin")]
+    #[serde(rename = "begin")]
+    #[default]
+    Begin,
+
+    #[strum(serialize = "continue")]
+    #[serde(rename = "continue")]
+    Continue,
+
+    #[strum(serialize = "end")]
+    #[serde(rename = "end")]
+    End,
+
+    #[strum(serialize = "forward hook")]
+    #[serde(rename = "forward hook")]
+    ForwardHook,
+
+    #[strum(serialize = "backward hook")]
+    #[serde(rename = "backward hook")]
+    BackwardHook,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/beam/
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone)]
+pub struct Beam {
+    #[serde(rename = "$value", default = "BeamValue::default")]
+    pub content: BeamValue,
+
+    /// Beam level, 1 for the eighth note beam, 2 for sixteenths and so on.
+    #[serde(default = "Option::default")]
+    pub number: Option<u8>,
+
+    #[serde(default = "Option::default")]
+    pub color: Option<String>,
+}
+
+impl Beam {
+    pub fn level(&self) -> u8 {
+        self.number.unwrap_or(1)
+    }
+}
+
+impl WriteXml for Beam {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .opt("number", &self.number)
+            .opt("color", &self.color);
+        w.text_element_with(name, attrs, &self.content);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Beam, BeamValue};
+    use serde_xml_rs::from_str;
+
+    #[test]
+    fn beam() {
+        let item: Beam = from_str(r#"<beam number="2">backward hook</beam>"#).unwrap();
+
+        assert_eq!(item.content, BeamValue::BackwardHook);
+        assert_eq!(item.level(), 2);
+
+        let item: Beam = from_str(r#"<beam>continue</beam>"#).unwrap();
+
+        assert_eq!(item.content, BeamValue::Continue);
+        assert_eq!(item.level(), 1);
+    }
+}
@@ -0,0 +1,459 @@
+use super::{
+    frame::{Barre, FirstFret, Frame, FrameNote},
+    harmony::Harmony,
+    interval::Interval,
+    note::StartStop,
+    printable_value::{LeftRight, PrintableValue},
+    staff_tuning::StaffTuning,
+    theory::PitchClassSet,
+    writer::{Attrs, XmlWriter},
+};
+
+/// Highest fret the generator uses.
+const MAX_FRET: u8 = 12;
+
+/// Most frets one hand covers, counted from the lowest fretted note.
+const HAND_SPAN: u8 = 3;
+
+const FINGERS: usize = 4;
+
+/// Highest fret played together with open strings.
+const OPEN_POSITION: u8 = 4;
+
+/// Diagram layout in SVG user units.
+const STRING_SPACING: f32 = 12.0;
+const FRET_SPACING: f32 = 14.0;
+const MARGIN: f32 = 20.0;
+const DOT_RADIUS: f32 = 4.5;
+
+/// A fret for each string from the lowest, `None` for strings not played.
+type Fingering = Vec<Option<u8>>;
+
+/// How a fingering is played, if it can be.
+struct Grip {
+    /// The fret and the lowest and highest strings of a barre, counted from the
+    /// lowest string.
+    barre: Option<(u8, usize, usize)>,
+    cost: u32,
+}
+
+impl Grip {
+    /// Checks that the fingering plays every required pitch class with the bass as
+    /// the lowest sounding note, muting only strings below the played ones, with at
+    /// most four fingers where a barre counts as one, and with open strings only in
+    /// the first frets. Lower positions, fewer muted strings, fewer fingers and
+    /// smaller stretches cost less, and a barre costs less than a finger. `open`
+    /// holds the MIDI note number of each open string.
+    fn new(frets: &Fingering, open: &[u8], bass: u8, required: &PitchClassSet) -> Option<Grip> {
+        let played: Vec<usize> = (0..frets.len()).filter(|s| frets[*s].is_some()).collect();
+        let (first, last) = (*played.first()?, *played.last()?);
+        let contiguous = played.len() == last - first + 1 && last == frets.len() - 1;
+        if played.len() < 3.min(frets.len()) || !contiguous {
+            return None;
+        }
+        let sounding = |s: usize| open[s] + frets[s].unwrap_or(0);
+        let class = |s: usize| sounding(s) % 12;
+        let lowest_sounding = played.iter().copied().min_by_key(|s| sounding(*s))?;
+        if class(lowest_sounding) != bass {
+            return None;
+        }
+        let classes: PitchClassSet = played.iter().map(|s| class(*s)).collect();
+        if required.iter().any(|pc| !classes.contains(pc)) {
+            return None;
+        }
+
+        let fretted: Vec<usize> = played
+            .iter()
+            .copied()
+            .filter(|s| frets[*s] > Some(0))
+            .collect();
+        let lowest = fretted.iter().filter_map(|s| frets[*s]).min().unwrap_or(0);
+        let highest = fretted.iter().filter_map(|s| frets[*s]).max().unwrap_or(0);
+        let at_lowest: Vec<usize> = fretted
+            .iter()
+            .copied()
+            .filter(|s| frets[*s] == Some(lowest))
+            .collect();
+
+        let barre = match (at_lowest.first(), at_lowest.last()) {
+            (Some(&low), Some(&high)) if low != high => {
+                let covered = (low..=high).all(|s| frets[s].is_none_or(|f| f >= lowest));
+                covered.then_some((lowest, low, high))
+            }
+            _ => None,
+        };
+        let fingers = match barre {
+            Some(_) => 1 + fretted.len() - at_lowest.len(),
+            None => fretted.len(),
+        };
+        if fingers > FINGERS {
+            return None;
+        }
+
+        let open_strings = played.len() - fretted.len();
+        if open_strings > 0 && highest > OPEN_POSITION {
+            return None;
+        }
+
+        let muted = frets.len() - played.len();
+        let fingering = 2 * fingers as u32 - barre.map_or(0, |_| 1);
+        let stretch = 2 * (highest - lowest) as u32;
+        let cost = 3 * muted as u32 + lowest as u32 + fingering + stretch;
+        Some(Grip { barre, cost })
+    }
+}
+
+/// Tries every fingering of the strings from `string` up and keeps the cheapest.
+fn search(
+    frets: &mut Fingering,
+    string: usize,
+    options: &[Vec<Option<u8>>],
+    check: &dyn Fn(&Fingering) -> Option<Grip>,
+    best: &mut Option<(Fingering, Grip)>,
+) {
+    if string == options.len() {
+        if let Some(grip) = check(frets) {
+            if best.as_ref().is_none_or(|(_, b)| grip.cost < b.cost) {
+                *best = Some((frets.clone(), grip));
+            }
+        }
+        return;
+    }
+    for option in &options[string] {
+        let fretted = frets
+            .iter()
+            .chain([option])
+            .filter_map(|f| *f)
+            .filter(|f| *f > 0);
+        let (low, high) = fretted.fold((u8::MAX, 0), |(l, h), f| (l.min(f), h.max(f)));
+        if high > 0 && high - low > HAND_SPAN {
+            continue;
+        }
+        frets.push(*option);
+        search(frets, string + 1, options, check, best);
+        frets.pop();
+    }
+}
+
+impl Harmony {
+    /// A playable chord diagram for strings tuned to `tuning`, with the bass or the
+    /// root as the lowest sounding note, which for re-entrant tunings like the
+    /// ukulele's need not be on the lowest line. Every chord tone is played, except the
+    /// fifth of chords with four or more notes. `None` if no fingering within the
+    /// first twelve frets works.
+    pub fn frame(&self, tuning: &[StaffTuning]) -> Option<Frame> {
+        let mut tuning = tuning.to_vec();
+        tuning.sort_by_key(|t| t.line);
+        let open: Vec<u8> = tuning
+            .iter()
+            .map(|t| t.pitch().midi_number().round() as u8)
+            .collect();
+
+        let root = self.root()?;
+        let names = self.pitch_names();
+        let bass = self.bass().unwrap_or_else(|| root.clone()).pitch_class();
+        let fifth = root.transpose(&Interval::new(4, 7)).pitch_class();
+        let required: PitchClassSet = names
+            .iter()
+            .map(|name| name.pitch_class())
+            .filter(|pc| names.len() < 4 || *pc != fifth || *pc == bass)
+            .chain([bass])
+            .collect();
+        if required.len() > tuning.len() {
+            return None;
+        }
+        let allowed = self.pitch_classes();
+
+        let options: Vec<Vec<Option<u8>>> = open
+            .iter()
+            .map(|open| {
+                let frets = (0..=MAX_FRET).filter(|f| allowed.contains((open + f) % 12));
+                [None].into_iter().chain(frets.map(Some)).collect()
+            })
+            .collect();
+        let check = |frets: &Fingering| Grip::new(frets, &open, bass, &required);
+        let mut best = None;
+        search(&mut vec![], 0, &options, &check, &mut best);
+        let (frets, grip) = best?;
+
+        let strings = frets.len();
+        let used = frets.iter().flatten().filter(|f| **f > 0);
+        let (lowest, highest) = used.fold((u8::MAX, 0), |(l, h), f| (l.min(*f), h.max(*f)));
+        let first_fret = (highest > 4).then(|| FirstFret {
+            content: lowest,
+            ..FirstFret::default()
+        });
+
+        let frame_notes = frets
+            .iter()
+            .enumerate()
+            .filter_map(|(s, fret)| fret.map(|f| (s, f)))
+            .map(|(s, fret)| {
+                let barre = grip.barre.and_then(|(f, low, high)| match s {
+                    _ if f != fret => None,
+                    s if s == low => Some(StartStop::Start),
+                    s if s == high => Some(StartStop::Stop),
+                    _ => None,
+                });
+                FrameNote {
+                    string: PrintableValue {
+                        content: (strings - s) as u8,
+                        ..PrintableValue::default()
+                    },
+                    fret: PrintableValue {
+                        content: fret,
+                        ..PrintableValue::default()
+                    },
+                    fingering: None,
+                    barre: barre.map(|r#type| Barre {
+                        r#type,
+                        color: None,
+                    }),
+                }
+            })
+            .collect();
+
+        Some(Frame {
+            frame_strings: strings as u8,
+            frame_frets: 4.max(highest.saturating_sub(lowest) + 1),
+            first_fret,
+            frame_notes,
+            ..Frame::default()
+        })
+    }
+}
+
+impl Frame {
+    /// The frets of each string from the highest, `None` for strings not played.
+    pub fn frets(&self) -> Vec<Option<u8>> {
+        (1..=self.frame_strings)
+            .map(|string| {
+                self.frame_notes
+                    .iter()
+                    .find(|n| n.string.content == string)
+                    .map(|n| n.fret.content)
+            })
+            .collect()
+    }
+
+    /// The diagram as a standalone SVG image, with the lowest string on the left.
+    /// The nut is drawn when the diagram starts at the first fret.
+    pub fn to_svg(&self) -> String {
+        let strings = self.frame_strings.max(1);
+        let frets = self.frame_frets.max(1);
+        let first = self.first_fret.as_ref().map_or(1, |f| f.content.max(1));
+        let width = 2.0 * MARGIN + (strings - 1) as f32 * STRING_SPACING;
+        let height = 2.0 * MARGIN + frets as f32 * FRET_SPACING;
+        let x = |string: u8| MARGIN + strings.saturating_sub(string) as f32 * STRING_SPACING;
+        let y =
+            |fret: u8| MARGIN + fret.saturating_add(1).saturating_sub(first) as f32 * FRET_SPACING;
+        // Notes off the strings or below the frets drawn are left out.
+        let shown = |string: u8, fret: u8| {
+            (1..=strings).contains(&string)
+                && (fret == 0 || (first..first.saturating_add(frets)).contains(&fret))
+        };
+        let dot = |fret: u8| y(fret) - FRET_SPACING / 2.0;
+        let color = self.color.clone().unwrap_or_else(|| "black".to_string());
+        let line = |w: &mut XmlWriter, (x1, y1, x2, y2): (f32, f32, f32, f32), width: f32| {
+            let attrs = Attrs::new()
+                .add("x1", &x1)
+                .add("y1", &y1)
+                .add("x2", &x2)
+                .add("y2", &y2)
+                .add("stroke", &color)
+                .add("stroke-width", &width)
+                .add("stroke-linecap", "round");
+            w.empty("line", attrs);
+        };
+
+        let mut w = XmlWriter::new();
+        w.start(
+            "svg",
+            Attrs::new()
+                .add("xmlns", "http://www.w3.org/2000/svg")
+                .add("width", &width)
+                .add("height", &height)
+                .add("viewBox", &format!("0 0 {width} {height}")),
+        );
+
+        for string in 1..=strings {
+            line(&mut w, (x(string), MARGIN, x(string), height - MARGIN), 1.0);
+        }
+        for fret in 0..=frets {
+            let top = MARGIN + fret as f32 * FRET_SPACING;
+            let thickness = if fret == 0 && first == 1 { 3.0 } else { 1.0 };
+            line(&mut w, (MARGIN, top, width - MARGIN, top), thickness);
+        }
+        if let Some(first_fret) = &self.first_fret {
+            let text = first_fret
+                .text
+                .clone()
+                .unwrap_or_else(|| first_fret.content.to_string());
+            let left = match first_fret.location {
+                Some(LeftRight::Right) => width - MARGIN / 2.0,
+                _ => MARGIN / 2.0,
+            };
+            let attrs = Attrs::new()
+                .add("x", &left)
+                .add("y", &(MARGIN + FRET_SPACING * 0.75))
+                .add("font-size", &10)
+                .add("text-anchor", "middle");
+            w.text_element_with("text", attrs, &text);
+        }
+
+        let unplayed = self.unplayed.as_deref().unwrap_or("x");
+        for (i, fret) in self.frets().iter().enumerate() {
+            let string = i as u8 + 1;
+            let above = MARGIN - DOT_RADIUS - 3.0;
+            match fret {
+                Some(0) => {
+                    let attrs = Attrs::new()
+                        .add("cx", &x(string))
+                        .add("cy", &above)
+                        .add("r", &(DOT_RADIUS - 1.0))
+                        .add("fill", "none")
+                        .add("stroke", &color);
+                    w.empty("circle", attrs);
+                }
+                None if !unplayed.is_empty() => {
+                    let attrs = Attrs::new()
+                        .add("x", &x(string))
+                        .add("y", &(above + 3.5))
+                        .add("font-size", &10)
+                        .add("text-anchor", "middle");
+                    w.text_element_with("text", attrs, unplayed);
+                }
+                _ => {}
+            }
+        }
+
+        let mut barre_start = None;
+        for note in &self.frame_notes {
+            let (string, fret) = (note.string.content, note.fret.content);
+            if !shown(string, fret) {
+                continue;
+            }
+            match note.barre.as_ref().map(|b| &b.r#type) {
+                Some(StartStop::Start) => barre_start = Some(string),
+                Some(StartStop::Stop) => {
+                    if let Some(start) = barre_start.take() {
+                        let y = dot(fret);
+                        line(&mut w, (x(start), y, x(string), y), 2.0 * DOT_RADIUS);
+                    }
+                }
+                None => {}
+            }
+            if fret > 0 {
+                let attrs = Attrs::new()
+                    .add("cx", &x(string))
+                    .add("cy", &dot(fret))
+                    .add("r", &DOT_RADIUS)
+                    .add("fill", &color);
+                w.empty("circle", attrs);
+            }
+            if let Some(fingering) = &note.fingering {
+                let attrs = Attrs::new()
+                    .add("x", &x(string))
+                    .add("y", &(height - MARGIN / 4.0))
+                    .add("font-size", &9)
+                    .add("text-anchor", "middle");
+                w.text_element_with("text", attrs, &fingering.content);
+            }
+        }
+
+        w.end("svg");
+        w.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::musicxml::frame::Frame;
+    use crate::musicxml::harmony::{Harmony, Step};
+    use crate::musicxml::staff_tuning::StaffTuning;
+    use serde_xml_rs::from_str;
+
+    /// Frets from the lowest string, like "x32010".
+    fn shape(symbol: &str, tuning: &[StaffTuning]) -> String {
+        let harmony: Harmony = symbol.parse().unwrap();
+        let frame = harmony.frame(tuning).unwrap();
+        frame
+            .frets()
+            .iter()
+            .rev()
+            .map(|f| f.map_or("x".to_string(), |f| f.to_string()))
+            .collect::<Vec<_>>()
+            .join("")
+    }
+
+    #[test]
+    fn generate() {
+        let guitar = StaffTuning::standard_guitar();
+        assert_eq!(shape("C", &guitar), "x32010");
+        assert_eq!(shape("E", &guitar), "022100");
+        assert_eq!(shape("G", &guitar), "320003");
+        assert_eq!(shape("D", &guitar), "xx0232");
+        assert_eq!(shape("Am", &guitar), "x02210");
+        assert_eq!(shape("E7", &guitar), "020100");
+        assert_eq!(shape("C/E", &guitar), "032010");
+
+        assert_eq!(shape("Bb", &guitar), "x13331");
+        assert_eq!(shape("Bm7b5", &guitar), "x20201");
+        let frame = "F".parse::<Harmony>().unwrap().frame(&guitar).unwrap();
+        assert_eq!(frame.first_fret, None);
+        let barre: Vec<_> = frame
+            .frame_notes
+            .iter()
+            .filter(|n| n.barre.is_some())
+            .collect();
+        assert_eq!(barre.len(), 2);
+
+        let frame = "Ab7".parse::<Harmony>().unwrap().frame(&guitar).unwrap();
+        assert!(frame.first_fret.is_some_and(|f| f.content == 4));
+
+        let ukulele: Vec<StaffTuning> = [(Step::G, 4), (Step::C, 4), (Step::E, 4), (Step::A, 4)]
+            .into_iter()
+            .enumerate()
+            .map(|(i, (step, octave))| StaffTuning::new(i as u8 + 1, step, octave))
+            .collect();
+        assert_eq!(shape("C", &ukulele), "0003");
+        assert!("C13".parse::<Harmony>().unwrap().frame(&ukulele).is_none());
+    }
+
+    #[test]
+    fn svg() {
+        let xml = r#"
+            <frame unplayed="x">
+                <frame-strings>6</frame-strings>
+                <frame-frets>4</frame-frets>
+                <first-fret>5</first-fret>
+                <frame-note><string>5</string><fret>5</fret><barre type="start"/></frame-note>
+                <frame-note><string>4</string><fret>7</fret></frame-note>
+                <frame-note><string>3</string><fret>7</fret></frame-note>
+                <frame-note><string>2</string><fret>7</fret></frame-note>
+                <frame-note>
+                    <string>1</string><fret>5</fret><fingering>1</fingering><barre type="stop"/>
+                </frame-note>
+            </frame>"#;
+        let frame: Frame = from_str(xml).unwrap();
+        let svg = frame.to_svg();
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert_eq!(svg.matches("<circle").count(), 5);
+        assert_eq!(svg.matches("<line").count(), 6 + 5 + 1);
+        assert!(svg.contains(">x</text>"));
+        assert!(svg.contains(">5</text>"));
+        assert!(svg.contains(">1</text>"));
+
+        let xml = r#"
+            <frame>
+                <frame-strings>4</frame-strings>
+                <frame-frets>4</frame-frets>
+                <frame-note><string>6</string><fret>2</fret></frame-note>
+                <frame-note><string>1</string><fret>255</fret></frame-note>
+                <frame-note><string>2</string><fret>1</fret></frame-note>
+            </frame>"#;
+        let frame: Frame = from_str(xml).unwrap();
+        assert_eq!(frame.to_svg().matches("<circle").count(), 1);
+    }
+}
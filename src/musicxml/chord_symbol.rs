@@ -0,0 +1,448 @@
+use super::{
+    bass::Bass,
+    degree::{Degree, DegreeTypeValue},
+    harmony::{Harmony, HarmonyItem, HarmonyKind, Step},
+    printable_value::PrintableValue,
+    root::Root,
+    theory::{PitchName, ScaleDegree},
+    yes_no::YesNo,
+};
+use crate::error::MusicXmlError;
+use std::fmt;
+use std::str::FromStr;
+
+/// Every kind, in the order of the MusicXML definition.
+const KINDS: [HarmonyKind; 33] = [
+    HarmonyKind::Major,
+    HarmonyKind::Minor,
+    HarmonyKind::Augmented,
+    HarmonyKind::Diminished,
+    HarmonyKind::Dominant,
+    HarmonyKind::MajorSeventh,
+    HarmonyKind::MinorSeventh,
+    HarmonyKind::DiminishedSeventh,
+    HarmonyKind::AugmentedSeventh,
+    HarmonyKind::HalfDiminishedSeventh,
+    HarmonyKind::MajorMinorSeventh,
+    HarmonyKind::MajorSixth,
+    HarmonyKind::MinorSixth,
+    HarmonyKind::DominantNinth,
+    HarmonyKind::MajorNinth,
+    HarmonyKind::MinorNinth,
+    HarmonyKind::Dominant11th,
+    HarmonyKind::Major11th,
+    HarmonyKind::Minor11th,
+    HarmonyKind::Dominant13th,
+    HarmonyKind::Major13th,
+    HarmonyKind::Minor13th,
+    HarmonyKind::SuspendedSecond,
+    HarmonyKind::SuspendedFourth,
+    HarmonyKind::Neapolitan,
+    HarmonyKind::Italian,
+    HarmonyKind::French,
+    HarmonyKind::German,
+    HarmonyKind::Pedal,
+    HarmonyKind::Power,
+    HarmonyKind::Tristan,
+    HarmonyKind::Other,
+    HarmonyKind::None,
+];
+
+/// Other common spellings understood when parsing.
+const ALIASES: [(&str, HarmonyKind); 6] = [
+    ("min", HarmonyKind::Minor),
+    ("aug", HarmonyKind::Augmented),
+    ("M7", HarmonyKind::MajorSeventh),
+    ("Δ", HarmonyKind::MajorSeventh),
+    ("ø", HarmonyKind::HalfDiminishedSeventh),
+    ("sus", HarmonyKind::SuspendedFourth),
+];
+
+const NO_CHORD: &str = "N.C.";
+
+impl HarmonyKind {
+    /// What follows the root in a chord symbol, like "m7" for a minor seventh chord,
+    /// or with `use_symbols` "-7" as in jazz notation.
+    pub fn abbreviation(&self, use_symbols: bool) -> &'static str {
+        let (text, symbol) = match self {
+            HarmonyKind::Major => ("", ""),
+            HarmonyKind::Minor => ("m", "-"),
+            HarmonyKind::Augmented => ("+", "+"),
+            HarmonyKind::Diminished => ("dim", "°"),
+            HarmonyKind::Dominant => ("7", "7"),
+            HarmonyKind::MajorSeventh => ("maj7", "Δ7"),
+            HarmonyKind::MinorSeventh => ("m7", "-7"),
+            HarmonyKind::DiminishedSeventh => ("dim7", "°7"),
+            HarmonyKind::AugmentedSeventh => ("+7", "+7"),
+            HarmonyKind::HalfDiminishedSeventh => ("m7b5", "ø7"),
+            HarmonyKind::MajorMinorSeventh => ("m(maj7)", "-Δ7"),
+            HarmonyKind::MajorSixth => ("6", "6"),
+            HarmonyKind::MinorSixth => ("m6", "-6"),
+            HarmonyKind::DominantNinth => ("9", "9"),
+            HarmonyKind::MajorNinth => ("maj9", "Δ9"),
+            HarmonyKind::MinorNinth => ("m9", "-9"),
+            HarmonyKind::Dominant11th => ("11", "11"),
+            HarmonyKind::Major11th => ("maj11", "Δ11"),
+            HarmonyKind::Minor11th => ("m11", "-11"),
+            HarmonyKind::Dominant13th => ("13", "13"),
+            HarmonyKind::Major13th => ("maj13", "Δ13"),
+            HarmonyKind::Minor13th => ("m13", "-13"),
+            HarmonyKind::SuspendedSecond => ("sus2", "sus2"),
+            HarmonyKind::SuspendedFourth => ("sus4", "sus4"),
+            HarmonyKind::Neapolitan => ("N6", "N6"),
+            HarmonyKind::Italian => ("It+6", "It+6"),
+            HarmonyKind::French => ("Fr+6", "Fr+6"),
+            HarmonyKind::German => ("Ger+6", "Ger+6"),
+            HarmonyKind::Pedal => ("ped", "ped"),
+            HarmonyKind::Power => ("5", "5"),
+            HarmonyKind::Tristan => ("Tristan", "Tristan"),
+            HarmonyKind::Other => ("", ""),
+            HarmonyKind::None => (NO_CHORD, NO_CHORD),
+        };
+        if use_symbols {
+            symbol
+        } else {
+            text
+        }
+    }
+}
+
+/// Chord symbols like "Bb7(#11)/D". The kind is written as its `text` attribute
+/// when present, and with symbols like "-" and "Δ" when `use-symbols` is set. Added
+/// degrees without alteration are written as "add9", the others in parentheses.
+impl fmt::Display for Harmony {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = self.items.iter().find_map(|item| match item {
+            HarmonyItem::Kind(kind) => Some(kind),
+            _ => None,
+        });
+        if kind.is_some_and(|k| k.content == HarmonyKind::None) {
+            return write!(
+                f,
+                "{}",
+                kind.and_then(|k| k.text.as_deref()).unwrap_or(NO_CHORD)
+            );
+        }
+
+        if let Some(root) = self.root() {
+            write!(f, "{root}")?;
+        }
+        if let Some(kind) = kind {
+            let use_symbols = kind.use_symbols == Some(YesNo::Yes);
+            match &kind.text {
+                Some(text) => write!(f, "{text}")?,
+                None => write!(f, "{}", kind.content.abbreviation(use_symbols))?,
+            }
+        }
+
+        let mut enclosed = vec![];
+        for item in &self.items {
+            let HarmonyItem::Degree(degree) = item else {
+                continue;
+            };
+            let tone = ScaleDegree {
+                degree: degree.value.content,
+                alter: degree.alter.content,
+            };
+            match degree.degree_type.content {
+                DegreeTypeValue::Add if tone.alter == 0.0 => write!(f, "add{tone}")?,
+                DegreeTypeValue::Subtract => enclosed.push(format!("no{}", tone.degree)),
+                _ => enclosed.push(tone.to_string()),
+            }
+        }
+        if !enclosed.is_empty() {
+            write!(f, "({})", enclosed.join(","))?;
+        }
+
+        if let Some(bass) = self.bass() {
+            write!(f, "/{bass}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads chord symbols as written by `Display`, also accepting "♭" and "♯", a few
+/// other spellings of the kinds and alterations without parentheses, like "C7#9".
+/// Suffixes that aren't understood give a chord of kind `other` with the suffix as
+/// its text.
+impl FromStr for Harmony {
+    type Err = MusicXmlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+        if matches!(text, "N.C." | "NC" | "N.C") {
+            let kind = value(HarmonyKind::None);
+            return Ok(Harmony {
+                items: vec![HarmonyItem::Kind(kind)],
+            });
+        }
+
+        let (chord, bass) = match text.rsplit_once('/') {
+            Some((chord, bass)) => match pitch_name(bass) {
+                Some((name, "")) => (chord, Some(name)),
+                _ => (text, None),
+            },
+            None => (text, None),
+        };
+        let (root, suffix) = pitch_name(chord)
+            .ok_or_else(|| MusicXmlError::Generic(format!("chord symbol without root {s:?}")))?;
+
+        let (mut kind, rest) = kind(suffix);
+        let degrees = match degrees(&kind.content, rest) {
+            Some(degrees) => degrees,
+            None => {
+                kind = value(HarmonyKind::Other);
+                kind.text = Some(suffix.to_string());
+                vec![]
+            }
+        };
+
+        let mut items = vec![HarmonyItem::Root(Root {
+            step: value(root.step),
+            alter: (root.alter != 0.0).then(|| value(root.alter)),
+        })];
+        items.push(HarmonyItem::Kind(kind));
+        items.extend(degrees.into_iter().map(HarmonyItem::Degree));
+        if let Some(bass) = bass {
+            items.push(HarmonyItem::Bass(Bass {
+                step: value(bass.step),
+                alter: (bass.alter != 0.0).then(|| value(bass.alter)),
+                ..Bass::default()
+            }));
+        }
+        Ok(Harmony { items })
+    }
+}
+
+fn value<T: Default>(content: T) -> PrintableValue<T> {
+    PrintableValue {
+        content,
+        ..PrintableValue::default()
+    }
+}
+
+/// Semitones of the accidental at the start of `s`, and the rest.
+fn accidental(s: &str) -> Option<(f32, &str)> {
+    let c = s.chars().next()?;
+    let alter = match c {
+        '#' | '♯' => 1.0,
+        'b' | '♭' => -1.0,
+        _ => return None,
+    };
+    Some((alter, &s[c.len_utf8()..]))
+}
+
+/// The note name at the start of `s`, like "Bb" or "F#", and the rest.
+fn pitch_name(s: &str) -> Option<(PitchName, &str)> {
+    let step = Step::from_str(s.get(..1)?).ok()?;
+    let mut rest = &s[1..];
+    let mut alter = 0.0;
+    while let Some((semitones, after)) = accidental(rest) {
+        alter += semitones;
+        rest = after;
+    }
+    Some((PitchName::new(step, alter), rest))
+}
+
+/// The kind with the longest abbreviation at the start of `suffix`, and the rest.
+/// Symbols set `use-symbols` and other spellings are kept as the kind's text.
+fn kind(suffix: &str) -> (PrintableValue<HarmonyKind>, &str) {
+    let spellings = KINDS
+        .iter()
+        .filter(|k| **k != HarmonyKind::Other)
+        .flat_map(|k| [(k.abbreviation(false), k), (k.abbreviation(true), k)])
+        .chain(ALIASES.iter().map(|(alias, k)| (*alias, k)));
+    let (spelling, matched) = spellings
+        .filter(|(spelling, _)| suffix.starts_with(spelling))
+        .max_by_key(|(spelling, _)| spelling.len())
+        .unwrap_or(("", &HarmonyKind::Major));
+
+    let mut kind = value(matched.clone());
+    if spelling != matched.abbreviation(false) {
+        if spelling == matched.abbreviation(true) {
+            kind.use_symbols = Some(YesNo::Yes);
+        } else {
+            kind.text = Some(spelling.to_string());
+        }
+    }
+    (kind, &suffix[spelling.len()..])
+}
+
+/// The degrees written after the kind, like "add9", "(b9,#11)", "#5" or "(no5)".
+/// Altered degrees of tones the kind has alter them, others are added.
+fn degrees(kind: &HarmonyKind, text: &str) -> Option<Vec<Degree>> {
+    let tones: Vec<u8> = kind
+        .intervals()
+        .iter()
+        .map(|interval| interval.number() as u8)
+        .collect();
+    let mut degrees = vec![];
+    let mut rest = text.trim_start();
+
+    while !rest.is_empty() {
+        let enclosed = rest.starts_with('(');
+        let token_end = if enclosed {
+            rest.find(')')?
+        } else {
+            let digits = rest.find(|c: char| c.is_ascii_digit())?;
+            rest[digits..]
+                .find(|c: char| !c.is_ascii_digit())
+                .map_or(rest.len(), |i| digits + i)
+        };
+        let token = rest[..token_end].trim_start_matches('(');
+        rest = rest[token_end..].trim_start_matches(')').trim_start();
+
+        for item in token.split([',', ' ']).filter(|item| !item.is_empty()) {
+            let (degree_type, item) = if let Some(item) = item.strip_prefix("add") {
+                (DegreeTypeValue::Add, item)
+            } else if let Some(item) = item.strip_prefix("no").or(item.strip_prefix("omit")) {
+                (DegreeTypeValue::Subtract, item)
+            } else {
+                (DegreeTypeValue::Alter, item)
+            };
+            let (alter, number) = match accidental(item) {
+                Some((alter, number)) => (alter, number),
+                None => (0.0, item),
+            };
+            let number: u8 = number.parse().ok()?;
+            let degree_type = match degree_type {
+                DegreeTypeValue::Alter if !tones.contains(&number) => DegreeTypeValue::Add,
+                degree_type => degree_type,
+            };
+            degrees.push(Degree {
+                value: value(number),
+                alter: value(alter),
+                degree_type: value(degree_type),
+            });
+        }
+    }
+    Some(degrees)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::musicxml::degree::DegreeTypeValue;
+    use crate::musicxml::harmony::{Harmony, HarmonyItem, HarmonyKind, Step};
+    use crate::musicxml::measure::MeasureContent;
+    use crate::musicxml::score_partwise::ScorePartwise;
+    use crate::musicxml::yes_no::YesNo;
+    use serde_xml_rs::from_str;
+    use std::fs;
+
+    fn symbols(file: &str) -> Vec<String> {
+        let xml = fs::read_to_string(format!("resources/xml-test-files/{file}")).unwrap();
+        let item: ScorePartwise = from_str(&xml).unwrap();
+        item.parts[0]
+            .measures
+            .iter()
+            .flat_map(|m| &m.content)
+            .filter_map(|c| match c {
+                MeasureContent::Harmony(harmony) => Some(harmony.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn kind(harmony: &Harmony) -> HarmonyKind {
+        harmony
+            .items
+            .iter()
+            .find_map(|item| match item {
+                HarmonyItem::Kind(kind) => Some(kind.content.clone()),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn all_chord_types() {
+        let symbols = symbols("71f-AllChordTypes.xml");
+        assert_eq!(
+            symbols[..13],
+            [
+                "C", "Cm", "C+", "Cdim", "C7", "Cmaj7", "Cm7", "Cdim7", "C+7", "Cm7b5", "Cm(maj7)",
+                "C6", "Cm6"
+            ]
+        );
+        assert_eq!(symbols[30], "CTristan");
+
+        for symbol in &symbols[..31] {
+            let harmony: Harmony = symbol.parse().unwrap();
+            assert_eq!(&harmony.to_string(), symbol);
+        }
+        let kinds: Vec<HarmonyKind> = symbols[..31]
+            .iter()
+            .map(|s| kind(&s.parse().unwrap()))
+            .collect();
+        assert_eq!(kinds, super::KINDS[..31]);
+    }
+
+    #[test]
+    fn kind_text() {
+        assert_eq!(
+            symbols("71g-MultipleChordnames.xml"),
+            vec!["C", "F#m6", "Dm7", "G7"]
+        );
+        assert_eq!(
+            symbols("71a-Chordnames.xml")[..3],
+            ["C", "Cmaj7(#11)", "B7(#5,#9)"]
+        );
+    }
+
+    #[test]
+    fn use_symbols() {
+        let xml = r#"
+            <harmony>
+                <root><root-step>E</root-step><root-alter>-1</root-alter></root>
+                <kind use-symbols="yes">major-minor-seventh</kind>
+            </harmony>"#;
+        let item: Result<Harmony, _> = from_str(xml);
+        assert!(item.is_err());
+
+        let xml = xml.replace("major-minor-seventh", "major-minor");
+        let item: Harmony = from_str(&xml).unwrap();
+        assert_eq!(item.to_string(), "Eb-Δ7");
+
+        let item: Harmony = "C-7".parse().unwrap();
+        let HarmonyItem::Kind(kind) = &item.items[1] else {
+            panic!("Expected a kind.");
+        };
+        assert_eq!(kind.content, HarmonyKind::MinorSeventh);
+        assert_eq!(kind.use_symbols, Some(YesNo::Yes));
+        assert_eq!(item.to_string(), "C-7");
+    }
+
+    #[test]
+    fn parse() {
+        let item: Harmony = "Bb7(#11)/D".parse().unwrap();
+        assert_eq!(item.root().unwrap().to_string(), "Bb");
+        assert_eq!(kind(&item), HarmonyKind::Dominant);
+        assert_eq!(item.bass().unwrap().step, Step::D);
+        let HarmonyItem::Degree(degree) = &item.items[2] else {
+            panic!("Expected a degree.");
+        };
+        assert_eq!(degree.value.content, 11);
+        assert_eq!(degree.alter.content, 1.0);
+        assert_eq!(degree.degree_type.content, DegreeTypeValue::Add);
+        assert_eq!(item.to_string(), "Bb7(#11)/D");
+
+        let item: Harmony = "C7#5b9".parse().unwrap();
+        assert_eq!(item.to_string(), "C7(#5,b9)");
+        let item: Harmony = "F♯maj7add9(no5)".parse().unwrap();
+        assert_eq!(item.to_string(), "F#maj7add9(no5)");
+        let item: Harmony = "Amin".parse().unwrap();
+        assert_eq!(item.to_string(), "Amin");
+        assert_eq!(kind(&item), HarmonyKind::Minor);
+
+        let item: Harmony = "N.C.".parse().unwrap();
+        assert_eq!(kind(&item), HarmonyKind::None);
+        assert_eq!(item.to_string(), "N.C.");
+
+        let item: Harmony = "Cwhatever".parse().unwrap();
+        assert_eq!(kind(&item), HarmonyKind::Other);
+        assert_eq!(item.to_string(), "Cwhatever");
+
+        assert!("".parse::<Harmony>().is_err());
+        assert!("H7".parse::<Harmony>().is_err());
+    }
+}
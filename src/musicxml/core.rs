@@ -1,11 +1,28 @@
 use super::articulations::ArticulationType;
+use super::timeline::Rational;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use strum_macros::EnumString;
 pub type Duration = usize;
 
-#[derive(Debug, EnumString, PartialEq, Serialize, Deserialize, PartialOrd, Default)]
+#[derive(Debug, EnumString, PartialEq, Serialize, Deserialize, PartialOrd, Default, Clone)]
 pub enum DurationType {
+    #[strum(serialize = "1024th")]
+    #[serde(rename = "1024th")]
+    Onethousandtwentyfourth,
+
+    #[strum(serialize = "512th")]
+    #[serde(rename = "512th")]
+    Fivehundredtwelfth,
+
+    #[strum(serialize = "256th")]
+    #[serde(rename = "256th")]
+    Twohundredfiftysixth,
+
+    #[strum(serialize = "128th")]
+    #[serde(rename = "128th")]
+    Onehundredtwentyeighth,
+
     #[strum(serialize = "64th")]
     #[serde(rename = "64th")]
     #[default]
@@ -38,6 +55,36 @@ pub enum DurationType {
     #[strum(serialize = "breve")]
     #[serde(rename = "breve")]
     Breve,
+
+    #[strum(serialize = "long")]
+    #[serde(rename = "long")]
+    Long,
+
+    #[strum(serialize = "maxima")]
+    #[serde(rename = "maxima")]
+    Maxima,
+}
+
+impl DurationType {
+    /// The undotted length of the note value in whole notes.
+    pub fn whole_notes(&self) -> Rational {
+        match self {
+            DurationType::Onethousandtwentyfourth => Rational::new(1, 1024),
+            DurationType::Fivehundredtwelfth => Rational::new(1, 512),
+            DurationType::Twohundredfiftysixth => Rational::new(1, 256),
+            DurationType::Onehundredtwentyeighth => Rational::new(1, 128),
+            DurationType::Sixtyfourth => Rational::new(1, 64),
+            DurationType::Thirtysecond => Rational::new(1, 32),
+            DurationType::Sixteenth => Rational::new(1, 16),
+            DurationType::Eighth => Rational::new(1, 8),
+            DurationType::Quarter => Rational::new(1, 4),
+            DurationType::Half => Rational::new(1, 2),
+            DurationType::Whole => Rational::from_integer(1),
+            DurationType::Breve => Rational::from_integer(2),
+            DurationType::Long => Rational::from_integer(4),
+            DurationType::Maxima => Rational::from_integer(8),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Default)]
@@ -67,6 +114,10 @@ pub enum SyllabicType {
     #[serde(rename = "end")]
     End,
 
+    #[strum(serialize = "middle")]
+    #[serde(rename = "middle")]
+    Middle,
+
     #[strum(serialize = "single")]
     #[serde(rename = "single")]
     Single,
@@ -84,12 +135,26 @@ pub enum Placement {
     Below,
 }
 
+#[derive(Debug, EnumString, PartialEq, Clone, Serialize, Deserialize, PartialOrd, Default)]
+pub enum TopBottom {
+    #[strum(serialize = "top")]
+    #[serde(rename = "top")]
+    #[default]
+    Top,
+
+    #[strum(serialize = "bottom")]
+    #[serde(rename = "bottom")]
+    Bottom,
+}
+
 #[derive(Debug, EnumString, Serialize, Deserialize, PartialEq, PartialOrd)]
 pub enum RepeatDirection {
     #[strum(serialize = "forward")]
+    #[serde(rename = "forward")]
     Forward,
 
     #[strum(serialize = "backward")]
+    #[serde(rename = "backward")]
     Backward,
 }
 
@@ -100,8 +165,9 @@ pub enum RepeatDirection {
 #[cfg(test)]
 mod test_core {
     use crate::musicxml::articulations::{ArticulationMeta, ArticulationType, Articulations};
-    use crate::musicxml::core::Placement;
+    use crate::musicxml::core::{DurationType, Placement};
     use crate::musicxml::note::NotationType;
+    use crate::musicxml::timeline::Rational;
     use crate::prelude::*;
     use std::str::FromStr;
     use strum_macros::EnumString;
@@ -113,6 +179,14 @@ mod test_core {
             ArticulationType::from_str("tenuto").unwrap()
         );
         assert_eq!(Placement::Above, Placement::from_str("above").unwrap());
+        assert_eq!(
+            DurationType::from_str("128th").unwrap().whole_notes(),
+            Rational::new(1, 128)
+        );
+        assert_eq!(
+            DurationType::from_str("long").unwrap().whole_notes(),
+            Rational::from_integer(4)
+        );
         assert_eq!(
             NotationType::Articulations(Articulations {
                 articulations: vec![ArticulationType::Tenuto(ArticulationMeta::default(),)]
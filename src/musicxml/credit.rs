@@ -2,6 +2,7 @@ use crate::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use super::printable_value::PrintableValue;
+use super::writer::{Attrs, WriteXml, XmlWriter};
 
 // https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/credit/
 #[derive(Debug, Serialize, Deserialize)]
@@ -12,6 +13,15 @@ pub struct Credit {
     pub credit_words: Option<PrintableValue<String>>,
 }
 
+impl WriteXml for Credit {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.opt_text_element("credit-type", &self.credit_type);
+        w.opt_element("credit-words", &self.credit_words);
+        w.end(name);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Credit;
@@ -1,6 +1,13 @@
 use serde::{Deserialize, Serialize};
 
+use crate::musicxml::writer::{Attrs, WriteXml, XmlWriter};
 use crate::prelude::*;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Defaults {}
+
+impl WriteXml for Defaults {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.empty(name, Attrs::new());
+    }
+}
@@ -1,30 +1,69 @@
 use super::{
     harmony::{HarmonyArrangement, Step},
     printable_value::PrintableValue,
+    writer::{Attrs, WriteXml, XmlWriter},
 };
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Default)]
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/data-types/degree-type-value/
+#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Default, Clone)]
 pub enum DegreeTypeValue {
     #[serde(rename = "add")]
     #[default]
     Add,
 
-    #[serde(rename = "add")]
+    #[serde(rename = "alter")]
     Alter,
 
-    #[serde(rename = "add")]
+    #[serde(rename = "subtract")]
     Subtract,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Default)]
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/degree/
+/// A chord tone added to, altered in or left out of the chord of `<kind>`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Default, Clone)]
 pub struct Degree {
+    /// The chord tone as a number, like 9 or 11.
     #[serde(rename = "degree-value", default = "PrintableValue::default")]
-    pub value: PrintableValue<String>,
+    pub value: PrintableValue<u8>,
 
+    /// Semitones from the tone as it would be in a dominant chord for an added
+    /// degree, or from the tone already in the chord of `<kind>` for an altered one.
     #[serde(rename = "degree-alter", default = "PrintableValue::default")]
-    pub alter: PrintableValue<u8>,
+    pub alter: PrintableValue<f32>,
 
     #[serde(rename = "degree-type", default = "PrintableValue::default")]
-    pub degree_type: PrintableValue<f32>,
+    pub degree_type: PrintableValue<DegreeTypeValue>,
+}
+
+impl WriteXml for Degree {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.element("degree-value", &self.value);
+        w.element("degree-alter", &self.alter);
+        w.element("degree-type", &self.degree_type);
+        w.end(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Degree, DegreeTypeValue};
+    use serde_xml_rs::from_str;
+
+    #[test]
+    fn degree() {
+        let xml = r#"
+            <degree>
+                <degree-value>5</degree-value>
+                <degree-alter>-1</degree-alter>
+                <degree-type text="b">alter</degree-type>
+            </degree>"#;
+        let item: Degree = from_str(xml).unwrap();
+
+        assert_eq!(item.value.content, 5);
+        assert_eq!(item.alter.content, -1.0);
+        assert_eq!(item.degree_type.content, DegreeTypeValue::Alter);
+        assert_eq!(item.degree_type.text, Some("b".to_string()));
+    }
 }
@@ -1,14 +1,22 @@
-use serde::{Deserialize, Serialize};
+use serde::{de::IgnoredAny, Deserialize, Serialize};
 use strum_macros::EnumString;
 
 use crate::musicxml::core::Placement;
 use crate::prelude::*;
 use std::str::FromStr;
 
-use super::core::DurationType;
-use super::dynamics::Dynamics;
-use super::note::NotationType;
+use super::accordion_registration::AccordionRegistration;
+use super::dynamics::{write_dynamics, Dynamics};
+use super::harp_pedals::HarpPedals;
+use super::metronome::Metronome;
+use super::note::StartStop;
+use super::offset::Offset;
+use super::percussion::Percussion;
 use super::printable_value::PrintableValue;
+use super::scordatura::Scordatura;
+use super::sound::Sound;
+use super::start_stop_continue::StartStopContinue;
+use super::writer::{scalar, Attrs, WriteXml, XmlWriter};
 use super::yes_no::YesNo;
 
 #[derive(Debug, EnumString, Serialize, Deserialize, PartialEq, PartialOrd, Default)]
@@ -35,8 +43,221 @@ pub enum WedgeType {
     Continue,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd)]
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/data-types/up-down-stop-continue/
+#[derive(Debug, EnumString, Serialize, Deserialize, PartialEq, Default, Clone)]
+pub enum OctaveShiftType {
+    #[strum(serialize = "up")]
+    #[serde(rename = "up")]
+    #[default]
+    Up,
+
+    #[strum(serialize = "down")]
+    #[serde(rename = "down")]
+    Down,
+
+    #[strum(serialize = "stop")]
+    #[serde(rename = "stop")]
+    Stop,
+
+    #[strum(serialize = "continue")]
+    #[serde(rename = "continue")]
+    Continue,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/octave-shift/
+/// An 8va or 8vb line. The type says which way the notes are displayed, so an 8va
+/// line above the staff is `down`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Clone)]
+pub struct OctaveShift {
+    #[serde(default = "OctaveShiftType::default")]
+    pub r#type: OctaveShiftType,
+
+    #[serde(default = "Option::default")]
+    pub number: Option<u8>,
+
+    /// 8, 15 or 22.
+    #[serde(default = "Option::default")]
+    pub size: Option<u8>,
+}
+
+impl OctaveShift {
+    /// Octaves the notes under the line sound above their written pitch. Zero for
+    /// stop and continue.
+    pub fn sounding_octaves(&self) -> i8 {
+        let octaves = (self.size.unwrap_or(8).saturating_sub(1) / 7) as i8;
+        match self.r#type {
+            OctaveShiftType::Down => octaves,
+            OctaveShiftType::Up => -octaves,
+            OctaveShiftType::Stop | OctaveShiftType::Continue => 0,
+        }
+    }
+}
+
+#[derive(Debug, EnumString, Serialize, Deserialize, PartialEq, Default, Clone)]
+pub enum PedalType {
+    #[strum(serialize = "start")]
+    #[serde(rename = "start")]
+    #[default]
+    Start,
+
+    #[strum(serialize = "stop")]
+    #[serde(rename = "stop")]
+    Stop,
+
+    #[strum(serialize = "sostenuto")]
+    #[serde(rename = "sostenuto")]
+    Sostenuto,
+
+    #[strum(serialize = "change")]
+    #[serde(rename = "change")]
+    Change,
+
+    #[strum(serialize = "continue")]
+    #[serde(rename = "continue")]
+    Continue,
+
+    #[strum(serialize = "discontinue")]
+    #[serde(rename = "discontinue")]
+    Discontinue,
+
+    #[strum(serialize = "resume")]
+    #[serde(rename = "resume")]
+    Resume,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/pedal/
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Clone)]
+pub struct Pedal {
+    #[serde(default = "PedalType::default")]
+    pub r#type: PedalType,
+
+    #[serde(default = "Option::default")]
+    pub number: Option<u8>,
+
+    #[serde(default = "Option::default")]
+    pub line: Option<YesNo>,
+
+    #[serde(default = "Option::default")]
+    pub sign: Option<YesNo>,
+
+    #[serde(default = "Option::default")]
+    pub abbreviated: Option<YesNo>,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/dashes/
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct Dashes {
+    #[serde(default = "StartStopContinue::default")]
+    pub r#type: StartStopContinue,
+
+    #[serde(default = "Option::default")]
+    pub number: Option<u8>,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/data-types/line-end/
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub enum LineEnd {
+    #[serde(rename = "up")]
+    Up,
+
+    #[serde(rename = "down")]
+    Down,
+
+    #[serde(rename = "both")]
+    Both,
+
+    #[serde(rename = "arrow")]
+    Arrow,
+
+    #[serde(rename = "none")]
+    #[default]
+    None,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/bracket/
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct Bracket {
+    #[serde(default = "StartStopContinue::default")]
+    pub r#type: StartStopContinue,
+
+    #[serde(default = "Option::default")]
+    pub number: Option<u8>,
+
+    #[serde(rename = "line-end", default = "LineEnd::default")]
+    pub line_end: LineEnd,
+
+    #[serde(rename = "end-length", default = "Option::default")]
+    pub end_length: Option<f32>,
+
+    #[serde(rename = "line-type", default = "Option::default")]
+    pub line_type: Option<String>,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/string-mute/
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct StringMute {
+    /// on or off.
+    #[serde(default = "String::default")]
+    pub r#type: String,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/image/
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct Image {
+    #[serde(default = "String::default")]
+    pub source: String,
+
+    /// The MIME type of the image.
+    #[serde(default = "String::default")]
+    pub r#type: String,
+
+    #[serde(default = "Option::default")]
+    pub height: Option<f32>,
+
+    #[serde(default = "Option::default")]
+    pub width: Option<f32>,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/principal-voice/
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct PrincipalVoice {
+    #[serde(rename = "$value", default = "String::default")]
+    pub content: String,
+
+    #[serde(default = "StartStop::default")]
+    pub r#type: StartStop,
+
+    /// Hauptstimme, Nebenstimme, plain or none.
+    #[serde(default = "String::default")]
+    pub symbol: String,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/staff-divide/
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct StaffDivide {
+    /// down, up or up-down.
+    #[serde(default = "String::default")]
+    pub r#type: String,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/direction-type/
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub enum DirectionType {
+    #[serde(rename = "rehearsal")]
+    Rehearsal(PrintableValue<String>),
+
+    #[serde(rename = "segno")]
+    Segno,
+
+    #[serde(rename = "coda")]
+    Coda,
+
+    #[serde(rename = "words")]
+    Words(PrintableValue<String>),
+
+    #[serde(rename = "symbol")]
+    Symbol(PrintableValue<String>),
+
     #[serde(rename = "wedge")]
     Wedge {
         #[serde(default = "WedgeType::default")]
@@ -48,29 +269,60 @@ pub enum DirectionType {
     #[serde(rename = "dynamics")]
     Dynamic(PrintableValue<Dynamics>),
 
-    #[serde(rename = "words")]
-    Words(PrintableValue<String>),
+    #[serde(rename = "dashes")]
+    Dashes(Dashes),
+
+    #[serde(rename = "bracket")]
+    Bracket(Bracket),
+
+    #[serde(rename = "pedal")]
+    Pedal(Pedal),
 
     #[serde(rename = "metronome")]
-    Metronome {
-        #[serde(rename = "beat-unit", default = "DurationType::default")]
-        beat_unit: DurationType,
+    Metronome(Metronome),
 
-        #[serde(rename = "per-minute", default = "u8::default")]
-        per_minute: u8,
-    },
+    #[serde(rename = "octave-shift")]
+    OctaveShift(OctaveShift),
 
-    #[serde(rename = "rehersal")]
-    Rehersal { text: String },
+    #[serde(rename = "harp-pedals")]
+    HarpPedals(HarpPedals),
 
-    #[serde(rename = "coda")]
-    Coda,
+    #[serde(rename = "damp")]
+    Damp,
 
-    #[serde(rename = "segno")]
-    Segno,
+    #[serde(rename = "damp-all")]
+    DampAll,
+
+    #[serde(rename = "eyeglasses")]
+    Eyeglasses,
+
+    #[serde(rename = "string-mute")]
+    StringMute(StringMute),
+
+    #[serde(rename = "scordatura")]
+    Scordatura(Scordatura),
+
+    #[serde(rename = "image")]
+    Image(Image),
+
+    #[serde(rename = "principal-voice")]
+    PrincipalVoice(PrincipalVoice),
+
+    #[serde(rename = "percussion")]
+    Percussion(Percussion),
+
+    #[serde(rename = "accordion-registration")]
+    AccordionRegistration(AccordionRegistration),
+
+    #[serde(rename = "staff-divide")]
+    StaffDivide(StaffDivide),
+
+    #[serde(rename = "other-direction")]
+    OtherDirection(PrintableValue<String>),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(from = "DirectionXml")]
 pub struct Direction {
     #[serde(default = "usize::default")]
     pub position: usize,
@@ -84,20 +336,189 @@ pub struct Direction {
     #[serde(default = "Option::default")]
     pub placement: Option<Placement>,
 
-    #[serde(default = "YesNo::default")]
-    pub directive: YesNo,
+    #[serde(default = "Option::default")]
+    pub directive: Option<YesNo>,
+
+    #[serde(default = "Option::default")]
+    pub offset: Option<Offset>,
+
+    #[serde(default = "Option::default")]
+    pub sound: Option<Sound>,
+}
+
+#[derive(Deserialize)]
+enum DirectionChild {
+    #[serde(rename = "direction-type")]
+    DirectionType(DirectionType),
+    #[serde(rename = "offset")]
+    Offset(Offset),
+    #[serde(rename = "footnote")]
+    Footnote(IgnoredAny),
+    #[serde(rename = "level")]
+    Level(IgnoredAny),
+    #[serde(rename = "voice")]
+    Voice(IgnoredAny),
+    #[serde(rename = "staff")]
+    Staff(u8),
+    #[serde(rename = "sound")]
+    Sound(Sound),
+    #[serde(rename = "listening")]
+    Listening(IgnoredAny),
+}
+
+#[derive(Deserialize)]
+struct DirectionXml {
+    #[serde(default = "Option::default")]
+    placement: Option<Placement>,
+
+    #[serde(default = "Option::default")]
+    directive: Option<YesNo>,
+
+    #[serde(rename = "$value", default = "Vec::default")]
+    children: Vec<DirectionChild>,
+}
+
+impl From<DirectionXml> for Direction {
+    fn from(xml: DirectionXml) -> Direction {
+        let mut direction = Direction {
+            position: 0,
+            directiontypes: vec![],
+            staff: 0,
+            placement: xml.placement,
+            directive: xml.directive,
+            offset: None,
+            sound: None,
+        };
+
+        for child in xml.children {
+            match child {
+                DirectionChild::DirectionType(t) => direction.directiontypes.push(t),
+                DirectionChild::Offset(offset) => direction.offset = Some(offset),
+                DirectionChild::Staff(staff) => direction.staff = staff,
+                DirectionChild::Sound(sound) => direction.sound = Some(sound),
+                _ => {}
+            }
+        }
+
+        direction
+    }
+}
+
+impl WriteXml for DirectionType {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        match self {
+            DirectionType::Rehearsal(rehearsal) => w.element("rehearsal", rehearsal),
+            DirectionType::Segno => w.empty("segno", Attrs::new()),
+            DirectionType::Coda => w.empty("coda", Attrs::new()),
+            DirectionType::Words(words) => w.element("words", words),
+            DirectionType::Symbol(symbol) => w.element("symbol", symbol),
+            DirectionType::Wedge { r#type, number } => w.empty(
+                "wedge",
+                Attrs::new()
+                    .add("type", r#type)
+                    .add_if(*number != 0, "number", number),
+            ),
+            DirectionType::Dynamic(dynamics) => write_dynamics(w, dynamics),
+            DirectionType::Dashes(dashes) => w.empty(
+                "dashes",
+                Attrs::new()
+                    .add("type", &dashes.r#type)
+                    .opt("number", &dashes.number),
+            ),
+            DirectionType::Bracket(bracket) => w.empty(
+                "bracket",
+                Attrs::new()
+                    .add("type", &bracket.r#type)
+                    .opt("number", &bracket.number)
+                    .add("line-end", &bracket.line_end)
+                    .opt("end-length", &bracket.end_length)
+                    .opt("line-type", &bracket.line_type),
+            ),
+            DirectionType::Pedal(pedal) => w.empty(
+                "pedal",
+                Attrs::new()
+                    .add("type", &pedal.r#type)
+                    .opt("number", &pedal.number)
+                    .opt("line", &pedal.line)
+                    .opt("sign", &pedal.sign)
+                    .opt("abbreviated", &pedal.abbreviated),
+            ),
+            DirectionType::Metronome(metronome) => w.element("metronome", metronome),
+            DirectionType::OctaveShift(shift) => w.empty(
+                "octave-shift",
+                Attrs::new()
+                    .add("type", &shift.r#type)
+                    .opt("number", &shift.number)
+                    .opt("size", &shift.size),
+            ),
+            DirectionType::HarpPedals(pedals) => w.element("harp-pedals", pedals),
+            DirectionType::Damp => w.empty("damp", Attrs::new()),
+            DirectionType::DampAll => w.empty("damp-all", Attrs::new()),
+            DirectionType::Eyeglasses => w.empty("eyeglasses", Attrs::new()),
+            DirectionType::StringMute(mute) => {
+                w.empty("string-mute", Attrs::new().add("type", &mute.r#type))
+            }
+            DirectionType::Scordatura(scordatura) => w.element("scordatura", scordatura),
+            DirectionType::Image(image) => w.empty(
+                "image",
+                Attrs::new()
+                    .add("source", &image.source)
+                    .add("type", &image.r#type)
+                    .opt("height", &image.height)
+                    .opt("width", &image.width),
+            ),
+            DirectionType::PrincipalVoice(voice) => w.text_element_with(
+                "principal-voice",
+                Attrs::new()
+                    .add("type", &voice.r#type)
+                    .add("symbol", &voice.symbol),
+                &voice.content,
+            ),
+            DirectionType::Percussion(percussion) => w.element("percussion", percussion),
+            DirectionType::AccordionRegistration(registration) => {
+                w.element("accordion-registration", registration)
+            }
+            DirectionType::StaffDivide(divide) => {
+                w.empty("staff-divide", Attrs::new().add("type", &divide.r#type))
+            }
+            DirectionType::OtherDirection(other) => w.element("other-direction", other),
+        }
+        w.end(name);
+    }
+}
+
+impl WriteXml for Direction {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .opt("placement", &self.placement)
+            .opt("directive", &self.directive);
+
+        w.start(name, attrs);
+        w.elements("direction-type", &self.directiontypes);
+        w.opt_element("offset", &self.offset);
+        if self.staff != 0 {
+            w.text_element("staff", &self.staff);
+        }
+        w.opt_element("sound", &self.sound);
+        w.end(name);
+    }
 }
 
 #[cfg(test)]
 mod test_direction {
     use crate::musicxml::{
         core::DurationType,
-        direction::{Direction, DirectionType, WedgeType},
+        direction::{Bracket, Direction, DirectionType, LineEnd, PedalType, WedgeType},
+        measure::MeasureContent,
         printable_value::{FontStyle, FontWeight, PrintableValue, TextDirection},
+        score_partwise::ScorePartwise,
+        writer::XmlWriter,
     };
 
     use roxmltree::Document;
     use serde_xml_rs::from_str;
+    use std::fs;
 
     #[test]
     fn direction_1() {
@@ -153,12 +574,106 @@ mod test_direction {
         };
 
         assert_eq!(*dir_type0, DirectionType::Words(check));
-        assert_eq!(
-            *dir_type1,
-            DirectionType::Metronome {
-                beat_unit: DurationType::Quarter,
-                per_minute: 120
+        match dir_type1 {
+            DirectionType::Metronome(metronome) => {
+                assert_eq!(metronome.beat_unit.unit, DurationType::Quarter);
+                assert_eq!(metronome.per_minute.as_deref(), Some("120"));
+                assert_eq!(metronome.quarters_per_minute(), Some(120.0));
             }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn directions() {
+        let xml = fs::read_to_string("resources/xml-test-files/31a-Directions.xml").unwrap();
+        let item: ScorePartwise = from_str(&xml).unwrap();
+
+        let types: Vec<&DirectionType> = item.parts[0]
+            .measures
+            .iter()
+            .flat_map(|m| &m.content)
+            .filter_map(|c| match c {
+                MeasureContent::Direction(d) => Some(d),
+                _ => None,
+            })
+            .flat_map(|d| &d.directiontypes)
+            .collect();
+
+        let rehearsals: Vec<&str> = types
+            .iter()
+            .filter_map(|t| match t {
+                DirectionType::Rehearsal(r) => Some(r.content.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(rehearsals, vec!["A", "B", "Test", "Crc"]);
+
+        let harp = types.iter().find_map(|t| match t {
+            DirectionType::HarpPedals(h) => Some(h),
+            _ => None,
+        });
+        assert_eq!(harp.unwrap().pedal_tunings.len(), 7);
+
+        let scordatura = types.iter().find_map(|t| match t {
+            DirectionType::Scordatura(s) => Some(s),
+            _ => None,
+        });
+        let accord = &scordatura.unwrap().accords[0];
+        assert_eq!((accord.string, accord.tuning_octave), (3, 3));
+
+        let accordion = types.iter().find_map(|t| match t {
+            DirectionType::AccordionRegistration(a) => Some(a),
+            _ => None,
+        });
+        assert_eq!(accordion.unwrap().middle, Some(2));
+
+        assert!(types.contains(&&DirectionType::Eyeglasses));
+        assert!(types.contains(&&DirectionType::DampAll));
+        assert!(types
+            .iter()
+            .any(|t| matches!(t, DirectionType::Pedal(p) if p.r#type == PedalType::Change)));
+    }
+
+    #[test]
+    fn bracket_line_end() {
+        let item: DirectionType = from_str(r#"<bracket type="start" line-end="down"/>"#).unwrap();
+        assert!(matches!(&item, DirectionType::Bracket(b) if b.line_end == LineEnd::Down));
+
+        let mut w = XmlWriter::new();
+        w.element(
+            "direction-type",
+            &DirectionType::Bracket(Bracket::default()),
         );
+        assert!(w
+            .finish()
+            .contains(r#"<bracket type="start" line-end="none"/>"#));
+    }
+
+    #[test]
+    fn octave_shifts() {
+        let xml =
+            fs::read_to_string("resources/xml-test-files/33d-Spanners-OctaveShifts.xml").unwrap();
+        let item: ScorePartwise = from_str(&xml).unwrap();
+
+        let directions: Vec<&Direction> = item.parts[0]
+            .measures
+            .iter()
+            .flat_map(|m| &m.content)
+            .filter_map(|c| match c {
+                MeasureContent::Direction(d) => Some(d),
+                _ => None,
+            })
+            .collect();
+        let shifts: Vec<i8> = directions
+            .iter()
+            .flat_map(|d| &d.directiontypes)
+            .filter_map(|t| match t {
+                DirectionType::OctaveShift(s) => Some(s.sounding_octaves()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(shifts, vec![2, 0, -2, 0, 1, 0, -1, 0]);
+        assert!(directions.iter().any(|d| d.offset.is_some()));
     }
 }
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+use super::printable_value::PrintableValue;
+use super::writer::{scalar, Attrs, XmlWriter};
+
 #[derive(Debug, Serialize, Deserialize, Default, PartialEq, PartialOrd)]
 pub enum Dynamics {
     #[serde(rename = "p")]
@@ -82,5 +85,15 @@ pub enum Dynamics {
     SFZP,
 
     #[serde(rename = "other-dynamics")]
-    OtherDynamics,
+    OtherDynamics(String),
+}
+
+/// Writes `<dynamics>` with its one mark, as directions and notations both have it.
+pub fn write_dynamics(w: &mut XmlWriter, dynamics: &PrintableValue<Dynamics>) {
+    w.start("dynamics", dynamics.attrs());
+    match &dynamics.content {
+        Dynamics::OtherDynamics(text) => w.text_element("other-dynamics", text),
+        content => w.empty(&scalar(content), Attrs::new()),
+    }
+    w.end("dynamics");
 }
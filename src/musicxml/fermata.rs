@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+
+use super::writer::{Attrs, WriteXml, XmlWriter};
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/data-types/fermata-shape/
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone)]
+pub enum FermataShape {
+    /// Also what an empty `<fermata/>` means.
+    #[serde(rename = "normal")]
+    #[default]
+    Normal,
+
+    #[serde(rename = "angled")]
+    Angled,
+
+    #[serde(rename = "square")]
+    Square,
+
+    #[serde(rename = "double-angled")]
+    DoubleAngled,
+
+    #[serde(rename = "double-square")]
+    DoubleSquare,
+
+    #[serde(rename = "double-dot")]
+    DoubleDot,
+
+    #[serde(rename = "half-curve")]
+    HalfCurve,
+
+    #[serde(rename = "curlew")]
+    Curlew,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/data-types/upright-inverted/
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub enum UprightInverted {
+    #[serde(rename = "upright")]
+    Upright,
+
+    #[serde(rename = "inverted")]
+    Inverted,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/fermata/
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone)]
+pub struct Fermata {
+    #[serde(rename = "$value", default = "FermataShape::default")]
+    pub content: FermataShape,
+
+    #[serde(rename = "type", default = "Option::default")]
+    pub r#type: Option<UprightInverted>,
+
+    #[serde(default = "Option::default")]
+    pub color: Option<String>,
+
+    #[serde(rename = "default-x", default = "Option::default")]
+    pub default_x: Option<f32>,
+
+    #[serde(rename = "default-y", default = "Option::default")]
+    pub default_y: Option<f32>,
+
+    #[serde(rename = "relative-x", default = "Option::default")]
+    pub relative_x: Option<String>,
+
+    #[serde(rename = "relative-y", default = "Option::default")]
+    pub relative_y: Option<String>,
+}
+
+impl WriteXml for Fermata {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .opt("type", &self.r#type)
+            .opt("color", &self.color)
+            .opt("default-x", &self.default_x)
+            .opt("default-y", &self.default_y)
+            .opt("relative-x", &self.relative_x)
+            .opt("relative-y", &self.relative_y);
+        match self.content {
+            FermataShape::Normal => w.empty(name, attrs),
+            _ => w.text_element_with(name, attrs, &self.content),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Fermata, FermataShape, UprightInverted};
+    use serde_xml_rs::from_str;
+
+    #[test]
+    fn fermata() {
+        let item: Fermata = from_str(r#"<fermata type="inverted">angled</fermata>"#).unwrap();
+        assert_eq!(item.content, FermataShape::Angled);
+        assert_eq!(item.r#type, Some(UprightInverted::Inverted));
+
+        let item: Fermata = from_str(r#"<fermata type="upright"/>"#).unwrap();
+        assert_eq!(item.content, FermataShape::Normal);
+        assert_eq!(item.r#type, Some(UprightInverted::Upright));
+    }
+}
@@ -0,0 +1,373 @@
+use serde::{de::IgnoredAny, Deserialize, Serialize};
+
+use super::{
+    attribute_cursor::AttributeState,
+    attributes::Key,
+    core::{Duration, Placement},
+    harmony::Pitch,
+    interval::Interval,
+    lyric::Extend,
+    measure::MeasureContent,
+    part::Part,
+    theory::{PitchClassSet, PitchName},
+    writer::{Attrs, WriteXml, XmlWriter},
+    yes_no::YesNo,
+};
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/figure/
+/// One number of a figured bass with the accidental or slash that alters it. An
+/// accidental without a number stands for the third.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct Figure {
+    /// plus, sharp, flat, natural, double-sharp, flat-flat or sharp-sharp.
+    #[serde(default = "Option::default")]
+    pub prefix: Option<String>,
+
+    #[serde(rename = "figure-number", default = "Option::default")]
+    pub number: Option<String>,
+
+    /// Like the prefix, or slash, back-slash or vertical through the number.
+    #[serde(default = "Option::default")]
+    pub suffix: Option<String>,
+
+    #[serde(default = "Option::default")]
+    pub extend: Option<Extend>,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/figured-bass/
+/// Figures above the bass note that follows. The duration, if any, tells how long
+/// they last when the figures change during that note.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+#[serde(from = "FiguredBassXml")]
+pub struct FiguredBass {
+    pub figures: Vec<Figure>,
+    pub duration: Option<Duration>,
+    pub parentheses: Option<YesNo>,
+    pub print_object: Option<YesNo>,
+    pub placement: Option<Placement>,
+    pub default_x: Option<f32>,
+    pub default_y: Option<f32>,
+    pub color: Option<String>,
+}
+
+#[derive(Deserialize)]
+enum FiguredBassChild {
+    #[serde(rename = "figure")]
+    Figure(Figure),
+    #[serde(rename = "duration")]
+    Duration(Duration),
+    #[serde(rename = "footnote")]
+    Footnote(IgnoredAny),
+    #[serde(rename = "level")]
+    Level(IgnoredAny),
+}
+
+#[derive(Deserialize)]
+struct FiguredBassXml {
+    #[serde(default = "Option::default")]
+    parentheses: Option<YesNo>,
+
+    #[serde(rename = "print-object", default = "Option::default")]
+    print_object: Option<YesNo>,
+
+    #[serde(default = "Option::default")]
+    placement: Option<Placement>,
+
+    #[serde(rename = "default-x", default = "Option::default")]
+    default_x: Option<f32>,
+
+    #[serde(rename = "default-y", default = "Option::default")]
+    default_y: Option<f32>,
+
+    #[serde(default = "Option::default")]
+    color: Option<String>,
+
+    #[serde(rename = "$value", default = "Vec::default")]
+    children: Vec<FiguredBassChild>,
+}
+
+impl From<FiguredBassXml> for FiguredBass {
+    fn from(xml: FiguredBassXml) -> Self {
+        let mut item = FiguredBass {
+            parentheses: xml.parentheses,
+            print_object: xml.print_object,
+            placement: xml.placement,
+            default_x: xml.default_x,
+            default_y: xml.default_y,
+            color: xml.color,
+            ..FiguredBass::default()
+        };
+        for child in xml.children {
+            match child {
+                FiguredBassChild::Figure(figure) => item.figures.push(figure),
+                FiguredBassChild::Duration(d) => item.duration = Some(d),
+                _ => {}
+            }
+        }
+        item
+    }
+}
+
+impl Figure {
+    /// The interval number above the bass, 3 for a lone accidental.
+    pub fn value(&self) -> Option<u8> {
+        match self.number.as_deref().map(str::trim) {
+            Some(number) => number.parse().ok(),
+            None => self.accidental(0.0).map(|_| 3),
+        }
+    }
+
+    /// The alteration of the note, given its alteration in the key. Sharps and flats
+    /// raise or lower the note of the key by a semitone, as do slashes and plus signs.
+    /// Naturals and double accidentals set the alteration outright.
+    fn accidental(&self, key: f32) -> Option<f32> {
+        [&self.prefix, &self.suffix]
+            .into_iter()
+            .flatten()
+            .find_map(|affix| match affix.trim() {
+                "sharp" | "plus" | "slash" | "back-slash" | "vertical" => Some(key + 1.0),
+                "flat" => Some(key - 1.0),
+                "natural" => Some(0.0),
+                "double-sharp" | "sharp-sharp" => Some(2.0),
+                "flat-flat" => Some(-2.0),
+                _ => None,
+            })
+    }
+}
+
+impl FiguredBass {
+    /// The interval numbers in full, adding those a continuo player takes for granted:
+    /// no figures or a lone 3 or 5 is 5 3, 6 is 6 3, 7 is 7 5 3, 6 5 is 6 5 3, 4 3 is
+    /// 6 4 3, and 4 2 or 2 is 6 4 2. Highest first.
+    pub fn numbers(&self) -> Vec<u8> {
+        let mut numbers: Vec<u8> = self.figures.iter().filter_map(Figure::value).collect();
+        numbers.sort_unstable_by(|a, b| b.cmp(a));
+        numbers.dedup();
+
+        let implied: &[u8] = match numbers.as_slice() {
+            [] | [3] | [5] | [5, 3] => &[5, 3],
+            [6] => &[3],
+            [7] => &[5, 3],
+            [6, 5] => &[3],
+            [4, 3] => &[6],
+            [2] | [4, 2] => &[6, 4],
+            _ => &[],
+        };
+        for number in implied {
+            if !numbers.contains(number) {
+                numbers.push(*number);
+            }
+        }
+        numbers.sort_unstable_by(|a, b| b.cmp(a));
+        numbers
+    }
+
+    /// The pitch classes the figures stand for above `bass` in `key`, the bass
+    /// included. Each number counts steps of the key from the bass, so a 6 above E in
+    /// G major is C and above E in D major is C♯, unless its figure alters it.
+    pub fn pitch_classes(&self, bass: &Pitch, key: &Key) -> PitchClassSet {
+        let bass = PitchName::from(bass);
+        let mut classes = PitchClassSet::new();
+        classes.insert(bass.pitch_class());
+
+        for number in self.numbers() {
+            let steps = (number.max(1) as i32 - 1) % 7;
+            let step = bass.transpose(&Interval::new(steps, 0)).step;
+            let in_key = key.alter(&step);
+            let alter = self
+                .figures
+                .iter()
+                .find(|f| f.value() == Some(number))
+                .and_then(|f| f.accidental(in_key))
+                .unwrap_or(in_key);
+            classes.insert(PitchName::new(step, alter).pitch_class());
+        }
+        classes
+    }
+}
+
+impl Part {
+    /// Every figured bass of the part with its pitch classes, resolved against the
+    /// lowest note of the chord that follows it in the measure and the key in effect
+    /// there. Figures without a pitched note after them are left out.
+    pub fn figured_bass(&self) -> Vec<(&FiguredBass, PitchClassSet)> {
+        let mut state = AttributeState::default();
+        let mut resolved = vec![];
+
+        for measure in &self.measures {
+            let mut pending: Vec<&FiguredBass> = vec![];
+            let mut contents = measure.content.iter().peekable();
+            while let Some(content) = contents.next() {
+                match content {
+                    MeasureContent::Attributes(attributes) => state.apply(attributes),
+                    MeasureContent::FiguredBass(figured_bass) => pending.push(figured_bass),
+                    MeasureContent::Note(note) if !pending.is_empty() => {
+                        let mut lowest = note.pitch.as_ref().filter(|_| !note.rest);
+                        while let Some(MeasureContent::Note(next)) = contents.peek() {
+                            if !next.chord {
+                                break;
+                            }
+                            if let Some(pitch) = &next.pitch {
+                                if lowest.is_none_or(|l| pitch.midi_number() < l.midi_number()) {
+                                    lowest = Some(pitch);
+                                }
+                            }
+                            contents.next();
+                        }
+
+                        let key = state.key(note.staff.max(1)).cloned().unwrap_or_default();
+                        if let Some(bass) = lowest {
+                            for figured_bass in pending.drain(..) {
+                                resolved
+                                    .push((figured_bass, figured_bass.pitch_classes(bass, &key)));
+                            }
+                        }
+                        pending.clear();
+                    }
+                    _ => {}
+                }
+            }
+        }
+        resolved
+    }
+}
+
+impl WriteXml for Figure {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.opt_text_element("prefix", &self.prefix);
+        w.opt_text_element("figure-number", &self.number);
+        w.opt_text_element("suffix", &self.suffix);
+        w.opt_element("extend", &self.extend);
+        w.end(name);
+    }
+}
+
+impl WriteXml for FiguredBass {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .opt("parentheses", &self.parentheses)
+            .opt("print-object", &self.print_object)
+            .opt("placement", &self.placement)
+            .opt("default-x", &self.default_x)
+            .opt("default-y", &self.default_y)
+            .opt("color", &self.color);
+        w.start(name, attrs);
+        w.elements("figure", &self.figures);
+        w.opt_text_element("duration", &self.duration);
+        w.end(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FiguredBass;
+    use crate::musicxml::attributes::Key;
+    use crate::musicxml::harmony::{Pitch, Step};
+    use crate::musicxml::measure::MeasureContent;
+    use crate::musicxml::score_partwise::ScorePartwise;
+    use crate::musicxml::theory::PitchClassSet;
+    use crate::musicxml::yes_no::YesNo;
+    use serde_xml_rs::from_str;
+    use std::fs;
+
+    fn read(file: &str) -> ScorePartwise {
+        let xml = fs::read_to_string(format!("resources/xml-test-files/{file}")).unwrap();
+        from_str(&xml).unwrap()
+    }
+
+    fn classes(pitch_classes: &[u8]) -> PitchClassSet {
+        pitch_classes.iter().copied().collect()
+    }
+
+    #[test]
+    fn figured_bass() {
+        let item = read("74a-FiguredBass.xml");
+        let figured: Vec<&FiguredBass> = item.parts[0].measures[0]
+            .content
+            .iter()
+            .filter_map(|c| match c {
+                MeasureContent::FiguredBass(f) => Some(f),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(figured.len(), 5);
+
+        let accidentals = &figured[1];
+        assert_eq!(accidentals.duration, Some(6));
+        let prefixes: Vec<&str> = accidentals
+            .figures
+            .iter()
+            .filter_map(|f| f.prefix.as_deref())
+            .collect();
+        assert_eq!(prefixes, vec!["sharp", "flat", "natural"]);
+
+        assert_eq!(figured[2].parentheses, Some(YesNo::Yes));
+        assert_eq!(figured[3].figures[1].number, Some("127".to_string()));
+        assert_eq!(figured[3].figures[1].suffix, Some("slash".to_string()));
+        assert!(figured[4].figures.is_empty());
+
+        // All over G in C major.
+        let resolved = item.parts[0].figured_bass();
+        assert_eq!(resolved.len(), 5);
+        assert_eq!(resolved[0].1, classes(&[7, 11, 2]));
+        assert_eq!(resolved[1].1, classes(&[7, 8, 10, 2]));
+        assert_eq!(resolved[2].1, classes(&[7, 11, 4]));
+        assert_eq!(resolved[4].1, classes(&[7, 11, 2]));
+
+        let chord_names = read("46g-PickupMeasure-Chordnames-FiguredBass.xml");
+        let resolved = chord_names.parts[0].figured_bass();
+        assert_eq!(resolved.len(), 2);
+        assert!(resolved.iter().all(|(_, c)| *c == classes(&[0, 4, 7])));
+    }
+
+    fn resolve(figures: &str, step: Step, alter: Option<f32>, fifths: i8) -> PitchClassSet {
+        let item: FiguredBass =
+            from_str(&format!("<figured-bass>{figures}</figured-bass>")).unwrap();
+        let bass = Pitch {
+            step,
+            alter,
+            octave: 3,
+        };
+        let key = Key {
+            fifths,
+            ..Key::default()
+        };
+        item.pitch_classes(&bass, &key)
+    }
+
+    #[test]
+    fn realization() {
+        let number = |n: u8| format!("<figure><figure-number>{n}</figure-number></figure>");
+
+        assert_eq!(resolve(&number(6), Step::E, None, 1), classes(&[4, 7, 0]));
+        assert_eq!(resolve(&number(6), Step::E, None, 2), classes(&[4, 7, 1]));
+        assert_eq!(
+            resolve(&[number(6), number(5)].concat(), Step::A, None, 0),
+            classes(&[9, 0, 4, 5])
+        );
+        assert_eq!(
+            resolve(&[number(4), number(2)].concat(), Step::F, None, 0),
+            classes(&[5, 7, 11, 2])
+        );
+        assert_eq!(
+            resolve(&number(7), Step::G, None, 0),
+            classes(&[7, 11, 2, 5])
+        );
+
+        // A lone sharp raises the third: D F# A in G minor.
+        let sharp = "<figure><prefix>sharp</prefix></figure>";
+        assert_eq!(resolve(sharp, Step::D, None, -2), classes(&[2, 6, 9]));
+
+        // A slashed 6 over Bb in F major is G#.
+        let slashed = "<figure><figure-number>6</figure-number><suffix>slash</suffix></figure>";
+        assert_eq!(
+            resolve(slashed, Step::B, Some(-1.0), -1),
+            classes(&[10, 2, 8])
+        );
+
+        let item: FiguredBass =
+            from_str(&format!("<figured-bass>{}</figured-bass>", number(6))).unwrap();
+        assert_eq!(item.numbers(), vec![6, 3]);
+    }
+}
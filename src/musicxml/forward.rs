@@ -1,21 +1,38 @@
 use serde::{Deserialize, Serialize};
 
-use super::{core::Duration, level::Level, printable_value::PrintableValue};
+use super::{
+    core::Duration,
+    level::Level,
+    printable_value::PrintableValue,
+    writer::{Attrs, WriteXml, XmlWriter},
+};
 
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct Forward {
     #[serde(default = "Duration::default")]
-    duration: Duration,
+    pub duration: Duration,
 
     #[serde(default = "Option::default")]
-    footnote: Option<PrintableValue<String>>,
+    pub footnote: Option<PrintableValue<String>>,
 
     #[serde(default = "Option::default")]
-    level: Option<Level>,
+    pub level: Option<Level>,
 
     #[serde(default = "Option::default")]
-    voice: Option<String>,
+    pub voice: Option<String>,
 
     #[serde(default = "Option::default")]
-    staff: Option<u8>,
+    pub staff: Option<u8>,
+}
+
+impl WriteXml for Forward {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.text_element("duration", &self.duration);
+        w.opt_element("footnote", &self.footnote);
+        w.opt_element("level", &self.level);
+        w.opt_text_element("voice", &self.voice);
+        w.opt_text_element("staff", &self.staff);
+        w.end(name);
+    }
 }
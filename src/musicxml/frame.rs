@@ -2,35 +2,43 @@ use serde::{Deserialize, Serialize};
 
 use super::{
     note::StartStop,
-    printable_value::{LeftRight, PrintableValue},
+    printable_value::{LeftCenterRight, LeftRight, PrintableValue, Valign},
+    writer::{Attrs, WriteXml, XmlWriter},
 };
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Default)]
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/first-fret/
+/// The fret shown at the top of the diagram when it isn't the nut.
+#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Default, Clone)]
 pub struct FirstFret {
     #[serde(rename = "$value", default = "u8::default")]
     pub content: u8,
 
-    #[serde(rename = "location", default = "LeftRight::default")]
-    pub location: LeftRight,
+    #[serde(rename = "location", default = "Option::default")]
+    pub location: Option<LeftRight>,
 
-    #[serde(rename = "text", default = "String::default")]
-    pub text: String,
+    #[serde(rename = "text", default = "Option::default")]
+    pub text: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Default)]
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/barre/
+/// Starts a barre on the lowest string it covers and stops it on the highest.
+#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Default, Clone)]
 pub struct Barre {
-    #[serde(rename = "location", default = "StartStop::default")]
+    #[serde(rename = "type", default = "StartStop::default")]
     pub r#type: StartStop,
 
-    #[serde(rename = "text", default = "Option::default")]
+    #[serde(rename = "color", default = "Option::default")]
     pub color: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Default)]
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/frame-note/
+#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Default, Clone)]
 pub struct FrameNote {
+    /// Counted from 1 for the highest string.
     #[serde(rename = "string", default = "PrintableValue::default")]
-    pub content: PrintableValue<u8>,
+    pub string: PrintableValue<u8>,
 
+    /// 0 for an open string.
     #[serde(rename = "fret", default = "PrintableValue::default")]
     pub fret: PrintableValue<u8>,
 
@@ -38,29 +46,171 @@ pub struct FrameNote {
     pub fingering: Option<PrintableValue<String>>,
 
     #[serde(rename = "barre", default = "Option::default")]
-    pub barre: Option<PrintableValue<String>>,
+    pub barre: Option<Barre>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Default)]
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/frame/
+/// A chord diagram of a fretted instrument.
+#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Default, Clone)]
+#[serde(from = "FrameXml")]
 pub struct Frame {
-    #[serde(rename = "frame-strings", default = "u8::default")]
-    pub frame_strings: u8,
+    pub default_x: Option<f32>,
+    pub default_y: Option<f32>,
+    pub relative_x: Option<f32>,
+    pub relative_y: Option<f32>,
+    pub color: Option<String>,
+    pub halign: Option<LeftCenterRight>,
+    pub valign: Option<Valign>,
+    pub height: Option<f32>,
+    pub width: Option<f32>,
 
-    #[serde(rename = "frame-frets", default = "u8::default")]
-    pub frame_frets: u8,
+    /// What to show above strings that aren't played, "x" if missing.
+    pub unplayed: Option<String>,
 
-    #[serde(rename = "first-fret", default = "Option::default")]
+    pub id: Option<String>,
+    pub frame_strings: u8,
+    pub frame_frets: u8,
     pub first_fret: Option<FirstFret>,
-
-    #[serde(rename = "frame-note", default = "Vec::default")]
     pub frame_notes: Vec<FrameNote>,
 }
 
+#[derive(Deserialize)]
+enum FrameChild {
+    #[serde(rename = "frame-strings")]
+    FrameStrings(u8),
+    #[serde(rename = "frame-frets")]
+    FrameFrets(u8),
+    #[serde(rename = "first-fret")]
+    FirstFret(FirstFret),
+    #[serde(rename = "frame-note")]
+    FrameNote(Box<FrameNote>),
+}
+
+#[derive(Deserialize)]
+struct FrameXml {
+    #[serde(rename = "default-x", default = "Option::default")]
+    default_x: Option<f32>,
+
+    #[serde(rename = "default-y", default = "Option::default")]
+    default_y: Option<f32>,
+
+    #[serde(rename = "relative-x", default = "Option::default")]
+    relative_x: Option<f32>,
+
+    #[serde(rename = "relative-y", default = "Option::default")]
+    relative_y: Option<f32>,
+
+    #[serde(default = "Option::default")]
+    color: Option<String>,
+
+    #[serde(default = "Option::default")]
+    halign: Option<LeftCenterRight>,
+
+    #[serde(default = "Option::default")]
+    valign: Option<Valign>,
+
+    #[serde(default = "Option::default")]
+    height: Option<f32>,
+
+    #[serde(default = "Option::default")]
+    width: Option<f32>,
+
+    #[serde(default = "Option::default")]
+    unplayed: Option<String>,
+
+    #[serde(default = "Option::default")]
+    id: Option<String>,
+
+    #[serde(rename = "$value", default = "Vec::default")]
+    children: Vec<FrameChild>,
+}
+
+impl From<FrameXml> for Frame {
+    fn from(xml: FrameXml) -> Self {
+        let mut frame = Frame {
+            default_x: xml.default_x,
+            default_y: xml.default_y,
+            relative_x: xml.relative_x,
+            relative_y: xml.relative_y,
+            color: xml.color,
+            halign: xml.halign,
+            valign: xml.valign,
+            height: xml.height,
+            width: xml.width,
+            unplayed: xml.unplayed,
+            id: xml.id,
+            ..Frame::default()
+        };
+        for child in xml.children {
+            match child {
+                FrameChild::FrameStrings(n) => frame.frame_strings = n,
+                FrameChild::FrameFrets(n) => frame.frame_frets = n,
+                FrameChild::FirstFret(f) => frame.first_fret = Some(f),
+                FrameChild::FrameNote(n) => frame.frame_notes.push(*n),
+            }
+        }
+        frame
+    }
+}
+
+impl WriteXml for FirstFret {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .opt("text", &self.text)
+            .opt("location", &self.location);
+        w.text_element_with(name, attrs, &self.content);
+    }
+}
+
+impl WriteXml for FrameNote {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.element("string", &self.string);
+        w.element("fret", &self.fret);
+        w.opt_element("fingering", &self.fingering);
+        if let Some(barre) = &self.barre {
+            let attrs = Attrs::new()
+                .add("type", &barre.r#type)
+                .opt("color", &barre.color);
+            w.empty("barre", attrs);
+        }
+        w.end(name);
+    }
+}
+
+impl WriteXml for Frame {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .opt("default-x", &self.default_x)
+            .opt("default-y", &self.default_y)
+            .opt("relative-x", &self.relative_x)
+            .opt("relative-y", &self.relative_y)
+            .opt("color", &self.color)
+            .opt("halign", &self.halign)
+            .opt("valign", &self.valign)
+            .opt("height", &self.height)
+            .opt("width", &self.width)
+            .opt("unplayed", &self.unplayed)
+            .opt("id", &self.id);
+        w.start(name, attrs);
+        w.text_element("frame-strings", &self.frame_strings);
+        w.text_element("frame-frets", &self.frame_frets);
+        w.opt_element("first-fret", &self.first_fret);
+        w.elements("frame-note", &self.frame_notes);
+        w.end(name);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_xml_rs::from_str;
+    use std::fs;
 
-    use crate::musicxml::harmony::Harmony;
+    use super::Frame;
+    use crate::musicxml::harmony::{Harmony, HarmonyItem};
+    use crate::musicxml::measure::MeasureContent;
+    use crate::musicxml::note::StartStop;
+    use crate::musicxml::score_partwise::ScorePartwise;
 
     #[test]
     fn frame() {
@@ -101,5 +251,60 @@ mod tests {
         "#;
 
         let item: Harmony = from_str(xml).unwrap();
+        let HarmonyItem::Frame(frame) = &item.items[2] else {
+            panic!("Expected a frame.");
+        };
+        assert_eq!(frame.unplayed, Some("x".to_string()));
+        assert_eq!(frame.frame_notes[1].string.content, 4);
+        assert_eq!(frame.frame_notes[1].fret.content, 2);
+        assert_eq!(
+            frame.frets(),
+            vec![Some(0), Some(2), Some(2), Some(2), Some(0), None]
+        );
+
+        let xml = r#"
+            <frame>
+                <frame-strings>6</frame-strings>
+                <frame-frets>4</frame-frets>
+                <first-fret text="5fr" location="right">5</first-fret>
+                <frame-note><string>6</string><fret>5</fret><barre type="start"/></frame-note>
+                <frame-note><string>1</string><fret>5</fret><barre type="stop"/></frame-note>
+            </frame>"#;
+        let frame: Frame = from_str(xml).unwrap();
+        let first_fret = frame.first_fret.unwrap();
+        assert_eq!(
+            (first_fret.content, first_fret.text),
+            (5, Some("5fr".to_string()))
+        );
+        let barre = frame.frame_notes[1].barre.clone().unwrap();
+        assert_eq!(barre.r#type, StartStop::Stop);
+    }
+
+    #[test]
+    fn chords_frets() {
+        let xml = fs::read_to_string("resources/xml-test-files/71c-ChordsFrets.xml").unwrap();
+        let item: ScorePartwise = from_str(&xml).unwrap();
+        let frames: Vec<&Frame> = item.parts[0]
+            .measures
+            .iter()
+            .flat_map(|m| &m.content)
+            .filter_map(|c| match c {
+                MeasureContent::Harmony(harmony) => Some(harmony),
+                _ => None,
+            })
+            .flat_map(|h| &h.items)
+            .filter_map(|i| match i {
+                HarmonyItem::Frame(frame) => Some(frame),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(frames.len(), 8);
+        assert_eq!(frames[0].frame_strings, 6);
+        assert_eq!(
+            frames[0].frets(),
+            vec![Some(0), Some(1), Some(0), Some(2), Some(3), Some(0)]
+        );
+        assert_eq!(frames[1].first_fret.as_ref().unwrap().content, 2);
     }
 }
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+use super::writer::{Attrs, WriteXml, XmlWriter};
+use super::yes_no::YesNo;
+use crate::prelude::*;
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/grace/
+/// Marks a grace note. Grace notes have no `<duration>`; their playback length is
+/// taken from a neighbouring note through the steal-time attributes.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone)]
+pub struct Grace {
+    /// Percentage of the previous note's duration the grace note takes.
+    #[serde(rename = "steal-time-previous", default = "Option::default")]
+    pub steal_time_previous: Option<f32>,
+
+    /// Percentage of the following note's duration the grace note takes.
+    #[serde(rename = "steal-time-following", default = "Option::default")]
+    pub steal_time_following: Option<f32>,
+
+    /// Extra time added for the grace note, in divisions.
+    #[serde(rename = "make-time", default = "Option::default")]
+    pub make_time: Option<f32>,
+
+    /// Whether the grace note is an acciaccatura.
+    #[serde(default = "Option::default")]
+    pub slash: Option<YesNo>,
+}
+
+impl Grace {
+    pub fn is_slashed(&self) -> bool {
+        self.slash == Some(YesNo::Yes)
+    }
+}
+
+impl WriteXml for Grace {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .opt("steal-time-previous", &self.steal_time_previous)
+            .opt("steal-time-following", &self.steal_time_following)
+            .opt("make-time", &self.make_time)
+            .opt("slash", &self.slash);
+        w.empty(name, attrs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grace;
+    use serde_xml_rs::from_str;
+
+    #[test]
+    fn fractional_make_time() {
+        let item: Grace = from_str(r#"<grace make-time="2.5" slash="yes"/>"#).unwrap();
+
+        assert_eq!(item.make_time, Some(2.5));
+        assert!(item.is_slashed());
+    }
+}
@@ -0,0 +1,221 @@
+use super::{
+    beam::BeamValue,
+    note::Note,
+    timeline::{Rational, Timeline},
+};
+use std::collections::HashMap;
+
+/// Notes joined by ties into one sounding note. Untied notes form a chain of one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TieChain {
+    /// Indices into `Timeline::notes`, in onset order.
+    pub notes: Vec<usize>,
+
+    /// Onset of the first note, in whole notes.
+    pub onset: Rational,
+
+    /// Sounding duration from the first onset to the end of the last note.
+    pub duration: Rational,
+
+    /// False if the last note starts a tie that no following note stops.
+    pub terminated: bool,
+}
+
+impl TieChain {
+    pub fn is_tied(&self) -> bool {
+        self.notes.len() > 1
+    }
+
+    pub fn end(&self) -> Rational {
+        self.onset + self.duration
+    }
+}
+
+/// Notes sharing one beam level, in document order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BeamGroup {
+    pub number: u8,
+    pub voice: u8,
+    pub staff: u8,
+
+    /// Indices into `Timeline::notes`.
+    pub notes: Vec<usize>,
+}
+
+impl Timeline<'_> {
+    /// Groups the pitched and unpitched notes into tie chains, across measures. A tie
+    /// stop continues the open chain that ends exactly at its onset on the same
+    /// pitch; a stop without such a chain starts a new one.
+    pub fn tie_chains(&self) -> Vec<TieChain> {
+        let mut order: Vec<usize> = (0..self.notes.len())
+            .filter(|i| !self.notes[*i].event.rest)
+            .collect();
+        order.sort_by_key(|i| self.notes[*i].onset);
+
+        let mut chains: Vec<TieChain> = vec![];
+        let mut open: Vec<usize> = vec![];
+
+        for idx in order {
+            let timed = &self.notes[idx];
+            let note = timed.event;
+
+            let continued = if note.tie_stop() {
+                open.iter().position(|c| {
+                    let chain = &chains[*c];
+                    let last = self.notes[*chain.notes.last().unwrap()].event;
+                    chain.end() == timed.onset && same_sound(last, note)
+                })
+            } else {
+                None
+            };
+
+            let chain = match continued {
+                Some(pos) => {
+                    let c = open.remove(pos);
+                    let chain = &mut chains[c];
+                    chain.notes.push(idx);
+                    chain.duration = timed.end() - chain.onset;
+                    c
+                }
+                None => {
+                    chains.push(TieChain {
+                        notes: vec![idx],
+                        onset: timed.onset,
+                        duration: timed.duration,
+                        terminated: true,
+                    });
+                    chains.len() - 1
+                }
+            };
+
+            if note.tie_start() {
+                open.push(chain);
+            }
+        }
+
+        for c in open {
+            chains[c].terminated = false;
+        }
+        chains
+    }
+
+    /// The notes whose tie start is never stopped, like 33i's last measures.
+    pub fn unterminated_ties(&self) -> Vec<usize> {
+        self.tie_chains()
+            .iter()
+            .filter(|c| !c.terminated)
+            .filter_map(|c| c.notes.last().copied())
+            .collect()
+    }
+
+    /// Collects beamed notes per staff, voice and beam level, following begin,
+    /// continue and end across measures. Chord notes share the beam of their first
+    /// note and hooks don't join groups, so neither is included.
+    pub fn beam_groups(&self) -> Vec<BeamGroup> {
+        let mut groups: Vec<BeamGroup> = vec![];
+        let mut open: HashMap<(u8, u8, u8), usize> = HashMap::new();
+
+        for (idx, timed) in self.notes.iter().enumerate() {
+            let note = timed.event;
+            if note.chord {
+                continue;
+            }
+
+            for beam in &note.beams {
+                let key = (note.staff, note.voice, beam.level());
+                match beam.content {
+                    BeamValue::Begin => {
+                        groups.push(BeamGroup {
+                            number: beam.level(),
+                            voice: note.voice,
+                            staff: note.staff,
+                            notes: vec![idx],
+                        });
+                        open.insert(key, groups.len() - 1);
+                    }
+                    BeamValue::Continue | BeamValue::End => {
+                        match open.get(&key) {
+                            Some(g) => groups[*g].notes.push(idx),
+                            None => {
+                                groups.push(BeamGroup {
+                                    number: beam.level(),
+                                    voice: note.voice,
+                                    staff: note.staff,
+                                    notes: vec![idx],
+                                });
+                                open.insert(key, groups.len() - 1);
+                            }
+                        }
+                        if beam.content == BeamValue::End {
+                            open.remove(&key);
+                        }
+                    }
+                    BeamValue::ForwardHook | BeamValue::BackwardHook => {}
+                }
+            }
+        }
+
+        groups
+    }
+}
+
+fn same_sound(a: &Note, b: &Note) -> bool {
+    match (&a.pitch, &b.pitch, &a.unpitched, &b.unpitched) {
+        (Some(a), Some(b), _, _) => a.is_enharmonic(b),
+        (None, None, Some(x), Some(y)) => x == y && a.instruments == b.instruments,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rational;
+    use crate::musicxml::score_partwise::ScorePartwise;
+    use serde_xml_rs::from_str;
+    use std::fs;
+
+    #[test]
+    fn ties_not_ended() {
+        let xml = fs::read_to_string("resources/xml-test-files/33i-Ties-NotEnded.xml").unwrap();
+        let item: ScorePartwise = from_str(&xml).unwrap();
+        let timeline = item.parts[0].timeline();
+        let chains = timeline.tie_chains();
+
+        let notes: Vec<Vec<usize>> = chains.iter().map(|c| c.notes.clone()).collect();
+        assert_eq!(notes, vec![vec![0], vec![1], vec![2], vec![3, 4]]);
+
+        let terminated: Vec<bool> = chains.iter().map(|c| c.terminated).collect();
+        assert_eq!(terminated, vec![false, true, false, true]);
+
+        assert_eq!(chains[3].onset, Rational::from(3));
+        assert_eq!(chains[3].duration, Rational::from(2));
+        assert_eq!(timeline.unterminated_ties(), vec![0, 2]);
+    }
+
+    #[test]
+    fn secondary_beam_breaks() {
+        let xml =
+            fs::read_to_string("resources/xml-test-files/03e-Rhythm-SecondaryBeamBreaks.musicxml")
+                .unwrap();
+        let item: ScorePartwise = from_str(&xml).unwrap();
+        let timeline = item.parts[0].timeline();
+        let groups = timeline.beam_groups();
+
+        let primary: Vec<&Vec<usize>> = groups
+            .iter()
+            .filter(|g| g.number == 1)
+            .map(|g| &g.notes)
+            .collect();
+        assert_eq!(primary.len(), 4);
+        assert_eq!(*primary[0], (0..8).collect::<Vec<usize>>());
+
+        let third: Vec<&Vec<usize>> = groups
+            .iter()
+            .filter(|g| g.number == 3)
+            .map(|g| &g.notes)
+            .take(4)
+            .collect();
+        assert_eq!(*third[0], vec![0, 1]);
+        assert_eq!(*third[1], vec![2, 3]);
+    }
+}
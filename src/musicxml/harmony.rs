@@ -4,16 +4,96 @@ use std::str::FromStr;
 use strum_macros::EnumString;
 
 use super::{
-    bass::Bass, degree::Degree, frame::Frame, level::Level, numeral::Numeral,
-    printable_value::PrintableValue, root::Root, yes_no::YesNo,
+    bass::Bass,
+    degree::Degree,
+    frame::Frame,
+    interval::Interval,
+    level::Level,
+    numeral::Numeral,
+    printable_value::PrintableValue,
+    root::Root,
+    writer::{Attrs, WriteXml, XmlWriter},
+    yes_no::YesNo,
 };
 
+/// Reference tuning for [`Pitch::frequency`].
+pub const A4_FREQUENCY: f32 = 440.0;
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/pitch/
 #[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Clone)]
 pub struct Pitch {
     pub step: Step,
+
+    /// Chromatic alteration in semitones, e.g. `-1` for flat or `0.5` for a quarter-tone sharp.
+    #[serde(default = "Option::default")]
+    pub alter: Option<f32>,
+
     pub octave: u8,
 }
 
+impl Pitch {
+    /// MIDI note number with C4 = 60. Microtonal alterations give fractional numbers.
+    pub fn midi_number(&self) -> f32 {
+        (self.octave as f32 + 1.0) * 12.0 + self.step.semitones() as f32 + self.alter.unwrap_or(0.0)
+    }
+
+    /// Frequency in Hz in twelve-tone equal temperament with A4 tuned to `a4`.
+    pub fn frequency(&self, a4: f32) -> f32 {
+        a4 * 2f32.powf((self.midi_number() - 69.0) / 12.0)
+    }
+
+    /// Whether both pitches sound the same, e.g. F#4 and Gb4, or B#3 and C4.
+    pub fn is_enharmonic(&self, other: &Pitch) -> bool {
+        (self.midi_number() - other.midi_number()).abs() < 0.001
+    }
+
+    /// Spells a MIDI note number in the key with `fifths` sharps, or flats if negative.
+    /// Scale notes follow the key signature. Other notes are naturals where possible,
+    /// and sharps in sharp keys or flats in flat keys otherwise.
+    pub fn from_midi_number(number: u8, fifths: i8) -> Pitch {
+        const STEPS: [Step; 7] = [
+            Step::C,
+            Step::D,
+            Step::E,
+            Step::F,
+            Step::G,
+            Step::A,
+            Step::B,
+        ];
+        let pitch_class = (number % 12) as i8;
+        let lands_on =
+            |step: &Step, alter: i8| (step.semitones() as i8 + alter).rem_euclid(12) == pitch_class;
+
+        let (step, alter) = STEPS
+            .iter()
+            .map(|s| (s, s.key_signature_alter(fifths)))
+            .chain(STEPS.iter().map(|s| (s, 0)))
+            .chain(STEPS.iter().map(|s| (s, if fifths < 0 { -1 } else { 1 })))
+            .find(|(s, alter)| lands_on(s, *alter))
+            .expect("every pitch class is a natural or an altered natural");
+
+        let octave = (number as i32 - step.semitones() as i32 - alter as i32) / 12 - 1;
+        Pitch {
+            step: step.clone(),
+            alter: (alter != 0).then_some(alter as f32),
+            octave: octave.max(0) as u8,
+        }
+    }
+
+    /// The pitch `interval` above, or below for a negative interval. The step moves
+    /// by the diatonic steps and the alteration makes up the chromatic semitones, so
+    /// a major third up from B♭ is D.
+    pub fn transpose(&self, interval: &Interval) -> Pitch {
+        let (step, alter) = self.step.transpose(self.alter.unwrap_or(0.0), interval);
+        let octaves = (self.step.index() + interval.diatonic).div_euclid(7);
+        Pitch {
+            step,
+            alter: (alter != 0.0).then_some(alter),
+            octave: (self.octave as i32 + octaves).clamp(0, 9) as u8,
+        }
+    }
+}
+
 #[derive(Debug, EnumString, PartialEq, Serialize, Deserialize, Default, PartialOrd, Clone)]
 pub enum Step {
     #[default]
@@ -26,6 +106,80 @@ pub enum Step {
     G,
 }
 
+impl Step {
+    /// The alteration the key signature with `fifths` sharps, or flats if negative,
+    /// gives this step.
+    pub fn key_signature_alter(&self, fifths: i8) -> i8 {
+        const SHARPS: [Step; 7] = [
+            Step::F,
+            Step::C,
+            Step::G,
+            Step::D,
+            Step::A,
+            Step::E,
+            Step::B,
+        ];
+
+        let position = SHARPS.iter().position(|s| s == self).unwrap_or(0) as i8;
+        if fifths > 0 && position < fifths {
+            1
+        } else if fifths < 0 && 6 - position < -fifths {
+            -1
+        } else {
+            0
+        }
+    }
+
+    /// Position in the C major scale, from 0 for C to 6 for B.
+    pub fn index(&self) -> i32 {
+        match self {
+            Step::C => 0,
+            Step::D => 1,
+            Step::E => 2,
+            Step::F => 3,
+            Step::G => 4,
+            Step::A => 5,
+            Step::B => 6,
+        }
+    }
+
+    /// The step at `index` in the C major scale, wrapping around the octave.
+    pub fn from_index(index: i32) -> Step {
+        match index.rem_euclid(7) {
+            0 => Step::C,
+            1 => Step::D,
+            2 => Step::E,
+            3 => Step::F,
+            4 => Step::G,
+            5 => Step::A,
+            _ => Step::B,
+        }
+    }
+
+    /// The step and alteration `interval` above this step altered by `alter`,
+    /// ignoring octaves.
+    pub fn transpose(&self, alter: f32, interval: &Interval) -> (Step, f32) {
+        let index = self.index() + interval.diatonic;
+        let step = Step::from_index(index);
+        let natural = step.semitones() as i32 + 12 * index.div_euclid(7);
+        let target = self.semitones() as f32 + alter + interval.chromatic as f32;
+        (step, target - natural as f32)
+    }
+
+    /// Semitones above C of the natural note.
+    pub fn semitones(&self) -> u8 {
+        match self {
+            Step::C => 0,
+            Step::D => 2,
+            Step::E => 4,
+            Step::F => 5,
+            Step::G => 7,
+            Step::A => 9,
+            Step::B => 11,
+        }
+    }
+}
+
 #[derive(Debug, EnumString, PartialEq, Serialize, Deserialize, Default, PartialOrd, Clone)]
 pub enum HarmonyArrangement {
     #[serde(rename = "horizontal")]
@@ -39,6 +193,7 @@ pub enum HarmonyArrangement {
     Diagonal,
 }
 
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/data-types/kind-value/
 #[derive(Debug, EnumString, Serialize, Deserialize, PartialEq, PartialOrd, Default, Clone)]
 pub enum HarmonyKind {
     #[strum(serialize = "major")]
@@ -74,18 +229,26 @@ pub enum HarmonyKind {
     #[serde(rename = "diminished-seventh")]
     DiminishedSeventh,
 
-    #[strum(serialize = "half-diminished-seventh")]
-    #[serde(rename = "half-diminished-seventh")]
+    #[strum(serialize = "half-diminished")]
+    #[serde(rename = "half-diminished")]
     HalfDiminishedSeventh,
 
     #[strum(serialize = "augmented-seventh")]
     #[serde(rename = "augmented-seventh")]
     AugmentedSeventh,
 
-    #[strum(serialize = "major-minor-seventh")]
-    #[serde(rename = "major-minor-seventh")]
+    #[strum(serialize = "major-minor")]
+    #[serde(rename = "major-minor")]
     MajorMinorSeventh,
 
+    #[strum(serialize = "major-sixth")]
+    #[serde(rename = "major-sixth")]
+    MajorSixth,
+
+    #[strum(serialize = "minor-sixth")]
+    #[serde(rename = "minor-sixth")]
+    MinorSixth,
+
     #[strum(serialize = "dominant-ninth")]
     #[serde(rename = "dominant-ninth")]
     DominantNinth,
@@ -130,20 +293,20 @@ pub enum HarmonyKind {
     #[serde(rename = "suspended-fourth")]
     SuspendedFourth,
 
-    #[strum(serialize = "neapolitan")]
-    #[serde(rename = "neapolitan")]
+    #[strum(serialize = "Neapolitan")]
+    #[serde(rename = "Neapolitan")]
     Neapolitan,
 
-    #[strum(serialize = "italian")]
-    #[serde(rename = "italian")]
+    #[strum(serialize = "Italian")]
+    #[serde(rename = "Italian")]
     Italian,
 
-    #[strum(serialize = "french")]
-    #[serde(rename = "french")]
+    #[strum(serialize = "French")]
+    #[serde(rename = "French")]
     French,
 
-    #[strum(serialize = "german")]
-    #[serde(rename = "german")]
+    #[strum(serialize = "German")]
+    #[serde(rename = "German")]
     German,
 
     #[strum(serialize = "pedal")]
@@ -154,13 +317,18 @@ pub enum HarmonyKind {
     #[serde(rename = "power")]
     Power,
 
-    #[strum(serialize = "tristan")]
-    #[serde(rename = "tristan")]
+    #[strum(serialize = "Tristan")]
+    #[serde(rename = "Tristan")]
     Tristan,
 
     #[strum(serialize = "other")]
     #[serde(rename = "other")]
     Other,
+
+    /// No chord, as in "N.C.", for which the root is optional.
+    #[strum(serialize = "none")]
+    #[serde(rename = "none")]
+    None,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Default, Clone)]
@@ -214,10 +382,52 @@ pub struct Harmony {
     pub items: Vec<HarmonyItem>,
 }
 
+impl WriteXml for Pitch {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.text_element("step", &self.step);
+        w.opt_text_element("alter", &self.alter);
+        w.text_element("octave", &self.octave);
+        w.end(name);
+    }
+}
+
+impl WriteXml for HarmonyOffset {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.text_element_with(name, Attrs::new().opt("sound", &self.sound), &self.content);
+    }
+}
+
+impl WriteXml for HarmonyItem {
+    fn write_xml(&self, w: &mut XmlWriter, _name: &str) {
+        match self {
+            HarmonyItem::Root(root) => w.element("root", root),
+            HarmonyItem::Numeral(numeral) => w.element("numeral", numeral),
+            HarmonyItem::Kind(kind) => w.element("kind", kind),
+            HarmonyItem::Inversion(inversion) => w.element("inversion", inversion),
+            HarmonyItem::Bass(bass) => w.element("bass", bass),
+            HarmonyItem::Degree(degree) => w.element("degree", degree),
+            HarmonyItem::Frame(frame) => w.element("frame", frame),
+            HarmonyItem::Offset(offset) => w.element("offset", offset),
+            HarmonyItem::Footnote(footnote) => w.element("footnote", footnote),
+            HarmonyItem::Level(level) => w.element("level", level),
+            HarmonyItem::Staff(staff) => w.text_element("staff", staff),
+        }
+    }
+}
+
+impl WriteXml for Harmony {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.elements("", &self.items);
+        w.end(name);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::musicxml::{
-        harmony::{Harmony, HarmonyItem, HarmonyKind, Step},
+        harmony::{Harmony, HarmonyItem, HarmonyKind, Pitch, Step, A4_FREQUENCY},
         numeral::{self, Numeral},
         printable_value::LeftCenterRight,
     };
@@ -241,7 +451,7 @@ mod tests {
         match &item.items[0] {
             HarmonyItem::Root(r) => {
                 assert_eq!(r.step.content, Step::D);
-                assert_eq!(r.alter.clone().unwrap().content, 0);
+                assert_eq!(r.alter.clone().unwrap().content, 0.0);
             }
             _ => {
                 panic!("Expected a root.");
@@ -278,7 +488,7 @@ mod tests {
         match &item.items[0] {
             HarmonyItem::Root(r) => {
                 assert_eq!(r.step.content, Step::A);
-                assert_eq!(r.alter.clone().unwrap().content, 0);
+                assert_eq!(r.alter.clone().unwrap().content, 0.0);
             }
             _ => {
                 panic!("Expected a root.");
@@ -322,7 +532,7 @@ mod tests {
         match &item.items[0] {
             HarmonyItem::Root(r) => {
                 assert_eq!(r.step.content, Step::D);
-                assert_eq!(r.alter.clone().unwrap().content, 0);
+                assert_eq!(r.alter.clone().unwrap().content, 0.0);
             }
             _ => {
                 panic!("Expected a root.");
@@ -468,7 +678,7 @@ mod tests {
         match &item.items[0] {
             HarmonyItem::Root(root) => {
                 assert_eq!(root.step.content, Step::C);
-                assert_eq!(root.alter.clone().unwrap().content, 0);
+                assert_eq!(root.alter.clone().unwrap().content, 0.0);
             },
             _ => {
                 panic!("Expected different item");
@@ -477,4 +687,82 @@ mod tests {
 
 
     }
+
+    #[test]
+    fn pitch_alter() {
+        let xml = r#"
+            <pitch>
+                <step>F</step>
+                <alter>1</alter>
+                <octave>4</octave>
+            </pitch>"#;
+        let f_sharp: Pitch = from_str(xml).unwrap();
+        assert_eq!(f_sharp.alter, Some(1.0));
+        assert_eq!(f_sharp.midi_number(), 66.0);
+
+        let xml = r#"
+            <pitch>
+                <step>G</step>
+                <alter>-0.5</alter>
+                <octave>4</octave>
+            </pitch>"#;
+        let g_half_flat: Pitch = from_str(xml).unwrap();
+        assert_eq!(g_half_flat.midi_number(), 66.5);
+    }
+
+    #[test]
+    fn pitch_frequency() {
+        let a4 = Pitch {
+            step: Step::A,
+            alter: None,
+            octave: 4,
+        };
+        assert_eq!(a4.midi_number(), 69.0);
+        assert_eq!(a4.frequency(A4_FREQUENCY), 440.0);
+        assert_eq!(a4.frequency(415.0), 415.0);
+
+        let c4 = Pitch {
+            step: Step::C,
+            alter: None,
+            octave: 4,
+        };
+        assert!((c4.frequency(A4_FREQUENCY) - 261.626).abs() < 0.01);
+    }
+
+    #[test]
+    fn pitch_enharmonic() {
+        let pitch = |step, alter, octave| Pitch {
+            step,
+            alter,
+            octave,
+        };
+
+        assert!(pitch(Step::F, Some(1.0), 4).is_enharmonic(&pitch(Step::G, Some(-1.0), 4)));
+        assert!(pitch(Step::B, Some(1.0), 3).is_enharmonic(&pitch(Step::C, None, 4)));
+        assert!(!pitch(Step::B, Some(1.0), 4).is_enharmonic(&pitch(Step::C, None, 4)));
+        assert!(!pitch(Step::F, Some(0.5), 4).is_enharmonic(&pitch(Step::F, Some(1.0), 4)));
+    }
+
+    #[test]
+    fn pitch_from_midi_number() {
+        let pitch = |step, alter, octave| Pitch {
+            step,
+            alter,
+            octave,
+        };
+
+        assert_eq!(Pitch::from_midi_number(60, 0), pitch(Step::C, None, 4));
+        assert_eq!(Pitch::from_midi_number(61, 2), pitch(Step::C, Some(1.0), 4));
+        assert_eq!(
+            Pitch::from_midi_number(61, -3),
+            pitch(Step::D, Some(-1.0), 4)
+        );
+        assert_eq!(Pitch::from_midi_number(65, 1), pitch(Step::F, None, 4));
+        assert_eq!(Pitch::from_midi_number(70, 0), pitch(Step::A, Some(1.0), 4));
+        assert_eq!(Pitch::from_midi_number(60, 7), pitch(Step::B, Some(1.0), 3));
+        assert_eq!(
+            Pitch::from_midi_number(59, -7),
+            pitch(Step::C, Some(-1.0), 4)
+        );
+    }
 }
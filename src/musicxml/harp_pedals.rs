@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+use super::harmony::Step;
+use super::writer::{Attrs, WriteXml, XmlWriter};
+use crate::prelude::*;
+
+/// The alteration one harp pedal is set to.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone)]
+pub struct PedalTuning {
+    #[serde(rename = "pedal-step", default = "Step::default")]
+    pub pedal_step: Step,
+
+    #[serde(rename = "pedal-alter", default = "f32::default")]
+    pub pedal_alter: f32,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/harp-pedals/
+/// The pedal diagram of a harp, pedals in D C B | E F G A order.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone)]
+#[serde(from = "HarpPedalsXml")]
+pub struct HarpPedals {
+    pub pedal_tunings: Vec<PedalTuning>,
+}
+
+#[derive(Deserialize)]
+enum HarpPedalsChild {
+    #[serde(rename = "pedal-tuning")]
+    PedalTuning(PedalTuning),
+}
+
+#[derive(Deserialize)]
+struct HarpPedalsXml {
+    #[serde(rename = "$value", default = "Vec::default")]
+    children: Vec<HarpPedalsChild>,
+}
+
+impl From<HarpPedalsXml> for HarpPedals {
+    fn from(xml: HarpPedalsXml) -> Self {
+        HarpPedals {
+            pedal_tunings: xml
+                .children
+                .into_iter()
+                .map(|HarpPedalsChild::PedalTuning(t)| t)
+                .collect(),
+        }
+    }
+}
+
+impl WriteXml for PedalTuning {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.text_element("pedal-step", &self.pedal_step);
+        w.text_element("pedal-alter", &self.pedal_alter);
+        w.end(name);
+    }
+}
+
+impl WriteXml for HarpPedals {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.elements("pedal-tuning", &self.pedal_tunings);
+        w.end(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HarpPedals;
+    use serde_xml_rs::from_str;
+
+    #[test]
+    fn harp_pedals() {
+        let xml = r#"<harp-pedals>
+            <pedal-tuning><pedal-step>D</pedal-step><pedal-alter>0</pedal-alter></pedal-tuning>
+            <pedal-tuning><pedal-step>C</pedal-step><pedal-alter>-1</pedal-alter></pedal-tuning>
+            <pedal-tuning><pedal-step>B</pedal-step><pedal-alter>-1</pedal-alter></pedal-tuning>
+            <pedal-tuning><pedal-step>E</pedal-step><pedal-alter>0</pedal-alter></pedal-tuning>
+        </harp-pedals>"#;
+        let item: HarpPedals = from_str(xml).unwrap();
+        assert_eq!(item.pedal_tunings.len(), 4);
+    }
+}
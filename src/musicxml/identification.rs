@@ -1,6 +1,7 @@
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use super::writer::{Attrs, WriteXml, XmlWriter};
 use super::yes_no::YesNo;
 
 #[derive(Debug, Serialize, Deserialize, Default, PartialEq, PartialOrd)]
@@ -81,6 +82,50 @@ pub struct Identification {
     pub miscellaneous: Option<Miscellaneous>,
 }
 
+impl WriteXml for TypedContent {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.text_element_with(name, Attrs::new().opt("type", &self.r#type), &self.content);
+    }
+}
+
+impl WriteXml for Encoding {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.opt_text_element("encoding-date", &self.encoding_date);
+        w.elements("encoder", &self.encoder);
+        w.opt_text_element("software", &self.software);
+        w.opt_text_element("encoding-description", &self.encoding_description);
+        w.end(name);
+    }
+}
+
+impl WriteXml for MiscellaneousField {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.text_element_with(name, Attrs::new().add("name", &self.name), &self.content);
+    }
+}
+
+impl WriteXml for Miscellaneous {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.elements("miscellaneous-field", &self.miscellaneous_fields);
+        w.end(name);
+    }
+}
+
+impl WriteXml for Identification {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.elements("creator", &self.creators);
+        w.elements("rights", &self.rights);
+        w.opt_element("encoding", &self.encoding);
+        w.opt_text_element("source", &self.source);
+        w.opt_text_element("relation", &self.relation);
+        w.opt_element("miscellaneous", &self.miscellaneous);
+        w.end(name);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::musicxml::identification::{Encoding, TypedContent};
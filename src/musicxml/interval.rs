@@ -0,0 +1,295 @@
+use crate::error::MusicXmlError;
+use crate::musicxml::harmony::Pitch;
+use std::fmt;
+use std::str::FromStr;
+
+/// Semitones of the major or perfect interval of each number, from the unison.
+const MAJOR_OR_PERFECT: [i32; 7] = [0, 2, 4, 5, 7, 9, 11];
+
+/// The quality of a named interval. Diminished and augmented count how often, so
+/// `Augmented(2)` is doubly augmented.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quality {
+    Diminished(u8),
+    Minor,
+    Perfect,
+    Major,
+    Augmented(u8),
+}
+
+impl fmt::Display for Quality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Quality::Diminished(n) => write!(f, "{}", "d".repeat(*n as usize)),
+            Quality::Minor => write!(f, "m"),
+            Quality::Perfect => write!(f, "P"),
+            Quality::Major => write!(f, "M"),
+            Quality::Augmented(n) => write!(f, "{}", "A".repeat(*n as usize)),
+        }
+    }
+}
+
+/// An interval counted in diatonic steps and chromatic semitones, as in
+/// `<transpose>`. A major third up is 2 steps and 4 semitones, an octave down is -7
+/// and -12. Counting both keeps the spelling of transposed notes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Interval {
+    pub diatonic: i32,
+    pub chromatic: i32,
+}
+
+impl Interval {
+    pub fn new(diatonic: i32, chromatic: i32) -> Interval {
+        Interval {
+            diatonic,
+            chromatic,
+        }
+    }
+
+    /// The interval from `from` to `to`, negative if `to` is lower.
+    pub fn between(from: &Pitch, to: &Pitch) -> Interval {
+        let steps = |p: &Pitch| p.octave as i32 * 7 + p.step.index();
+        Interval::new(
+            steps(to) - steps(from),
+            (to.midi_number() - from.midi_number()).round() as i32,
+        )
+    }
+
+    /// The interval of a quality and number, counted from 1 for the unison, upwards.
+    /// Gives `None` for combinations that don't exist, like a perfect third.
+    pub fn named(quality: Quality, number: u32) -> Option<Interval> {
+        let steps = i32::try_from(number.checked_sub(1)?).ok()?;
+        let perfect = is_perfect(steps);
+        let alteration = match (quality, perfect) {
+            (Quality::Perfect, true) | (Quality::Major, false) => 0,
+            (Quality::Minor, false) => -1,
+            (Quality::Augmented(n), _) => n as i32,
+            (Quality::Diminished(n), true) => -(n as i32),
+            (Quality::Diminished(n), false) => -(n as i32) - 1,
+            _ => return None,
+        };
+        let semitones = (steps / 7)
+            .checked_mul(12)?
+            .checked_add(MAJOR_OR_PERFECT[(steps % 7) as usize] + alteration)?;
+        Some(Interval::new(steps, semitones))
+    }
+
+    /// Whether the interval goes down.
+    pub fn is_descending(&self) -> bool {
+        self.diatonic < 0 || (self.diatonic == 0 && self.chromatic < 0)
+    }
+
+    /// The interval number regardless of direction, 1 for a unison, 10 for a tenth.
+    pub fn number(&self) -> u32 {
+        self.diatonic.unsigned_abs() + 1
+    }
+
+    /// The quality regardless of direction.
+    pub fn quality(&self) -> Quality {
+        let interval = if self.is_descending() {
+            self.inverse()
+        } else {
+            *self
+        };
+        let steps = interval.diatonic;
+        let reference = MAJOR_OR_PERFECT[(steps % 7) as usize] + 12 * (steps / 7);
+        let difference = interval.chromatic - reference;
+
+        match (difference, is_perfect(steps)) {
+            (0, true) => Quality::Perfect,
+            (0, false) => Quality::Major,
+            (-1, false) => Quality::Minor,
+            (d, _) if d > 0 => Quality::Augmented(d as u8),
+            (d, true) => Quality::Diminished(-d as u8),
+            (d, false) => Quality::Diminished((-d - 1) as u8),
+        }
+    }
+
+    /// The same interval in the opposite direction.
+    pub fn inverse(&self) -> Interval {
+        Interval::new(-self.diatonic, -self.chromatic)
+    }
+
+    /// How many fifths a key signature moves when transposed by this interval, like
+    /// 4 for a major third up or -2 for a major second down.
+    pub fn fifths(&self) -> i32 {
+        // Fifths of the major or perfect interval of each number, from the unison.
+        const FIFTHS: [i32; 7] = [0, 2, 4, -1, 1, 3, 5];
+
+        let base = FIFTHS[self.diatonic.rem_euclid(7) as usize];
+        let semitones = (base * 7).rem_euclid(12);
+        let augmented = (self.chromatic - semitones).rem_euclid(12);
+        let augmented = if augmented > 6 {
+            augmented - 12
+        } else {
+            augmented
+        };
+        base + 7 * augmented
+    }
+
+    /// A key signature of `fifths` sharps or flats moved by this interval. Keys
+    /// beyond seven sharps or flats stay theoretical, like eight sharps for G♯ major,
+    /// so they agree with the notes moved by the same interval.
+    pub fn transpose_fifths(&self, fifths: i8) -> i8 {
+        (fifths as i32 + self.fifths()).clamp(i8::MIN as i32, i8::MAX as i32) as i8
+    }
+
+    /// The enharmonic spelling of this interval, one diatonic step more or less,
+    /// that keeps the keys of `fifths` closest to no sharps or flats. Transposing
+    /// G major up an augmented unison this way gives A♭ major instead of G♯ major.
+    pub fn respelled_for(&self, fifths: &[i8]) -> Interval {
+        let widest = |interval: &Interval| {
+            fifths
+                .iter()
+                .map(|f| (*f as i32 + interval.fifths()).abs())
+                .max()
+                .unwrap_or(0)
+        };
+        [
+            *self,
+            Interval::new(self.diatonic + 1, self.chromatic),
+            Interval::new(self.diatonic - 1, self.chromatic),
+        ]
+        .into_iter()
+        .min_by_key(widest)
+        .unwrap_or(*self)
+    }
+}
+
+/// Unisons, fourths and fifths and their compounds are perfect, not major or minor.
+fn is_perfect(steps: i32) -> bool {
+    matches!(steps % 7, 0 | 3 | 4)
+}
+
+/// Short names like "M3", "P5", "A4" or "dd7", with a leading "-" when descending.
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.is_descending() { "-" } else { "" };
+        write!(f, "{sign}{}{}", self.quality(), self.number())
+    }
+}
+
+impl FromStr for Interval {
+    type Err = MusicXmlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || MusicXmlError::Generic(format!("invalid interval name {s:?}"));
+        let (descending, name) = match s.trim().strip_prefix('-') {
+            Some(name) => (true, name),
+            None => (false, s.trim()),
+        };
+        let digits = name
+            .find(|c: char| c.is_ascii_digit())
+            .ok_or_else(invalid)?;
+        let (quality, number) = name.split_at(digits);
+
+        let count = quality.len() as u8;
+        let quality = match quality {
+            "P" => Quality::Perfect,
+            "M" => Quality::Major,
+            "m" => Quality::Minor,
+            q if !q.is_empty() && q.chars().all(|c| c == 'A') => Quality::Augmented(count),
+            q if !q.is_empty() && q.chars().all(|c| c == 'd') => Quality::Diminished(count),
+            _ => return Err(invalid()),
+        };
+        let number = number.parse().map_err(|_| invalid())?;
+        let interval = Interval::named(quality, number).ok_or_else(invalid)?;
+        Ok(if descending {
+            interval.inverse()
+        } else {
+            interval
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Interval, Quality};
+    use crate::musicxml::harmony::{Pitch, Step};
+
+    fn pitch(step: Step, alter: f32, octave: u8) -> Pitch {
+        Pitch {
+            step,
+            alter: (alter != 0.0).then_some(alter),
+            octave,
+        }
+    }
+
+    #[test]
+    fn fifths() {
+        assert_eq!(Interval::new(2, 4).fifths(), 4);
+        assert_eq!(Interval::new(-1, -2).fifths(), -2);
+        assert_eq!(Interval::new(-5, -9).fifths(), -3);
+        assert_eq!(Interval::new(2, 3).fifths(), -3);
+        assert_eq!(Interval::new(-7, -12).fifths(), 0);
+        assert_eq!(Interval::new(3, 6).fifths(), 6);
+    }
+
+    #[test]
+    fn names() {
+        let names = [
+            "P1", "m2", "M3", "P4", "A4", "d5", "P5", "m6", "d7", "P8", "M10",
+        ];
+        for name in names {
+            let interval: Interval = name.parse().unwrap();
+            assert_eq!(interval.to_string(), name);
+        }
+
+        assert_eq!("M3".parse::<Interval>().unwrap(), Interval::new(2, 4));
+        assert_eq!("-P5".parse::<Interval>().unwrap(), Interval::new(-4, -7));
+        assert_eq!(Interval::new(3, 7).to_string(), "AA4");
+        assert!("P3".parse::<Interval>().is_err());
+        assert!("X5".parse::<Interval>().is_err());
+        assert!("M3000000000".parse::<Interval>().is_err());
+        assert!("P1000000000".parse::<Interval>().is_err());
+        assert_eq!(Interval::named(Quality::Perfect, 0), None);
+
+        let b_flat = pitch(Step::B, -1.0, 3);
+        assert_eq!(
+            Interval::between(&b_flat, &pitch(Step::D, 0.0, 4)).to_string(),
+            "M3"
+        );
+        assert_eq!(
+            Interval::between(&b_flat, &pitch(Step::C, 1.0, 4)).to_string(),
+            "A2"
+        );
+        assert_eq!(
+            Interval::between(&pitch(Step::E, 0.0, 4), &b_flat).to_string(),
+            "-A4"
+        );
+    }
+
+    #[test]
+    fn respelling() {
+        let augmented_unison = Interval::new(0, 1);
+        assert_eq!(augmented_unison.respelled_for(&[1]), Interval::new(1, 1));
+        assert_eq!(augmented_unison.respelled_for(&[-3]), augmented_unison);
+        assert_eq!(augmented_unison.transpose_fifths(1), 8);
+        assert_eq!(Interval::new(1, 1).transpose_fifths(1), -4);
+
+        let minor_second = Interval::new(1, 1);
+        assert_eq!(minor_second.respelled_for(&[5]), minor_second);
+        assert_eq!(minor_second.respelled_for(&[]), minor_second);
+    }
+
+    #[test]
+    fn transpose_pitch() {
+        let major_third = Interval::new(2, 4);
+        assert_eq!(
+            pitch(Step::B, -1.0, 3).transpose(&major_third),
+            pitch(Step::D, 0.0, 4)
+        );
+        assert_eq!(
+            pitch(Step::D, 0.0, 4).transpose(&major_third.inverse()),
+            pitch(Step::B, -1.0, 3)
+        );
+        assert_eq!(
+            pitch(Step::C, 0.0, 4).transpose(&Interval::new(-1, -2)),
+            pitch(Step::B, -1.0, 3)
+        );
+        assert_eq!(
+            pitch(Step::F, 1.0, 5).transpose(&Interval::new(-7, -12)),
+            pitch(Step::F, 1.0, 4)
+        );
+    }
+}
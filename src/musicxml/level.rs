@@ -2,6 +2,7 @@ use super::{
     note::StartStop,
     start_stop_single::{self, StartStopSingle},
     symbol_size::SymbolSize,
+    writer::{Attrs, WriteXml, XmlWriter},
     yes_no::YesNo,
 };
 use serde::{Deserialize, Serialize};
@@ -26,3 +27,15 @@ pub struct Level {
     #[serde(default = "Option::default")]
     r#type: Option<StartStopSingle>,
 }
+
+impl WriteXml for Level {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .opt("bracket", &self.bracket)
+            .opt("parentheses", &self.parentheses)
+            .opt("reference", &self.reference)
+            .opt("size", &self.size)
+            .opt("type", &self.r#type);
+        w.text_element_with(name, attrs, &self.content);
+    }
+}
@@ -2,12 +2,13 @@ use super::{
     core::{Placement, SyllabicType},
     printable_value::LeftCenterRight,
     start_stop_continue::StartStopContinue,
+    writer::{Attrs, WriteXml, XmlWriter},
     yes_no::YesNo,
 };
 
 use crate::prelude::*;
 use roxmltree::NodeType;
-use serde::{Deserialize, Serialize};
+use serde::{de::IgnoredAny, Deserialize, Serialize};
 use std::str::FromStr;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd)]
@@ -31,7 +32,18 @@ pub struct Extend {
     r#type: Option<StartStopContinue>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd)]
+/// A further syllable sung on the same note, joined to the previous one by an
+/// `<elision>`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Default)]
+pub struct ElidedSyllable {
+    /// The symbol joining the syllables, empty for the default undertie.
+    pub elision: String,
+    pub syllabic: Option<SyllabicType>,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Default)]
+#[serde(from = "LyricXml")]
 pub struct Lyric {
     // Attributes
     #[serde(default = "Option::default")]
@@ -76,8 +88,187 @@ pub struct Lyric {
     #[serde(default = "String::default")]
     pub text: String,
 
+    #[serde(default = "Vec::default")]
+    pub elided: Vec<ElidedSyllable>,
+
     #[serde(default = "Option::default")]
     pub extend: Option<Extend>,
+
+    /// A lyric that is laughed or hummed instead of sung has no text.
+    #[serde(default = "bool::default")]
+    pub laughing: bool,
+
+    #[serde(default = "bool::default")]
+    pub humming: bool,
+
+    #[serde(rename = "end-line", default = "bool::default")]
+    pub end_line: bool,
+
+    #[serde(rename = "end-paragraph", default = "bool::default")]
+    pub end_paragraph: bool,
+}
+
+#[derive(Deserialize)]
+enum LyricChild {
+    #[serde(rename = "syllabic")]
+    Syllabic(SyllabicType),
+    #[serde(rename = "text")]
+    Text(String),
+    #[serde(rename = "elision")]
+    Elision(String),
+    #[serde(rename = "extend")]
+    Extend(Extend),
+    #[serde(rename = "laughing")]
+    Laughing(IgnoredAny),
+    #[serde(rename = "humming")]
+    Humming(IgnoredAny),
+    #[serde(rename = "end-line")]
+    EndLine(IgnoredAny),
+    #[serde(rename = "end-paragraph")]
+    EndParagraph(IgnoredAny),
+    #[serde(rename = "footnote")]
+    Footnote(IgnoredAny),
+    #[serde(rename = "level")]
+    Level(IgnoredAny),
+}
+
+#[derive(Deserialize)]
+struct LyricXml {
+    #[serde(default = "Option::default")]
+    color: Option<String>,
+
+    #[serde(rename = "default-x", default = "Option::default")]
+    default_x: Option<f32>,
+
+    #[serde(rename = "default-y", default = "Option::default")]
+    default_y: Option<f32>,
+
+    #[serde(default = "Option::default")]
+    id: Option<String>,
+
+    #[serde(default = "Option::default")]
+    justify: Option<LeftCenterRight>,
+
+    #[serde(default = "Option::default")]
+    name: Option<String>,
+
+    #[serde(default = "Option::default")]
+    number: Option<u8>,
+
+    #[serde(default = "Option::default")]
+    placement: Option<Placement>,
+
+    #[serde(rename = "print-object", default = "Option::default")]
+    print_object: Option<YesNo>,
+
+    #[serde(rename = "relative-x", default = "Option::default")]
+    relative_x: Option<f32>,
+
+    #[serde(rename = "relative-y", default = "Option::default")]
+    relative_y: Option<f32>,
+
+    #[serde(rename = "time-only", default = "Option::default")]
+    time_only: Option<String>,
+
+    #[serde(rename = "$value", default = "Vec::default")]
+    children: Vec<LyricChild>,
+}
+
+impl From<LyricXml> for Lyric {
+    fn from(xml: LyricXml) -> Self {
+        let mut item = Lyric {
+            color: xml.color,
+            default_x: xml.default_x,
+            default_y: xml.default_y,
+            id: xml.id,
+            justify: xml.justify,
+            name: xml.name,
+            number: xml.number,
+            placement: xml.placement,
+            print_object: xml.print_object,
+            relative_x: xml.relative_x,
+            relative_y: xml.relative_y,
+            time_only: xml.time_only,
+            ..Lyric::default()
+        };
+        for child in xml.children {
+            match (child, item.elided.last_mut()) {
+                (LyricChild::Elision(elision), _) => item.elided.push(ElidedSyllable {
+                    elision,
+                    ..ElidedSyllable::default()
+                }),
+                (LyricChild::Syllabic(s), Some(syllable)) => syllable.syllabic = Some(s),
+                (LyricChild::Syllabic(s), None) => item.syllabic = Some(s),
+                (LyricChild::Text(t), Some(syllable)) => syllable.text = t,
+                (LyricChild::Text(t), None) => item.text = t,
+                (LyricChild::Extend(e), _) => item.extend = Some(e),
+                (LyricChild::Laughing(_), _) => item.laughing = true,
+                (LyricChild::Humming(_), _) => item.humming = true,
+                (LyricChild::EndLine(_), _) => item.end_line = true,
+                (LyricChild::EndParagraph(_), _) => item.end_paragraph = true,
+                _ => {}
+            }
+        }
+        item
+    }
+}
+
+impl WriteXml for Extend {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .opt("color", &self.color)
+            .opt("default-x", &self.default_x)
+            .opt("default-y", &self.default_y)
+            .opt("relative-x", &self.relative_x)
+            .opt("relative-y", &self.relative_y)
+            .opt("type", &self.r#type);
+        w.empty(name, attrs);
+    }
+}
+
+impl WriteXml for Lyric {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .opt("color", &self.color)
+            .opt("default-x", &self.default_x)
+            .opt("default-y", &self.default_y)
+            .opt("id", &self.id)
+            .opt("justify", &self.justify)
+            .opt("name", &self.name)
+            .opt("number", &self.number)
+            .opt("placement", &self.placement)
+            .opt("print-object", &self.print_object)
+            .opt("relative-x", &self.relative_x)
+            .opt("relative-y", &self.relative_y)
+            .opt("time-only", &self.time_only);
+
+        w.start(name, attrs);
+        if self.laughing {
+            w.empty("laughing", Attrs::new());
+        } else if self.humming {
+            w.empty("humming", Attrs::new());
+        } else if !self.text.is_empty() || self.extend.is_none() {
+            w.opt_text_element("syllabic", &self.syllabic);
+            w.text_element("text", &self.text);
+            for syllable in &self.elided {
+                if syllable.elision.is_empty() {
+                    w.empty("elision", Attrs::new());
+                } else {
+                    w.text_element("elision", &syllable.elision);
+                }
+                w.opt_text_element("syllabic", &syllable.syllabic);
+                w.text_element("text", &syllable.text);
+            }
+        }
+        w.opt_element("extend", &self.extend);
+        if self.end_line {
+            w.empty("end-line", Attrs::new());
+        }
+        if self.end_paragraph {
+            w.empty("end-paragraph", Attrs::new());
+        }
+        w.end(name);
+    }
 }
 
 #[cfg(test)]
@@ -87,6 +278,7 @@ mod tests {
         lyric::Lyric,
         printable_value::LeftCenterRight,
         start_stop_continue::StartStopContinue,
+        writer::XmlWriter,
     };
     use roxmltree::Document;
     use serde_xml_rs::from_str;
@@ -130,4 +322,40 @@ mod tests {
             StartStopContinue::Start
         );
     }
+
+    #[test]
+    fn elision() {
+        let xml = r#"
+            <lyric number="1">
+                <syllabic>begin</syllabic>
+                <text>A</text>
+                <elision/>
+                <syllabic>end</syllabic>
+                <text>b</text>
+                <end-line/>
+            </lyric>"#;
+        let item: Lyric = from_str(xml).unwrap();
+
+        assert_eq!(item.text, "A");
+        assert_eq!(item.syllabic, Some(SyllabicType::Begin));
+        assert_eq!(item.elided.len(), 1);
+        assert_eq!(item.elided[0].syllabic, Some(SyllabicType::End));
+        assert_eq!(item.elided[0].text, "b");
+        assert!(item.end_line);
+
+        let mut w = XmlWriter::new();
+        w.element("lyric", &item);
+        assert_eq!(
+            w.finish(),
+            r#"<lyric number="1">
+  <syllabic>begin</syllabic>
+  <text>A</text>
+  <elision/>
+  <syllabic>end</syllabic>
+  <text>b</text>
+  <end-line/>
+</lyric>
+"#
+        );
+    }
 }
@@ -1,7 +1,19 @@
 use super::{
-    attributes::Attributes, backup::Backup, barline::Barline, core::Duration, direction::Direction,
-    forward::Forward, harmony::Harmony, left_right_middle::LeftRightMiddle, level::Level,
-    note::Note, print::Print, yes_no::YesNo,
+    attributes::Attributes,
+    backup::Backup,
+    barline::Barline,
+    core::Duration,
+    direction::Direction,
+    figured_bass::FiguredBass,
+    forward::Forward,
+    harmony::Harmony,
+    left_right_middle::LeftRightMiddle,
+    level::Level,
+    note::Note,
+    print::Print,
+    sound::Sound,
+    writer::{Attrs, WriteXml, XmlWriter},
+    yes_no::YesNo,
 };
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -23,6 +35,9 @@ pub enum MeasureContent {
     #[serde(rename = "harmony")]
     Harmony(Harmony),
 
+    #[serde(rename = "figured-bass")]
+    FiguredBass(FiguredBass),
+
     #[serde(rename = "forward")]
     Forward(Forward),
 
@@ -32,27 +47,31 @@ pub enum MeasureContent {
     #[serde(rename = "direction")]
     Direction(Direction),
 
-    #[serde(rename = "non-controlling")]
-    NonControlling(YesNo),
+    #[serde(rename = "sound")]
+    Sound(Sound),
+}
 
-    #[serde(rename = "implicit")]
-    Implicit(YesNo),
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/measure-partwise/
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct Measure {
+    #[serde(default = "String::default")]
+    pub number: String,
 
-    #[serde(rename = "id")]
-    Id(String),
+    #[serde(default = "Option::default")]
+    pub id: Option<String>,
 
-    #[serde(rename = "width")]
-    Width(f32),
+    #[serde(default = "Option::default")]
+    pub implicit: Option<YesNo>,
 
-    #[serde(rename = "number")]
-    Number(String),
+    #[serde(rename = "non-controlling", default = "Option::default")]
+    pub non_controlling: Option<YesNo>,
 
-    #[serde(rename = "rest")]
-    Rest(String),
-}
+    #[serde(default = "Option::default")]
+    pub text: Option<String>,
+
+    #[serde(default = "Option::default")]
+    pub width: Option<f32>,
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Measure {
     #[serde(rename = "$value", default = "Vec::default")]
     pub content: Vec<MeasureContent>,
 }
@@ -91,9 +110,42 @@ impl Measure {
     }
 }
 
+impl WriteXml for MeasureContent {
+    fn write_xml(&self, w: &mut XmlWriter, _name: &str) {
+        match self {
+            MeasureContent::Note(note) => w.element("note", note),
+            MeasureContent::Backup(backup) => w.element("backup", backup),
+            MeasureContent::Barline(barline) => w.element("barline", barline),
+            MeasureContent::Attributes(attributes) => w.element("attributes", attributes),
+            MeasureContent::Harmony(harmony) => w.element("harmony", harmony),
+            MeasureContent::FiguredBass(figured_bass) => w.element("figured-bass", figured_bass),
+            MeasureContent::Forward(forward) => w.element("forward", forward),
+            MeasureContent::Print(print) => w.element("print", print),
+            MeasureContent::Direction(direction) => w.element("direction", direction),
+            MeasureContent::Sound(sound) => w.element("sound", sound),
+        }
+    }
+}
+
+impl WriteXml for Measure {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .add("number", &self.number)
+            .opt("id", &self.id)
+            .opt("implicit", &self.implicit)
+            .opt("non-controlling", &self.non_controlling)
+            .opt("text", &self.text)
+            .opt("width", &self.width);
+
+        w.start(name, attrs);
+        w.elements("", &self.content);
+        w.end(name);
+    }
+}
+
 #[cfg(test)]
 mod test_measure {
-    use crate::musicxml::attributes::{Key, KeyMode};
+    use crate::musicxml::attributes::{ClefSign, Key, KeyMode};
     use crate::musicxml::core::DurationType;
     use crate::musicxml::harmony::{Harmony, Pitch, Step};
     use crate::musicxml::measure::{Measure, MeasureContent};
@@ -134,25 +186,25 @@ mod test_measure {
 
         let item: Measure = from_str(xml).unwrap();
 
+        assert_eq!(item.number, "1".to_string());
         assert_eq!(2, item.content.len());
 
         match &item.content[0] {
             MeasureContent::Attributes(a) => {
                 assert_eq!(a.divisions.unwrap(), 1);
                 assert_eq!(
-                    a.key.clone().unwrap(),
-                    Key {
-                        number: 0,
+                    a.keys,
+                    vec![Key {
                         fifths: 0,
-                        mode: KeyMode::None
-                    }
+                        mode: KeyMode::None,
+                        ..Key::default()
+                    }]
                 );
 
-                assert_eq!(a.time.clone().unwrap().beats, 4);
-                assert_eq!(a.time.clone().unwrap().beat_type, 4);
+                assert_eq!(a.times[0].fraction(), Some((4, 4)));
 
-                assert_eq!(a.clef.clone().unwrap().sign, "G");
-                assert_eq!(a.clef.clone().unwrap().line, 2);
+                assert_eq!(a.clefs[0].sign, ClefSign::G);
+                assert_eq!(a.clefs[0].line, 2);
             }
 
             _ => {
@@ -166,11 +218,12 @@ mod test_measure {
                     n.pitch.clone().unwrap(),
                     Pitch {
                         step: Step::C,
+                        alter: None,
                         octave: 4
                     }
                 );
                 assert_eq!(n.duration, 4);
-                assert_eq!(n.notetype, DurationType::Whole);
+                assert_eq!(n.notetype, Some(DurationType::Whole));
             }
             _ => {
                 panic!("Expected note")
@@ -540,6 +593,44 @@ mod test_measure {
         let item: Measure = from_str(&xml).unwrap();
     }
 
+    #[test]
+    fn test_trailing_repeated_elements() {
+        let xml = r#"
+        <measure number="1">
+          <direction>
+            <direction-type>
+              <words>rit.</words>
+            </direction-type>
+          </direction>
+          <note>
+            <pitch>
+              <step>G</step>
+              <octave>4</octave>
+            </pitch>
+            <duration>3</duration>
+            <type>quarter</type>
+            <dot/>
+          </note>
+          <note>
+            <rest/>
+            <duration>1</duration>
+            <type>eighth</type>
+          </note>
+        </measure>"#;
+
+        let item: Measure = from_str(xml).unwrap();
+        assert_eq!(item.content.len(), 3);
+
+        match &item.content[1] {
+            MeasureContent::Note(n) => assert_eq!(n.dot.len(), 1),
+            _ => panic!("Expected note"),
+        }
+        match &item.content[2] {
+            MeasureContent::Note(n) => assert!(n.rest),
+            _ => panic!("Expected note"),
+        }
+    }
+
     // Fails as a individual test but passes when part of complete file...
     // #[test]
     // fn test_chords() {
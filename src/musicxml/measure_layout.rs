@@ -1,7 +1,17 @@
 use serde::{Deserialize, Serialize};
 
+use super::writer::{Attrs, WriteXml, XmlWriter};
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct MeasureLayout {
     #[serde(rename = "measure-distance", default = "Option::default")]
     measure_distance: Option<f32>,
 }
+
+impl WriteXml for MeasureLayout {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.opt_text_element("measure-distance", &self.measure_distance);
+        w.end(name);
+    }
+}
@@ -0,0 +1,197 @@
+use serde::{de::IgnoredAny, Deserialize, Serialize};
+
+use super::core::DurationType;
+use super::timeline::Rational;
+use super::writer::{Attrs, WriteXml, XmlWriter};
+use super::yes_no::YesNo;
+use crate::prelude::*;
+
+/// A note value with augmentation dots, as used for metronome beats.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone)]
+pub struct BeatUnit {
+    pub unit: DurationType,
+    pub dots: usize,
+}
+
+impl BeatUnit {
+    /// The dotted length in whole notes.
+    pub fn whole_notes(&self) -> Rational {
+        let mut length = self.unit.whole_notes();
+        let mut dot = length;
+        for _ in 0..self.dots {
+            dot /= 2;
+            length += dot;
+        }
+        length
+    }
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/metronome/
+/// Either a tempo mark, beat unit = per minute, or a metric modulation, beat unit =
+/// beat unit. Metronome marks built from `<metronome-note>` are not modelled.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone)]
+#[serde(from = "MetronomeXml")]
+pub struct Metronome {
+    pub beat_unit: BeatUnit,
+
+    /// The text of `<per-minute>`, which may be a range or approximate like "c. 60".
+    pub per_minute: Option<String>,
+
+    /// The right side of a metric modulation.
+    pub equals: Option<BeatUnit>,
+
+    pub parentheses: Option<YesNo>,
+    pub default_y: Option<f32>,
+}
+
+impl Metronome {
+    /// The tempo in quarter notes per minute, if `per_minute` starts with a number.
+    pub fn quarters_per_minute(&self) -> Option<f32> {
+        let per_minute = self.per_minute.as_deref()?;
+        let number: String = per_minute
+            .chars()
+            .skip_while(|c| !c.is_ascii_digit())
+            .take_while(|c| c.is_ascii_digit() || *c == '.')
+            .collect();
+        let bpm: f32 = number.parse().ok()?;
+        let quarters = self.beat_unit.whole_notes() * 4;
+        Some(bpm * *quarters.numer() as f32 / *quarters.denom() as f32)
+    }
+}
+
+#[derive(Deserialize)]
+enum MetronomeChild {
+    #[serde(rename = "beat-unit")]
+    BeatUnit(DurationType),
+    #[serde(rename = "beat-unit-dot")]
+    BeatUnitDot(IgnoredAny),
+    #[serde(rename = "beat-unit-tied")]
+    BeatUnitTied(IgnoredAny),
+    #[serde(rename = "per-minute")]
+    PerMinute(String),
+    #[serde(rename = "metronome-arrows")]
+    MetronomeArrows(IgnoredAny),
+    #[serde(rename = "metronome-note")]
+    MetronomeNote(IgnoredAny),
+    #[serde(rename = "metronome-relation")]
+    MetronomeRelation(IgnoredAny),
+}
+
+#[derive(Deserialize)]
+struct MetronomeXml {
+    #[serde(default = "Option::default")]
+    parentheses: Option<YesNo>,
+
+    #[serde(rename = "default-y", default = "Option::default")]
+    default_y: Option<f32>,
+
+    #[serde(rename = "$value", default = "Vec::default")]
+    children: Vec<MetronomeChild>,
+}
+
+impl From<MetronomeXml> for Metronome {
+    fn from(xml: MetronomeXml) -> Self {
+        let mut item = Metronome {
+            parentheses: xml.parentheses,
+            default_y: xml.default_y,
+            ..Metronome::default()
+        };
+        let mut units: Vec<BeatUnit> = vec![];
+        for child in xml.children {
+            match child {
+                MetronomeChild::BeatUnit(unit) => units.push(BeatUnit { unit, dots: 0 }),
+                MetronomeChild::BeatUnitDot(_) => {
+                    if let Some(unit) = units.last_mut() {
+                        unit.dots += 1;
+                    }
+                }
+                MetronomeChild::PerMinute(text) => item.per_minute = Some(text),
+                _ => {}
+            }
+        }
+        let mut units = units.into_iter();
+        item.beat_unit = units.next().unwrap_or_default();
+        item.equals = units.next();
+        item
+    }
+}
+
+impl WriteXml for BeatUnit {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.text_element(name, &self.unit);
+        for _ in 0..self.dots {
+            w.empty("beat-unit-dot", Attrs::new());
+        }
+    }
+}
+
+impl WriteXml for Metronome {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .opt("parentheses", &self.parentheses)
+            .opt("default-y", &self.default_y);
+        w.start(name, attrs);
+        w.element("beat-unit", &self.beat_unit);
+        match &self.equals {
+            Some(equals) => w.element("beat-unit", equals),
+            None => w.opt_text_element("per-minute", &self.per_minute),
+        }
+        w.end(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BeatUnit, Metronome};
+    use crate::musicxml::{core::DurationType, writer::XmlWriter};
+    use serde_xml_rs::from_str;
+
+    #[test]
+    fn dotted_beat_unit() {
+        let xml = r#"
+            <metronome parentheses="yes">
+                <beat-unit>quarter</beat-unit>
+                <beat-unit-dot/>
+                <per-minute>c. 60</per-minute>
+            </metronome>"#;
+        let item: Metronome = from_str(xml).unwrap();
+
+        assert_eq!(item.beat_unit.dots, 1);
+        assert_eq!(item.per_minute.as_deref(), Some("c. 60"));
+        assert_eq!(item.quarters_per_minute(), Some(90.0));
+    }
+
+    #[test]
+    fn metric_modulation() {
+        let xml = r#"
+            <metronome>
+                <beat-unit>half</beat-unit>
+                <beat-unit>quarter</beat-unit>
+                <beat-unit-dot/>
+            </metronome>"#;
+        let item: Metronome = from_str(xml).unwrap();
+
+        assert_eq!(
+            item.beat_unit,
+            BeatUnit {
+                unit: DurationType::Half,
+                dots: 0
+            }
+        );
+        assert_eq!(
+            item.equals,
+            Some(BeatUnit {
+                unit: DurationType::Quarter,
+                dots: 1
+            })
+        );
+        assert_eq!(item.quarters_per_minute(), None);
+    }
+
+    #[test]
+    fn no_tempo() {
+        let mut w = XmlWriter::new();
+        w.element("metronome", &Metronome::default());
+        assert!(!w.finish().contains("per-minute"));
+    }
+}
@@ -0,0 +1,1257 @@
+use midly::{
+    num::{u15, u24, u28, u4, u7},
+    Format, Header, MetaMessage, MidiMessage, Smf, Timing, TrackEvent, TrackEventKind,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+
+use super::{
+    attributes::{Attributes, Clef, ClefSign, Key, Time},
+    backup::Backup,
+    core::{DurationType, RepeatDirection},
+    direction::DirectionType,
+    dynamics::Dynamics,
+    harmony::Pitch,
+    measure::{Measure, MeasureContent},
+    midi_instrument::MidiInstrument,
+    note::{Dot, NotationType, NotationTypeMeta, Notations, Note, StartStop},
+    part::Part,
+    part_list::{PartList, PartListContent},
+    score_instrument::ScoreInstrument,
+    score_part::{ScorePart, ScorePartContent},
+    score_partwise::ScorePartwise,
+    tie::Tie,
+    timeline::{Rational, Timeline},
+    writer::MUSICXML_VERSION,
+};
+use crate::error::MusicXmlError;
+
+/// Resolution of exported files. Divisible by 3, 5 and 8 to keep common tuplets exact.
+pub const TICKS_PER_QUARTER: u16 = 480;
+
+/// MIDI channel reserved for percussion, counted from 0.
+const PERCUSSION_CHANNEL: u8 = 9;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MidiOptions {
+    /// Plays measures in `Part::performance_order`, following repeats, endings and jumps.
+    pub unroll_repeats: bool,
+
+    /// Quarter notes per minute until the first tempo marking.
+    pub default_tempo: f32,
+
+    /// Note velocity until the first dynamic.
+    pub default_velocity: u8,
+}
+
+impl Default for MidiOptions {
+    fn default() -> Self {
+        MidiOptions {
+            unroll_repeats: true,
+            default_tempo: 120.0,
+            default_velocity: 80,
+        }
+    }
+}
+
+impl ScorePartwise {
+    /// Renders the score as a type 1 Standard MIDI File.
+    pub fn to_midi(&self, options: &MidiOptions) -> Vec<u8> {
+        let mut bytes = vec![];
+        write_midi(self, options, &mut bytes).expect("writing to a Vec cannot fail");
+        bytes
+    }
+}
+
+/// Writes `score` as a type 1 Standard MIDI File. The first track holds tempo and
+/// time signature changes, followed by one track per part. Tied notes are merged
+/// into one note, and `<sound dynamics>` or dynamics markings set the velocity.
+pub fn write_midi<W: Write>(
+    score: &ScorePartwise,
+    options: &MidiOptions,
+    writer: W,
+) -> std::result::Result<(), MusicXmlError> {
+    let names: Vec<String> = score
+        .parts
+        .iter()
+        .map(|part| {
+            score
+                .score_part(&part.id)
+                .and_then(|p| p.part_name())
+                .unwrap_or(&part.id)
+                .to_string()
+        })
+        .collect();
+
+    let mut conductor = Events::default();
+    let mut tracks = vec![];
+    let mut next_channel = 0;
+
+    for (part, name) in score.parts.iter().zip(&names) {
+        let score_part = score.score_part(&part.id);
+        let channel = part_channel(part, score_part, &mut next_channel);
+
+        let mut events = Events::default();
+        events.push(
+            0,
+            TrackEventKind::Meta(MetaMessage::TrackName(name.as_bytes())),
+        );
+        if let Some(instrument) = score_part.and_then(|p| p.midi_instruments().next()) {
+            if let Some(bank) = instrument.midi_bank.filter(|b| *b > 0) {
+                events.control(channel, 0, ((bank - 1) >> 7) as u8);
+                events.control(channel, 32, ((bank - 1) & 0x7f) as u8);
+            }
+            if instrument.midi_program > 0 {
+                let program = u7::new((instrument.midi_program - 1).min(127));
+                events.push(0, midi(channel, MidiMessage::ProgramChange { program }));
+            }
+            if let Some(volume) = instrument.volume {
+                events.control(channel, 7, (volume / 100.0 * 127.0) as u8);
+            }
+            if let Some(pan) = instrument.pan {
+                events.control(
+                    channel,
+                    10,
+                    (64.0 + pan.clamp(-90.0, 90.0) / 90.0 * 63.0) as u8,
+                );
+            }
+        }
+
+        render_part(
+            part,
+            score_part,
+            channel,
+            options,
+            &mut events,
+            &mut conductor,
+            tracks.is_empty(),
+        );
+        tracks.push(events.into_track());
+    }
+
+    if !conductor.events.iter().any(|e| e.0 == 0 && is_tempo(&e.2)) {
+        conductor.push(0, tempo(options.default_tempo));
+    }
+    tracks.insert(0, conductor.into_track());
+
+    let smf = Smf {
+        header: Header::new(
+            Format::Parallel,
+            Timing::Metrical(u15::new(TICKS_PER_QUARTER)),
+        ),
+        tracks,
+    };
+    smf.write_std(writer)?;
+    Ok(())
+}
+
+/// Events with absolute times. Sorting by `(tick, rank)` keeps controllers before
+/// note offs, and note offs before note ons of the same tick.
+#[derive(Default)]
+struct Events<'a> {
+    events: Vec<(u64, u8, TrackEventKind<'a>)>,
+}
+
+impl<'a> Events<'a> {
+    fn push(&mut self, tick: u64, kind: TrackEventKind<'a>) {
+        let rank = match kind {
+            TrackEventKind::Midi {
+                message: MidiMessage::NoteOff { .. },
+                ..
+            } => 1,
+            TrackEventKind::Midi {
+                message: MidiMessage::NoteOn { .. },
+                ..
+            } => 2,
+            _ => 0,
+        };
+        self.events.push((tick, rank, kind));
+    }
+
+    fn control(&mut self, channel: u8, controller: u8, value: u8) {
+        let message = MidiMessage::Controller {
+            controller: u7::new(controller),
+            value: u7::new(value.min(127)),
+        };
+        self.push(0, midi(channel, message));
+    }
+
+    fn into_track(mut self) -> Vec<TrackEvent<'a>> {
+        self.events.sort_by_key(|e| (e.0, e.1));
+        // Parts may repeat the same tempo or time signature.
+        self.events
+            .dedup_by(|a, b| a.0 == b.0 && a.2 == b.2 && matches!(a.2, TrackEventKind::Meta(_)));
+
+        let mut track = vec![];
+        let mut last = 0;
+        for (tick, _, kind) in self.events {
+            track.push(TrackEvent {
+                delta: u28::new((tick - last) as u32),
+                kind,
+            });
+            last = tick;
+        }
+        track.push(TrackEvent {
+            delta: u28::new(0),
+            kind: TrackEventKind::Meta(MetaMessage::EndOfTrack),
+        });
+        track
+    }
+}
+
+struct SoundingNote {
+    channel: u8,
+    key: u8,
+    velocity: u8,
+    on: u64,
+    off: u64,
+}
+
+/// Adds the notes of `part` to `events`, and its tempo and, for the first part, time
+/// signature changes to `conductor`.
+fn render_part(
+    part: &Part,
+    score_part: Option<&ScorePart>,
+    channel: u8,
+    options: &MidiOptions,
+    events: &mut Events,
+    conductor: &mut Events,
+    first_part: bool,
+) {
+    let timeline = Timeline::of_part(part);
+    let sounding_times = timeline.sounding();
+    let order: Vec<usize> = if options.unroll_repeats {
+        part.performance_order()
+    } else {
+        (0..part.measures.len()).collect()
+    };
+
+    let mut notes: Vec<SoundingNote> = vec![];
+    let mut open_ties: HashMap<(u8, u8), usize> = HashMap::new();
+    let mut velocity = options.default_velocity;
+    let mut playback = Rational::from_integer(0);
+
+    for idx in order {
+        let span = &timeline.measures[idx];
+        let tick_at = |onset: Rational| ticks(playback + onset - span.onset);
+
+        if first_part {
+            let time = part.measures[idx].content.iter().find_map(|c| match c {
+                MeasureContent::Attributes(a) => a.times.first().and_then(Time::fraction),
+                _ => None,
+            });
+            let time = time.filter(|(beats, beat_type)| {
+                (1..=255).contains(beats) && beat_type.is_power_of_two()
+            });
+            if let Some((beats, beat_type)) = time {
+                let message =
+                    MetaMessage::TimeSignature(beats as u8, beat_type.ilog2() as u8, 24, 8);
+                conductor.push(tick_at(span.onset), TrackEventKind::Meta(message));
+            }
+        }
+
+        // Velocity and tempo changes of this measure, by onset.
+        let mut dynamics: Vec<(Rational, u8)> = vec![];
+        for direction in timeline.directions.iter().filter(|d| d.measure == idx) {
+            for directiontype in &direction.event.directiontypes {
+                match directiontype {
+                    DirectionType::Dynamic(d) => {
+                        if let Some(v) = dynamics_velocity(&d.content) {
+                            dynamics.push((direction.onset, v));
+                        }
+                    }
+                    DirectionType::Metronome(metronome)
+                        if direction
+                            .event
+                            .sound
+                            .as_ref()
+                            .and_then(|s| s.tempo)
+                            .is_none() =>
+                    {
+                        if let Some(bpm) = metronome.quarters_per_minute().filter(|t| *t > 0.0) {
+                            conductor.push(tick_at(direction.onset), tempo(bpm));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        for sound in timeline.sounds.iter().filter(|s| s.measure == idx) {
+            if let Some(d) = sound.event.dynamics {
+                dynamics.push((
+                    sound.onset,
+                    (d / 100.0 * 90.0).round().clamp(1.0, 127.0) as u8,
+                ));
+            }
+            if let Some(bpm) = sound.event.tempo.filter(|t| *t > 0.0) {
+                conductor.push(tick_at(sound.onset), tempo(bpm));
+            }
+        }
+        dynamics.sort_by_key(|d| d.0);
+
+        for (n, timed) in timeline.notes.iter().enumerate() {
+            let note = timed.event;
+            if timed.measure != idx || note.cue {
+                continue;
+            }
+            let velocity = dynamics
+                .iter()
+                .rev()
+                .find(|d| d.0 <= timed.onset)
+                .map_or(velocity, |d| d.1);
+
+            let Some((channel, key)) = note_key(note, score_part, channel) else {
+                continue;
+            };
+            let performed = &sounding_times[n];
+            if performed.duration <= Rational::from_integer(0) {
+                continue;
+            }
+
+            let on = tick_at(performed.onset);
+            let off = tick_at(performed.end());
+
+            let tie = if note.tie_stop() {
+                open_ties.remove(&(channel, key))
+            } else {
+                None
+            };
+            let sounding = match tie {
+                Some(sounding) => {
+                    notes[sounding].off = off;
+                    sounding
+                }
+                None => {
+                    notes.push(SoundingNote {
+                        channel,
+                        key,
+                        velocity,
+                        on,
+                        off,
+                    });
+                    notes.len() - 1
+                }
+            };
+            if note.tie_start() {
+                open_ties.insert((channel, key), sounding);
+            }
+        }
+
+        if let Some((_, v)) = dynamics.last() {
+            velocity = *v;
+        }
+        playback += span.duration;
+    }
+
+    // Unison voices sound one key once: overlapping notes of a key become one note
+    // that lasts until the latest of them ends.
+    notes.sort_by_key(|n| (n.channel, n.key, n.on));
+    let mut merged: Vec<SoundingNote> = vec![];
+    for note in notes {
+        match merged.last_mut() {
+            Some(last)
+                if (last.channel, last.key) == (note.channel, note.key) && note.on < last.off =>
+            {
+                last.off = last.off.max(note.off);
+            }
+            _ => merged.push(note),
+        }
+    }
+
+    for note in merged {
+        let key = u7::new(note.key);
+        let vel = u7::new(note.velocity.clamp(1, 127));
+        events.push(
+            note.on,
+            midi(note.channel, MidiMessage::NoteOn { key, vel }),
+        );
+        events.push(
+            note.off,
+            midi(note.channel, MidiMessage::NoteOff { key, vel }),
+        );
+    }
+}
+
+/// The channel of a part: the one of its first `<midi-instrument>`, the percussion
+/// channel for parts with unpitched notes, or the next free melodic channel.
+fn part_channel(part: &Part, score_part: Option<&ScorePart>, next_channel: &mut u8) -> u8 {
+    let assigned = score_part
+        .and_then(|p| p.midi_instruments().next())
+        .map(|i| i.midi_channel)
+        .filter(|c| (1..=16).contains(c));
+    if let Some(channel) = assigned {
+        return channel - 1;
+    }
+
+    let percussion = part.measures.iter().any(|m| {
+        m.content
+            .iter()
+            .any(|c| matches!(c, MeasureContent::Note(n) if n.unpitched.is_some()))
+    });
+    if percussion {
+        return PERCUSSION_CHANNEL;
+    }
+
+    if *next_channel == PERCUSSION_CHANNEL {
+        *next_channel += 1;
+    }
+    let channel = *next_channel % 16;
+    *next_channel = (*next_channel + 1) % 16;
+    channel
+}
+
+/// Channel and key a note sounds on. Unpitched notes use the channel and key of their
+/// percussion instrument. Rests and unresolved percussion notes give `None`.
+fn note_key(note: &Note, score_part: Option<&ScorePart>, channel: u8) -> Option<(u8, u8)> {
+    if note.rest {
+        return None;
+    }
+
+    if let Some(pitch) = &note.pitch {
+        return Some((channel, pitch.midi_number().round().clamp(0.0, 127.0) as u8));
+    }
+
+    let score_part = score_part?;
+    let instrument = score_part.percussion_instrument(note)?;
+    let channel = score_part
+        .midi_instruments()
+        .find(|i| i.id == instrument.id)
+        .map(|i| i.midi_channel)
+        .filter(|c| (1..=16).contains(c))
+        .map_or(channel, |c| c - 1);
+    Some((channel, instrument.midi_key?.min(127)))
+}
+
+/// MIDI velocity of a dynamics marking, following the values common notation
+/// programs use. Accents like `sfz` are not sustained levels and give `None`.
+fn dynamics_velocity(dynamics: &Dynamics) -> Option<u8> {
+    let velocity = match dynamics {
+        Dynamics::PPPPPP => 5,
+        Dynamics::PPPPP => 10,
+        Dynamics::PPPP => 15,
+        Dynamics::PPP => 23,
+        Dynamics::PP => 33,
+        Dynamics::P => 49,
+        Dynamics::MP => 64,
+        Dynamics::MF => 80,
+        Dynamics::F => 96,
+        Dynamics::FF => 112,
+        Dynamics::FFF => 120,
+        Dynamics::FFFF => 124,
+        Dynamics::FFFFF => 126,
+        Dynamics::FFFFFF => 127,
+        Dynamics::FP | Dynamics::SFP => 49,
+        Dynamics::SFPP => 33,
+        _ => return None,
+    };
+    Some(velocity)
+}
+
+fn ticks(whole_notes: Rational) -> u64 {
+    let ticks = whole_notes * 4 * TICKS_PER_QUARTER as i64;
+    ticks.round().to_integer().max(0) as u64
+}
+
+fn tempo(quarters_per_minute: f32) -> TrackEventKind<'static> {
+    let micros = (60_000_000.0 / quarters_per_minute).round() as u32;
+    TrackEventKind::Meta(MetaMessage::Tempo(u24::new(micros.min(0xff_ffff))))
+}
+
+fn is_tempo(kind: &TrackEventKind) -> bool {
+    matches!(kind, TrackEventKind::Meta(MetaMessage::Tempo(_)))
+}
+
+fn midi(channel: u8, message: MidiMessage) -> TrackEventKind<'static> {
+    TrackEventKind::Midi {
+        channel: u4::new(channel),
+        message,
+    }
+}
+
+/// How [`read_midi`] quantizes a MIDI file into measures.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MidiImportOptions {
+    pub beats: u8,
+    pub beat_type: u8,
+
+    /// Divisions per quarter note: 1, 2, 4, 8 or 16. Onsets and durations are
+    /// rounded to this grid, so 4 quantizes to sixteenth notes.
+    pub divisions: usize,
+}
+
+impl Default for MidiImportOptions {
+    fn default() -> Self {
+        MidiImportOptions {
+            beats: 4,
+            beat_type: 4,
+            divisions: 4,
+        }
+    }
+}
+
+/// A note of a MIDI file, quantized to divisions.
+struct RecordedNote {
+    key: u8,
+    on: usize,
+    off: usize,
+}
+
+#[derive(Default)]
+struct RecordedPart {
+    name: Option<String>,
+    channel: u8,
+    program: Option<u8>,
+    notes: Vec<RecordedNote>,
+}
+
+/// Reads a type 0 or type 1 Standard MIDI File into a score with one part per track
+/// and channel. Notes are quantized to `options.divisions`, spelled in an estimated
+/// key, split into voices where they overlap and tied across barlines.
+pub fn read_midi(
+    bytes: &[u8],
+    options: &MidiImportOptions,
+) -> std::result::Result<ScorePartwise, MusicXmlError> {
+    let smf = Smf::parse(bytes)?;
+    let ticks_per_quarter = match smf.header.timing {
+        Timing::Metrical(t) if t.as_int() > 0 => t.as_int() as u64,
+        Timing::Metrical(_) => {
+            return Err(MusicXmlError::Generic(
+                "MIDI file has zero ticks per quarter note".to_string(),
+            ))
+        }
+        Timing::Timecode(..) => {
+            return Err(MusicXmlError::Generic(
+                "MIDI files with timecode timing are not supported".to_string(),
+            ))
+        }
+    };
+
+    // Every quantized length is a whole number of divisions, so it can be written
+    // as notes if a single division is a note value.
+    if !options.divisions.is_power_of_two() || options.divisions > 16 {
+        return Err(MusicXmlError::Generic(format!(
+            "{} divisions per quarter is not a note value down to 64ths",
+            options.divisions
+        )));
+    }
+
+    // A measure is beats / beat_type whole notes, so beats * 4 * divisions / beat_type
+    // divisions long.
+    let beats_divisions = options.beats as usize * 4 * options.divisions;
+    if options.beats == 0
+        || options.beat_type == 0
+        || !beats_divisions.is_multiple_of(options.beat_type as usize)
+    {
+        return Err(MusicXmlError::Generic(format!(
+            "{} divisions cannot express a {}/{} measure",
+            options.divisions, options.beats, options.beat_type
+        )));
+    }
+    let measure_len = beats_divisions / options.beat_type as usize;
+
+    let quantize = |tick: u64| {
+        ((tick * options.divisions as u64 + ticks_per_quarter / 2) / ticks_per_quarter) as usize
+    };
+
+    let mut recorded: Vec<RecordedPart> = vec![];
+    for track in &smf.tracks {
+        let mut name = None;
+        let mut tick = 0;
+        let mut pending: HashMap<(u8, u8), Vec<u64>> = HashMap::new();
+        let mut channels: BTreeMap<u8, RecordedPart> = BTreeMap::new();
+
+        for event in track {
+            tick += event.delta.as_int() as u64;
+            let (channel, message) = match event.kind {
+                TrackEventKind::Meta(MetaMessage::TrackName(n)) => {
+                    name = Some(String::from_utf8_lossy(n).trim().to_string());
+                    continue;
+                }
+                TrackEventKind::Midi { channel, message } => (channel.as_int(), message),
+                _ => continue,
+            };
+            let part = channels.entry(channel).or_insert_with(|| RecordedPart {
+                channel,
+                ..RecordedPart::default()
+            });
+
+            match message {
+                MidiMessage::NoteOn { key, vel } if vel > 0 => {
+                    pending
+                        .entry((channel, key.as_int()))
+                        .or_default()
+                        .push(tick);
+                }
+                MidiMessage::NoteOn { key, .. } | MidiMessage::NoteOff { key, .. } => {
+                    let starts = pending.entry((channel, key.as_int())).or_default();
+                    if !starts.is_empty() {
+                        let on = quantize(starts.remove(0));
+                        part.notes.push(RecordedNote {
+                            key: key.as_int(),
+                            on,
+                            off: quantize(tick).max(on + 1),
+                        });
+                    }
+                }
+                MidiMessage::ProgramChange { program } => part.program = Some(program.as_int()),
+                _ => {}
+            }
+        }
+
+        for (_, mut part) in channels {
+            if !part.notes.is_empty() {
+                part.name = name.clone().filter(|n| !n.is_empty());
+                recorded.push(part);
+            }
+        }
+    }
+
+    let key = Key::estimate(
+        recorded
+            .iter()
+            .flat_map(|p| &p.notes)
+            .map(|n| (n.key, (n.off - n.on) as f32)),
+    );
+
+    let mut part_list = PartList::default();
+    let mut parts = vec![];
+    for (idx, recorded) in recorded.iter().enumerate() {
+        let id = format!("P{}", idx + 1);
+        let instrument_id = format!("{}-I1", id);
+        let name = recorded
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("Part {}", idx + 1));
+
+        part_list.parts.push(PartListContent::ScorePart(ScorePart {
+            identification: None,
+            id: id.clone(),
+            content: vec![
+                ScorePartContent::PartName(name.clone()),
+                ScorePartContent::ScoreInstrument(ScoreInstrument {
+                    id: instrument_id.clone(),
+                    instrument_name: name,
+                    instrument_sound: String::new(),
+                }),
+                ScorePartContent::MidiInstrument(MidiInstrument {
+                    id: instrument_id,
+                    midi_channel: recorded.channel + 1,
+                    midi_program: recorded.program.unwrap_or(0) + 1,
+                    ..MidiInstrument::default()
+                }),
+            ],
+        }));
+
+        parts.push(Part {
+            id,
+            measures: quantized_measures(recorded, &key, measure_len, options),
+        });
+    }
+
+    Ok(ScorePartwise {
+        work: None,
+        movement_number: None,
+        movement_title: None,
+        version: MUSICXML_VERSION.to_string(),
+        part_list,
+        parts,
+        identification: None,
+        defaults: None,
+        credits: vec![],
+    })
+}
+
+/// Notes with the same onset and end, written as one chord.
+struct Chord {
+    onset: usize,
+    end: usize,
+    keys: Vec<u8>,
+}
+
+fn quantized_measures(
+    recorded: &RecordedPart,
+    key: &Key,
+    measure_len: usize,
+    options: &MidiImportOptions,
+) -> Vec<Measure> {
+    let mut notes: Vec<&RecordedNote> = recorded.notes.iter().collect();
+    notes.sort_by_key(|n| (n.on, n.off, n.key));
+
+    let mut chords: Vec<Chord> = vec![];
+    for note in notes {
+        match chords.last_mut() {
+            Some(c) if c.onset == note.on && c.end == note.off => {
+                if !c.keys.contains(&note.key) {
+                    c.keys.push(note.key);
+                }
+            }
+            _ => chords.push(Chord {
+                onset: note.on,
+                end: note.off,
+                keys: vec![note.key],
+            }),
+        }
+    }
+
+    // Overlapping chords go to the first voice that is free at their onset.
+    let mut voices: Vec<Vec<Chord>> = vec![];
+    for chord in chords {
+        match voices
+            .iter_mut()
+            .find(|v| v.last().is_some_and(|c| c.end <= chord.onset))
+        {
+            Some(voice) => voice.push(chord),
+            None => voices.push(vec![chord]),
+        }
+    }
+
+    let end = voices
+        .iter()
+        .filter_map(|v| v.last())
+        .map(|c| c.end)
+        .max()
+        .unwrap_or(0);
+    let measure_count = end.div_ceil(measure_len).max(1);
+
+    let mut content: Vec<Vec<Vec<Note>>> = vec![];
+    for (idx, voice) in voices.iter().enumerate() {
+        let mut writer = VoiceWriter {
+            measures: (0..measure_count).map(|_| vec![]).collect(),
+            voice: idx as u8 + 1,
+            fifths: key.fifths,
+            measure_len,
+            divisions: options.divisions,
+        };
+        let mut cursor = 0;
+        for chord in voice {
+            writer.push(cursor, chord.onset, &[]);
+            writer.push(chord.onset, chord.end, &chord.keys);
+            cursor = chord.end;
+        }
+        writer.push(cursor, measure_count * measure_len, &[]);
+        content.push(writer.measures);
+    }
+
+    let low = recorded.notes.iter().map(|n| n.key as f32).sum::<f32>()
+        < 60.0 * recorded.notes.len() as f32;
+    let mut attributes = Attributes::empty();
+    attributes.divisions = Some(options.divisions);
+    attributes.keys = vec![key.clone()];
+    attributes.times = vec![Time::new(options.beats as u32, options.beat_type as u32)];
+    attributes.clefs = vec![Clef {
+        sign: if low { ClefSign::F } else { ClefSign::G },
+        line: if low { 4 } else { 2 },
+        octave_change: 0,
+        number: 0,
+    }];
+
+    let mut attributes = Some(attributes);
+    (0..measure_count)
+        .map(|m| {
+            let mut measure = Measure {
+                number: (m + 1).to_string(),
+                ..Measure::default()
+            };
+            if let Some(attributes) = attributes.take() {
+                measure.content.push(MeasureContent::Attributes(attributes));
+            }
+            for (idx, voice) in content.iter_mut().enumerate() {
+                if idx > 0 {
+                    measure.content.push(MeasureContent::Backup(Backup {
+                        duration: measure_len,
+                        footnote: None,
+                        level: None,
+                    }));
+                }
+                let notes = std::mem::take(&mut voice[m]);
+                measure
+                    .content
+                    .extend(notes.into_iter().map(MeasureContent::Note));
+            }
+            measure
+        })
+        .collect()
+}
+
+/// Collects the notes of one voice, by measure.
+struct VoiceWriter {
+    measures: Vec<Vec<Note>>,
+    voice: u8,
+    fifths: i8,
+    measure_len: usize,
+    divisions: usize,
+}
+
+impl VoiceWriter {
+    /// Writes the span from `start` to `end` as notes of `keys`, or as rests if there
+    /// are none. The span is split at barlines and into notatable values, and the
+    /// pieces of a note are tied together.
+    fn push(&mut self, start: usize, end: usize, keys: &[u8]) {
+        let mut pieces = vec![];
+        let mut position = start;
+        while position < end {
+            let measure_end = (position / self.measure_len + 1) * self.measure_len;
+            let span_end = end.min(measure_end);
+            for (notetype, dots, length) in note_values(span_end - position, self.divisions) {
+                pieces.push((position, notetype, dots, length));
+                position += length;
+            }
+        }
+
+        let count = pieces.len();
+        for (idx, (position, notetype, dots, length)) in pieces.into_iter().enumerate() {
+            let mut ties = vec![];
+            if !keys.is_empty() && idx > 0 {
+                ties.push(StartStop::Stop);
+            }
+            if !keys.is_empty() && idx + 1 < count {
+                ties.push(StartStop::Start);
+            }
+
+            let chord: Vec<Option<u8>> = if keys.is_empty() {
+                vec![None]
+            } else {
+                keys.iter().map(|k| Some(*k)).collect()
+            };
+
+            let measure = &mut self.measures[position / self.measure_len];
+            for (n, midi_key) in chord.into_iter().enumerate() {
+                measure.push(Note {
+                    duration: length,
+                    notetype: Some(notetype.clone()),
+                    pitch: midi_key.map(|k| Pitch::from_midi_number(k, self.fifths)),
+                    voice: self.voice,
+                    rest: midi_key.is_none(),
+                    dot: (0..dots).map(|_| Dot::default()).collect(),
+                    chord: n > 0,
+                    ties: ties
+                        .iter()
+                        .map(|t| Tie {
+                            r#type: t.clone(),
+                            ..Tie::default()
+                        })
+                        .collect(),
+                    notations: (!ties.is_empty()).then(|| Notations {
+                        notations: ties.iter().cloned().map(tied).collect(),
+                    }),
+                    ..Note::default()
+                });
+            }
+        }
+    }
+}
+
+fn tied(r#type: StartStop) -> NotationType {
+    NotationType::Tied(NotationTypeMeta {
+        r#type: r#type.into(),
+        ..NotationTypeMeta::default()
+    })
+}
+
+/// Splits `length` divisions into the largest notatable values, dotted or not.
+/// `divisions` must make a single division a note value, as [`read_midi`] checks.
+fn note_values(mut length: usize, divisions: usize) -> Vec<(DurationType, u8, usize)> {
+    let types = [
+        DurationType::Breve,
+        DurationType::Whole,
+        DurationType::Half,
+        DurationType::Quarter,
+        DurationType::Eighth,
+        DurationType::Sixteenth,
+        DurationType::Thirtysecond,
+        DurationType::Sixtyfourth,
+    ];
+    let mut values = vec![];
+    for notetype in types {
+        for dots in [1, 0] {
+            let scale = if dots == 1 {
+                Rational::new(3, 2)
+            } else {
+                Rational::from(1)
+            };
+            let value = notetype.whole_notes() * scale * 4 * divisions as i64;
+            if value.is_integer() {
+                values.push((notetype.clone(), dots, value.to_integer() as usize));
+            }
+        }
+    }
+
+    let mut pieces = vec![];
+    while length > 0 {
+        let value = values
+            .iter()
+            .find(|v| v.2 <= length)
+            .expect("a single division is a note value");
+        pieces.push(value.clone());
+        length -= value.2;
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_midi, MidiImportOptions, MidiOptions, TICKS_PER_QUARTER};
+    use crate::musicxml::{
+        harmony::Step,
+        measure::MeasureContent,
+        note::{NotationType, Note},
+        score_partwise::ScorePartwise,
+        tied_type::TiedType,
+    };
+    use midly::{
+        Format, Header, MetaMessage, MidiMessage, Smf, Timing, TrackEvent, TrackEventKind,
+    };
+    use serde_xml_rs::from_str;
+
+    /// Note ons of a track as `(tick, channel, key, velocity)`.
+    fn note_ons(track: &[TrackEvent]) -> Vec<(u32, u8, u8, u8)> {
+        let mut tick = 0;
+        let mut notes = vec![];
+        for event in track {
+            tick += event.delta.as_int();
+            if let TrackEventKind::Midi {
+                channel,
+                message: MidiMessage::NoteOn { key, vel },
+            } = event.kind
+            {
+                notes.push((tick, channel.as_int(), key.as_int(), vel.as_int()));
+            }
+        }
+        notes
+    }
+
+    const SCORE: &str = r#"
+        <score-partwise version="4.0">
+            <part-list>
+                <score-part id="P1">
+                    <part-name>Violin</part-name>
+                    <score-instrument id="P1-I1"><instrument-name>Violin</instrument-name></score-instrument>
+                    <midi-instrument id="P1-I1">
+                        <midi-channel>2</midi-channel>
+                        <midi-program>41</midi-program>
+                    </midi-instrument>
+                </score-part>
+            </part-list>
+            <part id="P1">
+                <measure number="1">
+                    <attributes>
+                        <divisions>1</divisions>
+                        <time><beats>4</beats><beat-type>4</beat-type></time>
+                    </attributes>
+                    <direction>
+                        <direction-type><dynamics><p/></dynamics></direction-type>
+                        <sound tempo="90"/>
+                    </direction>
+                    <note>
+                        <pitch><step>C</step><octave>4</octave></pitch>
+                        <duration>2</duration><type>half</type>
+                    </note>
+                    <note>
+                        <pitch><step>D</step><octave>4</octave></pitch>
+                        <duration>2</duration><type>half</type>
+                        <notations><tied type="start"/></notations>
+                    </note>
+                </measure>
+                <measure number="2">
+                    <note>
+                        <pitch><step>D</step><octave>4</octave></pitch>
+                        <duration>2</duration><type>half</type>
+                        <notations><tied type="stop"/></notations>
+                    </note>
+                    <note>
+                        <pitch><step>E</step><octave>4</octave></pitch>
+                        <duration>2</duration><type>half</type>
+                    </note>
+                    <barline location="right">
+                        <bar-style>light-heavy</bar-style>
+                        <repeat direction="backward"/>
+                    </barline>
+                </measure>
+            </part>
+        </score-partwise>"#;
+
+    #[test]
+    fn export() {
+        let score: ScorePartwise = from_str(SCORE).unwrap();
+        let bytes = score.to_midi(&MidiOptions {
+            unroll_repeats: false,
+            ..MidiOptions::default()
+        });
+        let smf = Smf::parse(&bytes).unwrap();
+
+        assert_eq!(smf.tracks.len(), 2);
+        assert!(smf.tracks[0]
+            .iter()
+            .any(|e| e.kind == TrackEventKind::Meta(MetaMessage::Tempo(666_667.into()))));
+        assert!(smf.tracks[0]
+            .iter()
+            .any(|e| e.kind == TrackEventKind::Meta(MetaMessage::TimeSignature(4, 2, 24, 8))));
+        assert!(smf.tracks[1].iter().any(|e| matches!(
+            e.kind,
+            TrackEventKind::Midi {
+                message: MidiMessage::ProgramChange { program },
+                ..
+            } if program == 40
+        )));
+
+        let half = 2 * TICKS_PER_QUARTER as u32;
+        assert_eq!(
+            note_ons(&smf.tracks[1]),
+            vec![(0, 1, 60, 49), (half, 1, 62, 49), (3 * half, 1, 64, 49)]
+        );
+    }
+
+    #[test]
+    fn export_unrolled_repeats() {
+        let score: ScorePartwise = from_str(SCORE).unwrap();
+        let smf_bytes = score.to_midi(&MidiOptions::default());
+        let smf = Smf::parse(&smf_bytes).unwrap();
+
+        let keys: Vec<u8> = note_ons(&smf.tracks[1]).iter().map(|n| n.2).collect();
+        assert_eq!(keys, vec![60, 62, 64, 60, 62, 64]);
+
+        // The tied D sounds for three half notes.
+        let mut tick = 0;
+        let mut off = None;
+        for event in &smf.tracks[1] {
+            tick += event.delta.as_int();
+            if let TrackEventKind::Midi {
+                message: MidiMessage::NoteOff { key, .. },
+                ..
+            } = event.kind
+            {
+                if key == 62 {
+                    off.get_or_insert(tick);
+                }
+            }
+        }
+        assert_eq!(off, Some(6 * TICKS_PER_QUARTER as u32));
+    }
+
+    #[test]
+    fn export_percussion() {
+        let xml = r#"
+            <score-partwise version="4.0">
+                <part-list>
+                    <score-part id="P1">
+                        <part-name>Drums</part-name>
+                        <score-instrument id="P1-I36"><instrument-name>Bass Drum</instrument-name></score-instrument>
+                        <score-instrument id="P1-I39"><instrument-name>Snare</instrument-name></score-instrument>
+                        <midi-instrument id="P1-I36"><midi-unpitched>36</midi-unpitched></midi-instrument>
+                        <midi-instrument id="P1-I39"><midi-unpitched>39</midi-unpitched></midi-instrument>
+                    </score-part>
+                </part-list>
+                <part id="P1">
+                    <measure number="1">
+                        <attributes><divisions>1</divisions></attributes>
+                        <note>
+                            <unpitched><display-step>F</display-step><display-octave>4</display-octave></unpitched>
+                            <duration>1</duration><instrument id="P1-I36"/><type>quarter</type>
+                        </note>
+                        <note>
+                            <unpitched><display-step>C</display-step><display-octave>5</display-octave></unpitched>
+                            <duration>1</duration><instrument id="P1-I39"/><type>quarter</type>
+                        </note>
+                    </measure>
+                </part>
+            </score-partwise>"#;
+        let score: ScorePartwise = from_str(xml).unwrap();
+        let smf_bytes = score.to_midi(&MidiOptions::default());
+        let smf = Smf::parse(&smf_bytes).unwrap();
+
+        assert_eq!(
+            note_ons(&smf.tracks[1]),
+            vec![(0, 9, 35, 80), (TICKS_PER_QUARTER as u32, 9, 38, 80)]
+        );
+    }
+
+    #[test]
+    fn export_unison_voices() {
+        let xml = r#"
+            <score-partwise version="4.0">
+                <part-list><score-part id="P1"><part-name>Piano</part-name></score-part></part-list>
+                <part id="P1">
+                    <measure number="1">
+                        <attributes><divisions>1</divisions></attributes>
+                        <note>
+                            <pitch><step>C</step><octave>4</octave></pitch>
+                            <duration>1</duration><voice>1</voice><type>quarter</type>
+                        </note>
+                        <backup><duration>1</duration></backup>
+                        <note>
+                            <pitch><step>C</step><octave>4</octave></pitch>
+                            <duration>2</duration><voice>2</voice><type>half</type>
+                        </note>
+                    </measure>
+                </part>
+            </score-partwise>"#;
+        let score: ScorePartwise = from_str(xml).unwrap();
+        let smf_bytes = score.to_midi(&MidiOptions::default());
+        let smf = Smf::parse(&smf_bytes).unwrap();
+
+        assert_eq!(note_ons(&smf.tracks[1]).len(), 1);
+        let mut tick = 0;
+        let mut offs = vec![];
+        for event in &smf.tracks[1] {
+            tick += event.delta.as_int();
+            if let TrackEventKind::Midi {
+                message: MidiMessage::NoteOff { .. },
+                ..
+            } = event.kind
+            {
+                offs.push(tick);
+            }
+        }
+        assert_eq!(offs, vec![2 * TICKS_PER_QUARTER as u32]);
+    }
+
+    fn notes(content: &[MeasureContent]) -> Vec<&Note> {
+        content
+            .iter()
+            .filter_map(|c| match c {
+                MeasureContent::Note(n) => Some(n),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn ties(note: &Note) -> Vec<TiedType> {
+        note.notations
+            .iter()
+            .flat_map(|n| &n.notations)
+            .filter_map(|t| match t {
+                NotationType::Tied(meta) => Some(meta.r#type.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn import_ties_across_barlines() {
+        let score: ScorePartwise = from_str(SCORE).unwrap();
+        let bytes = score.to_midi(&MidiOptions {
+            unroll_repeats: false,
+            ..MidiOptions::default()
+        });
+        let options = MidiImportOptions {
+            divisions: 1,
+            ..MidiImportOptions::default()
+        };
+        let imported = read_midi(&bytes, &options).unwrap();
+
+        let score_part = imported.score_part("P1").unwrap();
+        assert_eq!(score_part.part_name(), Some("Violin"));
+        let instrument = score_part.midi_instruments().next().unwrap();
+        assert_eq!((instrument.midi_channel, instrument.midi_program), (2, 41));
+
+        let measures = &imported.parts[0].measures;
+        assert_eq!(measures.len(), 2);
+        assert!(matches!(
+            measures[0].content[0],
+            MeasureContent::Attributes(_)
+        ));
+
+        let first = notes(&measures[0].content);
+        let second = notes(&measures[1].content);
+        assert_eq!(first.len(), 2);
+        assert_eq!(second.len(), 2);
+        assert_eq!(first[1].pitch.as_ref().unwrap().step, Step::D);
+        assert_eq!(first[1].duration, 2);
+        assert_eq!(ties(first[1]), vec![TiedType::Start]);
+        assert_eq!(second[0].pitch.as_ref().unwrap().step, Step::D);
+        assert_eq!(ties(second[0]), vec![TiedType::Stop]);
+        assert_eq!(second[1].pitch.as_ref().unwrap().step, Step::E);
+
+        assert!(crate::parse_str(&imported.to_xml()).is_ok());
+
+        for divisions in [3, 32] {
+            let options = MidiImportOptions {
+                divisions,
+                ..MidiImportOptions::default()
+            };
+            assert!(read_midi(&bytes, &options).is_err());
+        }
+        let options = MidiImportOptions {
+            beats: 0,
+            ..MidiImportOptions::default()
+        };
+        assert!(read_midi(&bytes, &options).is_err());
+    }
+
+    #[test]
+    fn import_overlapping_voices() {
+        let event = |delta: u32, kind| TrackEvent {
+            delta: delta.into(),
+            kind,
+        };
+        let on = |key: u8| TrackEventKind::Midi {
+            channel: 0.into(),
+            message: MidiMessage::NoteOn {
+                key: key.into(),
+                vel: 64.into(),
+            },
+        };
+        let off = |key: u8| TrackEventKind::Midi {
+            channel: 0.into(),
+            message: MidiMessage::NoteOff {
+                key: key.into(),
+                vel: 0.into(),
+            },
+        };
+        let track = vec![
+            event(0, TrackEventKind::Meta(MetaMessage::TrackName(b"Piano"))),
+            event(0, on(60)),
+            event(0, on(64)),
+            event(480, off(64)),
+            event(0, on(67)),
+            event(480, off(67)),
+            event(0, off(60)),
+            event(480, on(62)),
+            event(960, off(62)),
+            event(0, TrackEventKind::Meta(MetaMessage::EndOfTrack)),
+        ];
+        let smf = Smf {
+            header: Header::new(Format::SingleTrack, Timing::Metrical(480.into())),
+            tracks: vec![track],
+        };
+        let mut bytes = vec![];
+        smf.write_std(&mut bytes).unwrap();
+
+        let imported = read_midi(&bytes, &MidiImportOptions::default()).unwrap();
+        let measures = &imported.parts[0].measures;
+        assert_eq!(measures.len(), 2);
+        assert!(measures[0]
+            .content
+            .iter()
+            .any(|c| matches!(c, MeasureContent::Backup(b) if b.duration == 16)));
+
+        let voice1 = measures[0].get_voice(1);
+        let steps: Vec<Option<Step>> = voice1
+            .iter()
+            .map(|n| n.pitch.as_ref().map(|p| p.step.clone()))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![Some(Step::E), Some(Step::G), None, Some(Step::D)]
+        );
+        assert_eq!(ties(voice1[3]), vec![TiedType::Start]);
+
+        let voice2 = measures[0].get_voice(2);
+        assert_eq!(voice2[0].pitch.as_ref().unwrap().step, Step::C);
+        assert_eq!(voice2[0].duration, 8);
+        assert!(voice2[1].rest);
+
+        let next = measures[1].get_voice(1);
+        assert_eq!(ties(next[0]), vec![TiedType::Stop]);
+        assert!(next[1].rest);
+        assert_eq!(next[1].dot.len(), 1);
+
+        let smf = Smf {
+            header: Header::new(Format::SingleTrack, Timing::Metrical(0.into())),
+            tracks: smf.tracks,
+        };
+        let mut bytes = vec![];
+        smf.write_std(&mut bytes).unwrap();
+        assert!(read_midi(&bytes, &MidiImportOptions::default()).is_err());
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 
+use crate::musicxml::writer::{Attrs, WriteXml, XmlWriter};
 use crate::prelude::*;
 
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -10,3 +11,12 @@ pub struct MidiDevice {
     #[serde(default = "u8::default")]
     pub port: u8,
 }
+
+impl WriteXml for MidiDevice {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .add_if(!self.id.is_empty(), "id", &self.id)
+            .add_if(self.port != 0, "port", &self.port);
+        w.empty(name, attrs);
+    }
+}
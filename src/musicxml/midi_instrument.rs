@@ -1,21 +1,81 @@
 use serde::{Deserialize, Serialize};
 
+use crate::musicxml::writer::{Attrs, WriteXml, XmlWriter};
 use crate::prelude::*;
 
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/midi-instrument/
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct MidiInstrument {
     #[serde(default = "String::default")]
     pub id: String,
 
-    #[serde(default = "u8::default")]
+    #[serde(rename = "midi-channel", default = "u8::default")]
     pub midi_channel: u8,
 
-    #[serde(default = "u8::default")]
+    #[serde(rename = "midi-name", default = "Option::default")]
+    pub midi_name: Option<String>,
+
+    #[serde(rename = "midi-bank", default = "Option::default")]
+    pub midi_bank: Option<u16>,
+
+    #[serde(rename = "midi-program", default = "u8::default")]
     pub midi_program: u8,
 
-    #[serde(default = "f32::default")]
-    pub volume: f32,
+    /// Percussion key, numbered 1 to 128 like the other MIDI values in MusicXML.
+    #[serde(rename = "midi-unpitched", default = "Option::default")]
+    pub midi_unpitched: Option<u8>,
+
+    #[serde(default = "Option::default")]
+    pub volume: Option<f32>,
+
+    #[serde(default = "Option::default")]
+    pub pan: Option<f32>,
+
+    #[serde(default = "Option::default")]
+    pub elevation: Option<f32>,
+}
+
+impl WriteXml for MidiInstrument {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new().add("id", &self.id));
+        if self.midi_channel != 0 {
+            w.text_element("midi-channel", &self.midi_channel);
+        }
+        w.opt_text_element("midi-name", &self.midi_name);
+        w.opt_text_element("midi-bank", &self.midi_bank);
+        if self.midi_program != 0 {
+            w.text_element("midi-program", &self.midi_program);
+        }
+        w.opt_text_element("midi-unpitched", &self.midi_unpitched);
+        w.opt_text_element("volume", &self.volume);
+        w.opt_text_element("pan", &self.pan);
+        w.opt_text_element("elevation", &self.elevation);
+        w.end(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MidiInstrument;
+    use serde_xml_rs::from_str;
+
+    #[test]
+    fn midi_instrument() {
+        let xml = r#"
+            <midi-instrument id="P1-I36">
+                <midi-channel>10</midi-channel>
+                <midi-program>1</midi-program>
+                <midi-unpitched>36</midi-unpitched>
+                <volume>80</volume>
+                <pan>0</pan>
+            </midi-instrument>"#;
+        let item: MidiInstrument = from_str(xml).unwrap();
 
-    #[serde(default = "f32::default")]
-    pub pan: f32,
+        assert_eq!(item.id, "P1-I36".to_string());
+        assert_eq!(item.midi_channel, 10);
+        assert_eq!(item.midi_program, 1);
+        assert_eq!(item.midi_unpitched, Some(36));
+        assert_eq!(item.volume, Some(80.0));
+        assert_eq!(item.elevation, None);
+    }
 }
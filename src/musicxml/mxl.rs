@@ -0,0 +1,166 @@
+use super::Score;
+use crate::error::MusicXmlError;
+use crate::prelude::*;
+use std::{
+    fs,
+    io::{Read, Seek, Write},
+    path::Path,
+};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+// https://www.w3.org/2021/06/musicxml40/tutorial/compressed-mxl-files/
+pub const MIMETYPE: &str = "application/vnd.recordare.musicxml";
+pub const MUSICXML_MEDIA_TYPE: &str = "application/vnd.recordare.musicxml+xml";
+pub const CONTAINER_PATH: &str = "META-INF/container.xml";
+
+/// Returns the path of the main score inside the archive. That is the first
+/// `<rootfile>` without a media type or with the MusicXML media type.
+pub fn rootfile_path(container: &str) -> std::result::Result<String, MusicXmlError> {
+    let doc = Document::parse(container)?;
+
+    doc.descendants()
+        .filter(|n| n.tag_name().name() == "rootfile")
+        .find(|n| match n.attribute("media-type") {
+            Some(t) => t == MUSICXML_MEDIA_TYPE,
+            None => true,
+        })
+        .and_then(|n| n.attribute("full-path"))
+        .map(|p| p.to_string())
+        .ok_or(MusicXmlError::Generic(
+            "container.xml does not name a MusicXML rootfile".to_string(),
+        ))
+}
+
+/// Opens a compressed MusicXML archive and parses its main score.
+pub fn read_mxl<R: Read + Seek>(reader: R) -> std::result::Result<Score, MusicXmlError> {
+    let mut archive = ZipArchive::new(reader)?;
+
+    let mut container = String::new();
+    archive
+        .by_name(CONTAINER_PATH)?
+        .read_to_string(&mut container)?;
+
+    let path = rootfile_path(&container)?;
+    let mut xml = String::new();
+    archive.by_name(&path)?.read_to_string(&mut xml)?;
+
+    super::parse_str(&xml)
+}
+
+pub fn read_mxl_file<P: AsRef<Path>>(path: P) -> std::result::Result<Score, MusicXmlError> {
+    read_mxl(fs::File::open(path)?)
+}
+
+/// Packages `score_xml` as `rootfile` into a compressed MusicXML archive. `extra_files`
+/// are stored next to it (e.g. a PDF rendering or images) and are not listed as rootfiles.
+pub fn write_mxl<W: Write + Seek>(
+    writer: W,
+    rootfile: &str,
+    score_xml: &str,
+    extra_files: &[(&str, &[u8])],
+) -> std::result::Result<W, MusicXmlError> {
+    let mut zip = ZipWriter::new(writer);
+
+    // The mimetype entry has to come first and must not be compressed.
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    zip.start_file("mimetype", stored)?;
+    zip.write_all(MIMETYPE.as_bytes())?;
+
+    zip.start_file(CONTAINER_PATH, deflated)?;
+    zip.write_all(container_xml(rootfile).as_bytes())?;
+
+    zip.start_file(rootfile, deflated)?;
+    zip.write_all(score_xml.as_bytes())?;
+
+    for (name, content) in extra_files {
+        zip.start_file(*name, deflated)?;
+        zip.write_all(content)?;
+    }
+
+    Ok(zip.finish()?)
+}
+
+fn container_xml(rootfile: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<container>
+  <rootfiles>
+    <rootfile full-path="{}" media-type="{}"/>
+  </rootfiles>
+</container>
+"#,
+        rootfile, MUSICXML_MEDIA_TYPE
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_mxl, read_mxl_file, rootfile_path, write_mxl, MIMETYPE};
+    use crate::musicxml::{parse_file, Score};
+    use std::{
+        fs,
+        io::{Cursor, Read},
+    };
+    use zip::ZipArchive;
+
+    #[test]
+    fn container() {
+        let xml = r#"
+            <container>
+                <rootfiles>
+                    <rootfile full-path="score.pdf" media-type="application/pdf"/>
+                    <rootfile full-path="score.musicxml"/>
+                </rootfiles>
+            </container>"#;
+
+        assert_eq!(rootfile_path(xml).unwrap(), "score.musicxml".to_string());
+        assert!(rootfile_path("<container/>").is_err());
+    }
+
+    #[test]
+    fn read_compressed() {
+        let score = read_mxl_file("resources/xml-test-files/90a-Compressed-MusicXML.mxl").unwrap();
+        match score {
+            Score::Partwise(s) => {
+                assert_eq!(s.parts.len(), 1);
+                assert!(!s.parts[0].measures.is_empty());
+            }
+            _ => panic!("Expected a partwise score."),
+        }
+
+        // parse_file picks the container reader by extension.
+        parse_file("resources/xml-test-files/90a-Compressed-MusicXML.mxl").unwrap();
+    }
+
+    #[test]
+    fn write_compressed() {
+        let xml = fs::read_to_string("xml-files/hello-world.xml").unwrap();
+        let pdf: &[u8] = b"%PDF-1.4";
+
+        let buffer = write_mxl(
+            Cursor::new(Vec::new()),
+            "hello-world.musicxml",
+            &xml,
+            &[("hello-world.pdf", pdf)],
+        )
+        .unwrap();
+
+        let mut archive = ZipArchive::new(Cursor::new(buffer.get_ref().clone())).unwrap();
+        assert_eq!(archive.len(), 4);
+
+        let mut mimetype = archive.by_index(0).unwrap();
+        assert_eq!(mimetype.name(), "mimetype");
+        assert_eq!(mimetype.compression(), zip::CompressionMethod::Stored);
+        let mut content = String::new();
+        mimetype.read_to_string(&mut content).unwrap();
+        assert_eq!(content, MIMETYPE);
+        drop(mimetype);
+
+        assert!(archive.by_name("hello-world.pdf").is_ok());
+
+        let score = read_mxl(Cursor::new(buffer.into_inner())).unwrap();
+        assert_eq!(score.into_partwise().parts[0].id, "P1".to_string());
+    }
+}
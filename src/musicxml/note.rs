@@ -1,21 +1,46 @@
-use serde::{Deserialize, Serialize};
+use serde::{de::IgnoredAny, Deserialize, Serialize};
 use strum_macros::EnumString;
 
 use crate::musicxml::{
-    core::{DirectionUD, Duration, DurationType, Placement},
+    core::{DirectionUD, Duration, DurationType, Placement, TopBottom},
     pitch::parse_option_pitch,
 };
 use crate::prelude::*;
 use std::str::FromStr;
 
+use super::accidental::{Accidental, NoteAccidental};
 use super::articulations::{ArticulationType, Articulations};
+use super::beam::Beam;
+use super::dynamics::{write_dynamics, Dynamics};
+use super::fermata::Fermata;
+use super::grace::Grace;
 use super::harmony::Pitch;
 use super::lyric::Lyric;
+use super::notehead::Notehead;
+use super::ornaments::Ornaments;
+use super::printable_value::PrintableValue;
+use super::start_stop_continue::StartStopContinue;
 use super::stem::Stem;
+use super::technical::Technical;
+use super::tie::Tie;
+use super::tied_type::TiedType;
+use super::time_modification::TimeModification;
+use super::timeline::Rational;
+use super::tuplet::Tuplet;
+use super::unpitched::Unpitched;
+use super::writer::{Attrs, WriteXml, XmlWriter};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default)]
 pub struct Dot {}
 
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/instrument/
+/// Reference from a note to a `<score-instrument>` of its part.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct Instrument {
+    #[serde(default = "String::default")]
+    pub id: String,
+}
+
 // https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/notations/
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct Notations {
@@ -23,17 +48,35 @@ pub struct Notations {
     pub notations: Vec<NotationType>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(from = "NoteXml")]
 pub struct Note {
+    #[serde(default = "Option::default")]
+    pub grace: Option<Grace>,
+
+    /// A cue note, shown small and not played.
+    #[serde(default = "bool::default")]
+    pub cue: bool,
+
     #[serde(default = "Duration::default")]
     pub duration: Duration,
 
-    #[serde(rename = "type")]
-    pub notetype: DurationType,
+    #[serde(rename = "tie", default = "Vec::default")]
+    pub ties: Vec<Tie>,
+
+    /// Absent for whole-measure rests and some unmeasured notes.
+    #[serde(rename = "type", default = "Option::default")]
+    pub notetype: Option<DurationType>,
 
     #[serde(rename = "pitch")]
     pub pitch: Option<Pitch>,
 
+    #[serde(default = "Option::default")]
+    pub unpitched: Option<Unpitched>,
+
+    #[serde(rename = "instrument", default = "Vec::default")]
+    pub instruments: Vec<Instrument>,
+
     #[serde(default = "u8::default")]
     pub voice: u8,
 
@@ -46,9 +89,21 @@ pub struct Note {
     #[serde(default = "Vec::default")]
     pub dot: Vec<Dot>,
 
+    #[serde(default = "Option::default")]
+    pub accidental: Option<NoteAccidental>,
+
+    #[serde(rename = "time-modification", default = "Option::default")]
+    pub time_modification: Option<TimeModification>,
+
     #[serde(default = "Option::default")]
     pub stem: Option<Stem>,
 
+    #[serde(default = "Option::default")]
+    pub notehead: Option<Notehead>,
+
+    #[serde(rename = "beam", default = "Vec::default")]
+    pub beams: Vec<Beam>,
+
     #[serde(default = "usize::default")]
     pub position: usize,
 
@@ -81,7 +136,199 @@ pub struct Note {
     pub default_y: Option<f32>,
 }
 
-#[derive(Debug, EnumString, PartialEq, Serialize, Deserialize, Default, PartialOrd)]
+#[derive(Deserialize)]
+enum NoteChild {
+    #[serde(rename = "grace")]
+    Grace(Grace),
+    #[serde(rename = "chord")]
+    Chord(IgnoredAny),
+    #[serde(rename = "pitch")]
+    Pitch(Pitch),
+    #[serde(rename = "unpitched")]
+    Unpitched(Unpitched),
+    #[serde(rename = "rest")]
+    Rest(IgnoredAny),
+    #[serde(rename = "cue")]
+    Cue(IgnoredAny),
+    #[serde(rename = "duration")]
+    Duration(Duration),
+    #[serde(rename = "tie")]
+    Tie(Tie),
+    #[serde(rename = "instrument")]
+    Instrument(Instrument),
+    #[serde(rename = "footnote")]
+    Footnote(IgnoredAny),
+    #[serde(rename = "level")]
+    Level(IgnoredAny),
+    #[serde(rename = "voice")]
+    Voice(u8),
+    #[serde(rename = "type")]
+    Type(DurationType),
+    #[serde(rename = "dot")]
+    Dot(Dot),
+    #[serde(rename = "accidental")]
+    Accidental(NoteAccidental),
+    #[serde(rename = "time-modification")]
+    TimeModification(TimeModification),
+    #[serde(rename = "stem")]
+    Stem(Stem),
+    #[serde(rename = "notehead")]
+    Notehead(Notehead),
+    #[serde(rename = "notehead-text")]
+    NoteheadText(IgnoredAny),
+    #[serde(rename = "staff")]
+    Staff(u8),
+    #[serde(rename = "beam")]
+    Beam(Beam),
+    #[serde(rename = "notations")]
+    Notations(Notations),
+    #[serde(rename = "lyric")]
+    Lyric(Lyric),
+    #[serde(rename = "play")]
+    Play(IgnoredAny),
+    #[serde(rename = "listen")]
+    Listen(IgnoredAny),
+}
+
+// serde_xml_rs loses track of the following sibling when a struct field is a
+// repeated element such as `<dot/>`, so elements with repeated children are read as
+// a flat list of them, like here, and converted with `#[serde(from = ...)]`.
+#[derive(Deserialize)]
+struct NoteXml {
+    #[serde(default = "Option::default")]
+    attack: Option<f32>,
+
+    #[serde(default = "Option::default")]
+    color: Option<String>,
+
+    #[serde(rename = "default-x", default = "Option::default")]
+    default_x: Option<f32>,
+
+    #[serde(rename = "default-y", default = "Option::default")]
+    default_y: Option<f32>,
+
+    #[serde(rename = "$value", default = "Vec::default")]
+    children: Vec<NoteChild>,
+}
+
+impl From<NoteXml> for Note {
+    fn from(xml: NoteXml) -> Note {
+        let mut note = Note {
+            grace: None,
+            cue: false,
+            duration: Duration::default(),
+            ties: vec![],
+            notetype: None,
+            pitch: None,
+            unpitched: None,
+            instruments: vec![],
+            voice: 0,
+            staff: 0,
+            rest: false,
+            dot: vec![],
+            accidental: None,
+            time_modification: None,
+            stem: None,
+            notehead: None,
+            beams: vec![],
+            position: 0,
+            chord: false,
+            chord_notes: vec![],
+            lyrics_above: vec![],
+            lyrics_below: vec![],
+            notations: None,
+            attack: xml.attack,
+            color: xml.color,
+            default_x: xml.default_x,
+            default_y: xml.default_y,
+        };
+
+        for child in xml.children {
+            match child {
+                NoteChild::Grace(grace) => note.grace = Some(grace),
+                NoteChild::Cue(_) => note.cue = true,
+                NoteChild::Chord(_) => note.chord = true,
+                NoteChild::Pitch(pitch) => note.pitch = Some(pitch),
+                NoteChild::Unpitched(unpitched) => note.unpitched = Some(unpitched),
+                NoteChild::Rest(_) => note.rest = true,
+                NoteChild::Duration(duration) => note.duration = duration,
+                NoteChild::Tie(tie) => note.ties.push(tie),
+                NoteChild::Instrument(instrument) => note.instruments.push(instrument),
+                NoteChild::Voice(voice) => note.voice = voice,
+                NoteChild::Type(t) => note.notetype = Some(t),
+                NoteChild::Dot(dot) => note.dot.push(dot),
+                NoteChild::TimeModification(t) => note.time_modification = Some(t),
+                NoteChild::Accidental(a) => note.accidental = Some(a),
+                NoteChild::Stem(stem) => note.stem = Some(stem),
+                NoteChild::Notehead(n) => note.notehead = Some(n),
+                NoteChild::Staff(staff) => note.staff = staff,
+                NoteChild::Beam(beam) => note.beams.push(beam),
+                NoteChild::Notations(notations) => match note.notations.as_mut() {
+                    Some(n) => n.notations.extend(notations.notations),
+                    None => note.notations = Some(notations),
+                },
+                NoteChild::Lyric(lyric) => match lyric.placement {
+                    Some(Placement::Above) => note.lyrics_above.push(lyric),
+                    _ => note.lyrics_below.push(lyric),
+                },
+                _ => {}
+            }
+        }
+
+        note
+    }
+}
+
+impl Note {
+    pub fn is_grace(&self) -> bool {
+        self.grace.is_some()
+    }
+
+    /// The length implied by the note type, dots and tuplet ratio, in whole notes.
+    /// Zero for grace notes and `None` without a type. Unlike `duration` this does
+    /// not depend on divisions.
+    pub fn notated_duration(&self) -> Option<Rational> {
+        if self.is_grace() {
+            return Some(Rational::from_integer(0));
+        }
+        let base = self.notetype.as_ref()?.whole_notes();
+        let mut length = base;
+        let mut dot = base;
+        for _ in &self.dot {
+            dot /= 2;
+            length += dot;
+        }
+        match &self.time_modification {
+            Some(t) => Some(length * t.ratio()),
+            None => Some(length),
+        }
+    }
+
+    /// Whether a tie starts at this note. `<tie>` is authoritative; the `<tied>`
+    /// notation is only consulted when the note has no `<tie>` at all.
+    pub fn tie_start(&self) -> bool {
+        self.has_tie(StartStop::Start)
+    }
+
+    /// Whether a tie from a previous note ends at this note.
+    pub fn tie_stop(&self) -> bool {
+        self.has_tie(StartStop::Stop)
+    }
+
+    fn has_tie(&self, r#type: StartStop) -> bool {
+        if !self.ties.is_empty() {
+            return self.ties.iter().any(|t| t.r#type == r#type);
+        }
+        let tied = TiedType::from(r#type);
+        self.notations.as_ref().is_some_and(|n| {
+            n.notations
+                .iter()
+                .any(|t| matches!(t, NotationType::Tied(meta) if meta.r#type == tied))
+        })
+    }
+}
+
+#[derive(Debug, EnumString, PartialEq, Serialize, Deserialize, Default, PartialOrd, Clone)]
 pub enum StartStop {
     #[strum(serialize = "start")]
     #[serde(rename = "start")]
@@ -95,8 +342,8 @@ pub enum StartStop {
 
 #[derive(Debug, PartialEq, Serialize, Deserialize, Default)]
 pub struct  NotationTypeMeta {
-    #[serde(default = "StartStop::default")]
-    pub r#type: StartStop,
+    #[serde(default = "TiedType::default")]
+    pub r#type: TiedType,
 
     #[serde(rename = "bezier-offset", default = "Option::default")]
     pub bezier_offset: Option<f32>,
@@ -120,8 +367,6 @@ pub struct  NotationTypeMeta {
     pub color: Option<String>,
 }
 
-// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/accidental-mark/
-
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub enum NotationType {
     #[serde(rename = "tied")]
@@ -129,21 +374,15 @@ pub enum NotationType {
 
     #[serde(rename = "slur")]
     Slur {
-        #[serde(default = "StartStop::default")]
-        r#type: StartStop,
+        #[serde(default = "StartStopContinue::default")]
+        r#type: StartStopContinue,
 
         #[serde(default = "u8::default")]
         number: u8,
     },
 
     #[serde(rename = "tuplet")]
-    Tuplet {
-        #[serde(default = "StartStop::default")]
-        r#type: StartStop,
-
-        #[serde(default = "u8::default")]
-        number: u8,
-    },
+    Tuplet(Tuplet),
 
     #[serde(rename = "glissando")]
     Glissando {
@@ -164,70 +403,40 @@ pub enum NotationType {
     },
 
     #[serde(rename = "ornaments")]
-    Ornaments {
-        #[serde(default = "StartStop::default")]
-        r#type: StartStop,
-
-        #[serde(default = "u8::default")]
-        number: u8,
-    },
+    Ornaments(Ornaments),
 
     #[serde(rename = "technical")]
-    Technical {
-        #[serde(default = "StartStop::default")]
-        r#type: StartStop,
-
-        #[serde(default = "u8::default")]
-        number: u8,
-    },
+    Technical(Technical),
 
     #[serde(rename = "articulations")]
     Articulations(Articulations),
 
     #[serde(rename = "dynamics")]
-    Dynamics {
-        #[serde(default = "StartStop::default")]
-        r#type: StartStop,
-
-        #[serde(default = "u8::default")]
-        number: u8,
-    },
+    Dynamics(PrintableValue<Dynamics>),
 
     #[serde(rename = "fermata")]
-    Fermata {
-        #[serde(default = "StartStop::default")]
-        r#type: StartStop,
-
-        #[serde(default = "u8::default")]
-        number: u8,
-    },
+    Fermata(Fermata),
 
     #[serde(rename = "arpeggiate")]
     Arpeggiate {
-        #[serde(default = "StartStop::default")]
-        r#type: StartStop,
-
         #[serde(default = "u8::default")]
         number: u8,
+
+        #[serde(default = "Option::default")]
+        direction: Option<DirectionUD>,
     },
 
     #[serde(rename = "non-arpeggiate")]
     NonArpeggiate {
-        #[serde(default = "StartStop::default")]
-        r#type: StartStop,
+        r#type: TopBottom,
 
         #[serde(default = "u8::default")]
         number: u8,
     },
 
+    // https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/accidental-mark/
     #[serde(rename = "accidental-mark")]
-    AccidentalMark {
-        #[serde(default = "StartStop::default")]
-        r#type: StartStop,
-
-        #[serde(default = "u8::default")]
-        number: u8,
-    },
+    AccidentalMark(PrintableValue<Accidental>),
 
     #[serde(rename = "other-notation")]
     OtherNotation {
@@ -239,16 +448,153 @@ pub enum NotationType {
     },
 }
 
+impl WriteXml for Dot {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.empty(name, Attrs::new());
+    }
+}
+
+impl WriteXml for Instrument {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.empty(name, Attrs::new().add("id", &self.id));
+    }
+}
+
+impl WriteXml for Note {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .opt("default-x", &self.default_x)
+            .opt("default-y", &self.default_y)
+            .opt("color", &self.color)
+            .opt("attack", &self.attack);
+
+        w.start(name, attrs);
+        w.opt_element("grace", &self.grace);
+        if self.cue {
+            w.empty("cue", Attrs::new());
+        }
+        if self.chord {
+            w.empty("chord", Attrs::new());
+        }
+        w.opt_element("pitch", &self.pitch);
+        w.opt_element("unpitched", &self.unpitched);
+        if self.rest {
+            w.empty("rest", Attrs::new());
+        }
+        if self.grace.is_none() {
+            w.text_element("duration", &self.duration);
+        }
+        w.elements("tie", &self.ties);
+        w.elements("instrument", &self.instruments);
+        if self.voice != 0 {
+            w.text_element("voice", &self.voice);
+        }
+        w.opt_text_element("type", &self.notetype);
+        w.elements("dot", &self.dot);
+        w.opt_element("accidental", &self.accidental);
+        w.opt_element("time-modification", &self.time_modification);
+        w.opt_element("stem", &self.stem);
+        w.opt_element("notehead", &self.notehead);
+        if self.staff != 0 {
+            w.text_element("staff", &self.staff);
+        }
+        w.elements("beam", &self.beams);
+        w.opt_element("notations", &self.notations);
+        w.elements("lyric", &self.lyrics_above);
+        w.elements("lyric", &self.lyrics_below);
+        w.end(name);
+    }
+}
+
+impl WriteXml for Notations {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.elements("", &self.notations);
+        w.end(name);
+    }
+}
+
+impl NotationTypeMeta {
+    fn attrs(&self) -> Attrs {
+        Attrs::new()
+            .add("type", &self.r#type)
+            .opt("bezier-offset", &self.bezier_offset)
+            .opt("bezier-offset2", &self.bezier_offset2)
+            .opt("bezier-x", &self.bezier_x)
+            .opt("bezier-x2", &self.bezier_x2)
+            .opt("bezier-y", &self.bezier_y)
+            .opt("bezier-y2", &self.bezier_y2)
+            .opt("color", &self.color)
+    }
+}
+
+impl WriteXml for NotationType {
+    fn write_xml(&self, w: &mut XmlWriter, _name: &str) {
+        match self {
+            NotationType::Tied(meta) => w.empty("tied", meta.attrs()),
+            NotationType::Slur { r#type, number } => w.empty(
+                "slur",
+                Attrs::new()
+                    .add("type", r#type)
+                    .add_if(*number != 0, "number", number),
+            ),
+            NotationType::Tuplet(tuplet) => w.element("tuplet", tuplet),
+            NotationType::Glissando { r#type, number } => w.empty(
+                "glissando",
+                Attrs::new()
+                    .add("type", r#type)
+                    .add_if(*number != 0, "number", number),
+            ),
+            NotationType::Slide { r#type, number } => w.empty(
+                "slide",
+                Attrs::new()
+                    .add("type", r#type)
+                    .add_if(*number != 0, "number", number),
+            ),
+            NotationType::Ornaments(ornaments) => w.element("ornaments", ornaments),
+            NotationType::Technical(technical) => w.element("technical", technical),
+            NotationType::Articulations(articulations) => w.element("articulations", articulations),
+            NotationType::Dynamics(dynamics) => write_dynamics(w, dynamics),
+            NotationType::Fermata(fermata) => w.element("fermata", fermata),
+            NotationType::Arpeggiate { number, direction } => w.empty(
+                "arpeggiate",
+                Attrs::new()
+                    .add_if(*number != 0, "number", number)
+                    .opt("direction", direction),
+            ),
+            NotationType::NonArpeggiate { r#type, number } => w.empty(
+                "non-arpeggiate",
+                Attrs::new()
+                    .add("type", r#type)
+                    .add_if(*number != 0, "number", number),
+            ),
+            NotationType::AccidentalMark(mark) => w.element("accidental-mark", mark),
+            NotationType::OtherNotation { r#type, number } => w.empty(
+                "other-notation",
+                Attrs::new()
+                    .add("type", r#type)
+                    .add_if(*number != 0, "number", number),
+            ),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests_note {
     use std::default;
 
     use crate::musicxml::{
+        accidental::Accidental,
         articulations::{ArticulationMeta, ArticulationType, Articulations},
-        core::{DirectionUD, DurationType, Placement},
+        core::{DirectionUD, DurationType, Placement, TopBottom},
         harmony::Step,
-        note::{NotationType, NotationTypeMeta, Note, StartStop},
+        note::{NotationType, NotationTypeMeta, Notations, Note},
+        notehead::NoteheadValue,
+        start_stop_continue::StartStopContinue,
         stem::Stem,
+        tied_type::TiedType,
+        writer::{WriteXml, XmlWriter},
+        yes_no::YesNo,
     };
     use roxmltree::Document;
     use serde_xml_rs::from_str;
@@ -272,7 +618,7 @@ mod tests_note {
         assert_eq!(Step::C, pitch.step);
         assert_eq!(4, pitch.octave);
         assert_eq!(4, note.duration);
-        assert_eq!(DurationType::Whole, note.notetype);
+        assert_eq!(Some(DurationType::Whole), note.notetype);
     }
 
     #[test]
@@ -296,7 +642,7 @@ mod tests_note {
         assert_eq!(7, note.duration);
         assert_eq!(2, note.dot.len());
         assert_eq!(1, note.voice);
-        assert_eq!(DurationType::Half, note.notetype);
+        assert_eq!(Some(DurationType::Half), note.notetype);
         assert_eq!(
             Some(Stem {
                 content: DirectionUD::Up,
@@ -341,7 +687,7 @@ mod tests_note {
             },
             note.stem.unwrap()
         );
-        assert_eq!(DurationType::Quarter, note.notetype);
+        assert_eq!(Some(DurationType::Quarter), note.notetype);
 
         let pitch = note.pitch.unwrap();
         assert_eq!(Step::G, pitch.step);
@@ -350,17 +696,58 @@ mod tests_note {
         let notations = note.notations.unwrap();
         assert_eq!(
             NotationType::Slur {
-                r#type: StartStop::Start,
+                r#type: StartStopContinue::Start,
                 number: 1
             },
             notations.notations[0]
         );
         assert_eq!(
-            NotationType::Tied(NotationTypeMeta::default()),
+            NotationType::Tied(NotationTypeMeta {
+                r#type: TiedType::Stop,
+                ..NotationTypeMeta::default()
+            }),
             notations.notations[1]
         );
     }
 
+    #[test]
+    fn tied_types() {
+        let xml = r#"
+        <note>
+            <pitch><step>E</step><octave>2</octave></pitch>
+            <duration>4</duration>
+            <voice>1</voice>
+            <notations>
+                <tied type="continue"/>
+                <tied type="let-ring"/>
+            </notations>
+        </note>"#;
+
+        let note: Note = from_str(xml).unwrap();
+        let types: Vec<TiedType> = note
+            .notations
+            .as_ref()
+            .unwrap()
+            .notations
+            .iter()
+            .filter_map(|n| match n {
+                NotationType::Tied(meta) => Some(meta.r#type.clone()),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(types, vec![TiedType::Continue, TiedType::LetRing]);
+        assert!(!note.tie_start());
+        assert!(!note.tie_stop());
+
+        let mut w = XmlWriter::new();
+        note.notations.unwrap().write_xml(&mut w, "notations");
+        assert_eq!(
+            w.finish(),
+            "<notations>\n  <tied type=\"continue\"/>\n  <tied type=\"let-ring\"/>\n</notations>\n"
+        );
+    }
+
     #[test]
     fn accidental_mark() {
         let xml = r#"
@@ -382,6 +769,97 @@ mod tests_note {
         assert_eq!(note.default_x.unwrap(), 84.0);
     }
 
+    #[test]
+    fn lyrics() {
+        let xml = r#"
+            <note>
+                <pitch>
+                    <step>C</step>
+                    <octave>4</octave>
+                </pitch>
+                <duration>4</duration>
+                <type>whole</type>
+                <lyric number="1"><text>Ah</text></lyric>
+                <lyric number="2" placement="above"><text>Oh</text></lyric>
+            </note>"#;
+        let note: Note = from_str(xml).unwrap();
+
+        assert_eq!(note.lyrics_below.len(), 1);
+        assert_eq!(note.lyrics_below[0].text, "Ah");
+        assert_eq!(note.lyrics_above.len(), 1);
+        assert_eq!(note.lyrics_above[0].text, "Oh");
+    }
+
+    #[test]
+    fn accidental_and_notehead() {
+        let xml = r#"
+            <note>
+                <pitch>
+                    <step>F</step>
+                    <alter>1</alter>
+                    <octave>4</octave>
+                </pitch>
+                <duration>1</duration>
+                <type>quarter</type>
+                <accidental editorial="yes" cautionary="yes">sharp</accidental>
+                <notehead filled="no">inverted triangle</notehead>
+            </note>"#;
+        let note: Note = from_str(xml).unwrap();
+
+        let accidental = note.accidental.as_ref().unwrap();
+        assert_eq!(accidental.content, Accidental::Sharp);
+        assert_eq!(accidental.cautionary, Some(YesNo::Yes));
+        assert_eq!(
+            note.notehead.as_ref().unwrap().content,
+            NoteheadValue::InvertedTriangle
+        );
+
+        let mut w = XmlWriter::new();
+        w.element("note", &note);
+        let written = w.finish();
+        assert!(written.contains(
+            r#"<type>quarter</type>
+  <accidental cautionary="yes" editorial="yes">sharp</accidental>
+  <notehead filled="no">inverted triangle</notehead>"#
+        ));
+    }
+
+    #[test]
+    fn marks_and_arpeggios() {
+        let xml = r#"
+            <notations>
+                <dynamics placement="below"><sfp/></dynamics>
+                <fermata type="inverted">square</fermata>
+                <arpeggiate direction="up"/>
+                <non-arpeggiate type="top"/>
+                <accidental-mark placement="above">sharp</accidental-mark>
+            </notations>"#;
+        let item: Notations = from_str(xml).unwrap();
+
+        assert!(matches!(
+            &item.notations[3],
+            NotationType::NonArpeggiate {
+                r#type: TopBottom::Top,
+                ..
+            }
+        ));
+        let mut w = XmlWriter::new();
+        w.element("notations", &item);
+        assert_eq!(
+            w.finish(),
+            r#"<notations>
+  <dynamics placement="below">
+    <sfp/>
+  </dynamics>
+  <fermata type="inverted">square</fermata>
+  <arpeggiate direction="up"/>
+  <non-arpeggiate type="top"/>
+  <accidental-mark placement="above">sharp</accidental-mark>
+</notations>
+"#
+        );
+    }
+
     // https://www.w3.org/2021/06/musicxml40/musicxml-reference/examples/accent-element/
     #[test]
     fn test_accent() {
@@ -407,7 +885,7 @@ mod tests_note {
         assert_eq!(item.default_x.unwrap(), 36.0);
         assert_eq!(item.duration, 4);
         assert_eq!(item.voice, 1);
-        assert_eq!(item.notetype, DurationType::Half);
+        assert_eq!(item.notetype, Some(DurationType::Half));
 
         let stem = &item.stem.unwrap();
         assert_eq!(
@@ -457,7 +935,7 @@ mod tests_note {
         assert_eq!(item.default_x.unwrap(), 84.0);
         assert_eq!(item.duration, 2);
         assert_eq!(item.voice, 1);
-        assert_eq!(item.notetype, DurationType::Quarter);
+        assert_eq!(item.notetype, Some(DurationType::Quarter));
 
         let stem = &item.stem.unwrap();
         assert_eq!(
@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+
+use super::printable_value::{FontStyle, FontWeight};
+use super::writer::{Attrs, WriteXml, XmlWriter};
+use super::yes_no::YesNo;
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/data-types/notehead-value/
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone)]
+pub enum NoteheadValue {
+    #[serde(rename = "normal")]
+    #[default]
+    Normal,
+
+    #[serde(rename = "slash")]
+    Slash,
+
+    #[serde(rename = "triangle")]
+    Triangle,
+
+    #[serde(rename = "diamond")]
+    Diamond,
+
+    #[serde(rename = "square")]
+    Square,
+
+    #[serde(rename = "cross")]
+    Cross,
+
+    #[serde(rename = "x")]
+    X,
+
+    #[serde(rename = "circle-x")]
+    CircleX,
+
+    #[serde(rename = "inverted triangle")]
+    InvertedTriangle,
+
+    #[serde(rename = "arrow down")]
+    ArrowDown,
+
+    #[serde(rename = "arrow up")]
+    ArrowUp,
+
+    #[serde(rename = "circled")]
+    Circled,
+
+    #[serde(rename = "slashed")]
+    Slashed,
+
+    #[serde(rename = "back slashed")]
+    BackSlashed,
+
+    #[serde(rename = "cluster")]
+    Cluster,
+
+    #[serde(rename = "circle dot")]
+    CircleDot,
+
+    #[serde(rename = "left triangle")]
+    LeftTriangle,
+
+    #[serde(rename = "rectangle")]
+    Rectangle,
+
+    #[serde(rename = "none")]
+    None,
+
+    #[serde(rename = "do")]
+    Do,
+
+    #[serde(rename = "re")]
+    Re,
+
+    #[serde(rename = "mi")]
+    Mi,
+
+    #[serde(rename = "fa")]
+    Fa,
+
+    #[serde(rename = "fa up")]
+    FaUp,
+
+    #[serde(rename = "so")]
+    So,
+
+    #[serde(rename = "la")]
+    La,
+
+    #[serde(rename = "ti")]
+    Ti,
+
+    #[serde(rename = "other")]
+    Other,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/notehead/
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone)]
+pub struct Notehead {
+    #[serde(rename = "$value", default = "NoteheadValue::default")]
+    pub content: NoteheadValue,
+
+    #[serde(default = "Option::default")]
+    pub filled: Option<YesNo>,
+
+    #[serde(default = "Option::default")]
+    pub parentheses: Option<YesNo>,
+
+    #[serde(default = "Option::default")]
+    pub smufl: Option<String>,
+
+    #[serde(default = "Option::default")]
+    pub color: Option<String>,
+
+    #[serde(rename = "font-family", default = "Option::default")]
+    pub font_family: Option<String>,
+
+    #[serde(rename = "font-size", default = "Option::default")]
+    pub font_size: Option<f32>,
+
+    #[serde(rename = "font-style", default = "Option::default")]
+    pub font_style: Option<FontStyle>,
+
+    #[serde(rename = "font-weight", default = "Option::default")]
+    pub font_weight: Option<FontWeight>,
+}
+
+impl WriteXml for Notehead {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .opt("filled", &self.filled)
+            .opt("parentheses", &self.parentheses)
+            .opt("smufl", &self.smufl)
+            .opt("color", &self.color)
+            .opt("font-family", &self.font_family)
+            .opt("font-size", &self.font_size)
+            .opt("font-style", &self.font_style)
+            .opt("font-weight", &self.font_weight);
+        w.text_element_with(name, attrs, &self.content);
+    }
+}
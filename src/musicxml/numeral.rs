@@ -1,4 +1,9 @@
-use super::{harmony::Step, numeral_key::NumeralKey, printable_value::PrintableValue};
+use super::{
+    harmony::Step,
+    numeral_key::NumeralKey,
+    printable_value::PrintableValue,
+    writer::{Attrs, WriteXml, XmlWriter},
+};
 use serde::{Deserialize, Serialize};
 
 // https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/numeral/
@@ -15,6 +20,16 @@ pub struct Numeral {
     pub key: Option<NumeralKey>,
 }
 
+impl WriteXml for Numeral {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.element("numeral-root", &self.root);
+        w.opt_element("numeral-alter", &self.alter);
+        w.opt_element("numeral-key", &self.key);
+        w.end(name);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Numeral;
@@ -1,3 +1,4 @@
+use super::writer::{Attrs, WriteXml, XmlWriter};
 use super::yes_no::YesNo;
 use serde::{Deserialize, Serialize};
 
@@ -33,3 +34,12 @@ pub struct NumeralKey {
     #[serde(rename = "print-object", default = "Option::default")]
     pub print_object: Option<YesNo>,
 }
+
+impl WriteXml for NumeralKey {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new().opt("print-object", &self.print_object));
+        w.text_element("numeral-fifths", &self.fifths);
+        w.text_element("numeral-mode", &self.mode);
+        w.end(name);
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+use super::writer::{Attrs, WriteXml, XmlWriter};
+use super::yes_no::YesNo;
+use crate::prelude::*;
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/offset/
+/// Distance in divisions between where a direction or sound is notated and where it
+/// takes effect. Only affects playback when `sound` is yes.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone)]
+pub struct Offset {
+    #[serde(rename = "$value", default = "f32::default")]
+    pub content: f32,
+
+    #[serde(default = "Option::default")]
+    pub sound: Option<YesNo>,
+}
+
+impl Offset {
+    pub fn affects_sound(&self) -> bool {
+        self.sound == Some(YesNo::Yes)
+    }
+}
+
+impl WriteXml for Offset {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.text_element_with(name, Attrs::new().opt("sound", &self.sound), &self.content);
+    }
+}
@@ -0,0 +1,283 @@
+use crate::musicxml::core::Placement;
+use crate::musicxml::writer::{Attrs, WriteXml, XmlWriter};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use strum_macros::EnumString;
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/ornaments/
+/// Attributes shared by the ornament elements. `content` holds the text of
+/// `<tremolo>`, `<accidental-mark>` and `<other-ornament>`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Default)]
+pub struct OrnamentMeta {
+    #[serde(rename = "$value", default = "Option::default")]
+    pub content: Option<String>,
+
+    /// start, stop or continue for `<wavy-line>`; single, start, stop or
+    /// unmeasured for `<tremolo>`.
+    #[serde(default = "Option::default")]
+    pub r#type: Option<String>,
+
+    #[serde(default = "Option::default")]
+    pub number: Option<u8>,
+
+    #[serde(default = "Option::default")]
+    pub placement: Option<Placement>,
+
+    // Trill sound
+    #[serde(default = "Option::default", rename = "start-note")]
+    pub start_note: Option<String>,
+
+    #[serde(default = "Option::default", rename = "trill-step")]
+    pub trill_step: Option<String>,
+
+    #[serde(default = "Option::default", rename = "two-note-turn")]
+    pub two_note_turn: Option<String>,
+
+    #[serde(default = "Option::default")]
+    pub accelerate: Option<String>,
+
+    #[serde(default = "Option::default")]
+    pub beats: Option<f32>,
+
+    #[serde(default = "Option::default", rename = "second-beat")]
+    pub second_beat: Option<f32>,
+
+    #[serde(default = "Option::default", rename = "last-beat")]
+    pub last_beat: Option<f32>,
+
+    // Turns and mordents
+    #[serde(default = "Option::default")]
+    pub slash: Option<String>,
+
+    #[serde(default = "Option::default")]
+    pub long: Option<String>,
+
+    #[serde(default = "Option::default")]
+    pub approach: Option<String>,
+
+    #[serde(default = "Option::default")]
+    pub departure: Option<String>,
+
+    // Print style
+    #[serde(default = "Option::default")]
+    pub color: Option<String>,
+
+    #[serde(default = "Option::default", rename = "default-x")]
+    pub default_x: Option<f32>,
+
+    #[serde(default = "Option::default", rename = "default-y")]
+    pub default_y: Option<f32>,
+
+    #[serde(default = "Option::default", rename = "relative-x")]
+    pub relative_x: Option<f32>,
+
+    #[serde(default = "Option::default", rename = "relative-y")]
+    pub relative_y: Option<f32>,
+
+    #[serde(default = "Option::default")]
+    pub smufl: Option<String>,
+}
+
+#[derive(Debug, EnumString, PartialEq, Clone, Serialize, Deserialize)]
+pub enum OrnamentType {
+    #[strum(serialize = "trill-mark")]
+    #[serde(rename = "trill-mark")]
+    TrillMark(OrnamentMeta),
+
+    #[strum(serialize = "turn")]
+    #[serde(rename = "turn")]
+    Turn(OrnamentMeta),
+
+    #[strum(serialize = "delayed-turn")]
+    #[serde(rename = "delayed-turn")]
+    DelayedTurn(OrnamentMeta),
+
+    #[strum(serialize = "inverted-turn")]
+    #[serde(rename = "inverted-turn")]
+    InvertedTurn(OrnamentMeta),
+
+    #[strum(serialize = "delayed-inverted-turn")]
+    #[serde(rename = "delayed-inverted-turn")]
+    DelayedInvertedTurn(OrnamentMeta),
+
+    #[strum(serialize = "vertical-turn")]
+    #[serde(rename = "vertical-turn")]
+    VerticalTurn(OrnamentMeta),
+
+    #[strum(serialize = "inverted-vertical-turn")]
+    #[serde(rename = "inverted-vertical-turn")]
+    InvertedVerticalTurn(OrnamentMeta),
+
+    #[strum(serialize = "shake")]
+    #[serde(rename = "shake")]
+    Shake(OrnamentMeta),
+
+    #[strum(serialize = "wavy-line")]
+    #[serde(rename = "wavy-line")]
+    WavyLine(OrnamentMeta),
+
+    #[strum(serialize = "mordent")]
+    #[serde(rename = "mordent")]
+    Mordent(OrnamentMeta),
+
+    #[strum(serialize = "inverted-mordent")]
+    #[serde(rename = "inverted-mordent")]
+    InvertedMordent(OrnamentMeta),
+
+    #[strum(serialize = "schleifer")]
+    #[serde(rename = "schleifer")]
+    Schleifer(OrnamentMeta),
+
+    #[strum(serialize = "tremolo")]
+    #[serde(rename = "tremolo")]
+    Tremolo(OrnamentMeta),
+
+    #[strum(serialize = "haydn")]
+    #[serde(rename = "haydn")]
+    Haydn(OrnamentMeta),
+
+    #[strum(serialize = "other-ornament")]
+    #[serde(rename = "other-ornament")]
+    OtherOrnament(OrnamentMeta),
+
+    #[strum(serialize = "accidental-mark")]
+    #[serde(rename = "accidental-mark")]
+    AccidentalMark(OrnamentMeta),
+}
+
+impl OrnamentType {
+    pub fn meta(&self) -> &OrnamentMeta {
+        match self {
+            OrnamentType::TrillMark(m)
+            | OrnamentType::Turn(m)
+            | OrnamentType::DelayedTurn(m)
+            | OrnamentType::InvertedTurn(m)
+            | OrnamentType::DelayedInvertedTurn(m)
+            | OrnamentType::VerticalTurn(m)
+            | OrnamentType::InvertedVerticalTurn(m)
+            | OrnamentType::Shake(m)
+            | OrnamentType::WavyLine(m)
+            | OrnamentType::Mordent(m)
+            | OrnamentType::InvertedMordent(m)
+            | OrnamentType::Schleifer(m)
+            | OrnamentType::Tremolo(m)
+            | OrnamentType::Haydn(m)
+            | OrnamentType::OtherOrnament(m)
+            | OrnamentType::AccidentalMark(m) => m,
+        }
+    }
+
+    /// Number of tremolo strokes, 0 to 8. `None` for other ornaments.
+    pub fn tremolo_marks(&self) -> Option<u8> {
+        match self {
+            OrnamentType::Tremolo(m) => Some(
+                m.content
+                    .as_deref()
+                    .and_then(|c| c.trim().parse().ok())
+                    .unwrap_or(0),
+            ),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Default)]
+pub struct Ornaments {
+    #[serde(rename = "$value", default = "Vec::default")]
+    pub ornaments: Vec<OrnamentType>,
+}
+
+impl WriteXml for OrnamentMeta {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .opt("type", &self.r#type)
+            .opt("number", &self.number)
+            .opt("placement", &self.placement)
+            .opt("start-note", &self.start_note)
+            .opt("trill-step", &self.trill_step)
+            .opt("two-note-turn", &self.two_note_turn)
+            .opt("accelerate", &self.accelerate)
+            .opt("beats", &self.beats)
+            .opt("second-beat", &self.second_beat)
+            .opt("last-beat", &self.last_beat)
+            .opt("slash", &self.slash)
+            .opt("long", &self.long)
+            .opt("approach", &self.approach)
+            .opt("departure", &self.departure)
+            .opt("color", &self.color)
+            .opt("default-x", &self.default_x)
+            .opt("default-y", &self.default_y)
+            .opt("relative-x", &self.relative_x)
+            .opt("relative-y", &self.relative_y)
+            .opt("smufl", &self.smufl);
+        match &self.content {
+            Some(content) => w.text_element_with(name, attrs, content),
+            None => w.empty(name, attrs),
+        }
+    }
+}
+
+impl WriteXml for OrnamentType {
+    fn write_xml(&self, w: &mut XmlWriter, _name: &str) {
+        match self {
+            OrnamentType::TrillMark(m) => w.element("trill-mark", m),
+            OrnamentType::Turn(m) => w.element("turn", m),
+            OrnamentType::DelayedTurn(m) => w.element("delayed-turn", m),
+            OrnamentType::InvertedTurn(m) => w.element("inverted-turn", m),
+            OrnamentType::DelayedInvertedTurn(m) => w.element("delayed-inverted-turn", m),
+            OrnamentType::VerticalTurn(m) => w.element("vertical-turn", m),
+            OrnamentType::InvertedVerticalTurn(m) => w.element("inverted-vertical-turn", m),
+            OrnamentType::Shake(m) => w.element("shake", m),
+            OrnamentType::WavyLine(m) => w.element("wavy-line", m),
+            OrnamentType::Mordent(m) => w.element("mordent", m),
+            OrnamentType::InvertedMordent(m) => w.element("inverted-mordent", m),
+            OrnamentType::Schleifer(m) => w.element("schleifer", m),
+            OrnamentType::Tremolo(m) => w.element("tremolo", m),
+            OrnamentType::Haydn(m) => w.element("haydn", m),
+            OrnamentType::OtherOrnament(m) => w.element("other-ornament", m),
+            OrnamentType::AccidentalMark(m) => w.element("accidental-mark", m),
+        }
+    }
+}
+
+impl WriteXml for Ornaments {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.elements("", &self.ornaments);
+        w.end(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OrnamentMeta, OrnamentType, Ornaments};
+    use crate::musicxml::core::Placement;
+    use serde_xml_rs::from_str;
+
+    #[test]
+    fn ornaments() {
+        let xml = r#"<ornaments>
+            <wavy-line number="1" placement="below" type="start"/>
+            <inverted-mordent long="yes" approach="below"/>
+            <tremolo type="single">3</tremolo>
+            <accidental-mark placement="above">sharp</accidental-mark>
+        </ornaments>"#;
+        let item: Ornaments = from_str(xml).unwrap();
+
+        assert_eq!(
+            item.ornaments[0],
+            OrnamentType::WavyLine(OrnamentMeta {
+                r#type: Some("start".to_string()),
+                number: Some(1),
+                placement: Some(Placement::Below),
+                ..OrnamentMeta::default()
+            })
+        );
+        assert_eq!(item.ornaments[1].meta().long.as_deref(), Some("yes"));
+        assert_eq!(item.ornaments[2].tremolo_marks(), Some(3));
+        assert_eq!(item.ornaments[2].meta().r#type.as_deref(), Some("single"));
+        assert_eq!(item.ornaments[3].meta().content.as_deref(), Some("sharp"));
+        assert_eq!(item.ornaments[3].tremolo_marks(), None);
+    }
+}
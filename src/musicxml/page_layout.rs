@@ -1,7 +1,14 @@
 use super::{
-    attributes::Attributes, barline::Barline, core::Duration, direction::Direction,
-    harmony::Harmony, left_right_middle::LeftRightMiddle, level::Level, note::Note,
+    attributes::Attributes,
+    barline::Barline,
+    core::Duration,
+    direction::Direction,
+    harmony::Harmony,
+    left_right_middle::LeftRightMiddle,
+    level::Level,
+    note::Note,
     page_margins::PageMargins,
+    writer::{Attrs, WriteXml, XmlWriter},
 };
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -18,6 +25,16 @@ pub struct PageLayout {
     pub page_margins: Vec<PageMargins>,
 }
 
+impl WriteXml for PageLayout {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.opt_text_element("page-height", &self.page_height);
+        w.opt_text_element("page-width", &self.page_width);
+        w.elements("page-margins", &self.page_margins);
+        w.end(name);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::PageLayout;
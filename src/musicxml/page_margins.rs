@@ -1,6 +1,13 @@
 use super::{
-    attributes::Attributes, barline::Barline, core::Duration, direction::Direction,
-    harmony::Harmony, left_right_middle::LeftRightMiddle, level::Level, note::Note,
+    attributes::Attributes,
+    barline::Barline,
+    core::Duration,
+    direction::Direction,
+    harmony::Harmony,
+    left_right_middle::LeftRightMiddle,
+    level::Level,
+    note::Note,
+    writer::{Attrs, WriteXml, XmlWriter},
 };
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -35,3 +42,14 @@ pub struct PageMargins {
     #[serde(default = "Option::default")]
     pub r#type: Option<MarginType>,
 }
+
+impl WriteXml for PageMargins {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new().opt("type", &self.r#type));
+        w.text_element("left-margin", &self.left_margin);
+        w.text_element("right-margin", &self.right_margin);
+        w.text_element("top-margin", &self.top_margin);
+        w.text_element("bottom-margin", &self.bottom_margin);
+        w.end(name);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::musicxml::measure::Measure;
+use crate::musicxml::writer::{Attrs, WriteXml, XmlWriter};
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -10,3 +11,11 @@ pub struct Part {
     #[serde(rename = "measure", default = "Vec::default")]
     pub measures: Vec<Measure>,
 }
+
+impl WriteXml for Part {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new().add("id", &self.id));
+        w.elements("measure", &self.measures);
+        w.end(name);
+    }
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::printable_value::PrintableValue;
+use super::writer::{Attrs, WriteXml, XmlWriter};
 
 // https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/part-name-display/
 #[derive(Debug, Serialize, Deserialize)]
@@ -11,3 +12,12 @@ pub struct PartDisplay {
     #[serde(rename = "accidental-text", default = "Option::default")]
     accidental_text: Option<PrintableValue<String>>,
 }
+
+impl WriteXml for PartDisplay {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.opt_element("display-text", &self.display_text);
+        w.opt_element("accidental-text", &self.accidental_text);
+        w.end(name);
+    }
+}
@@ -1,8 +1,14 @@
 use std::fmt;
 
 use super::{
-    accidental::Accidental, group_barline::GroupBarline, group_symbol::GroupSymbol, level::Level,
-    note::StartStop, printable_value::PrintableValue, yes_no::YesNo,
+    accidental::Accidental,
+    group_barline::GroupBarline,
+    group_symbol::GroupSymbol,
+    level::Level,
+    note::StartStop,
+    printable_value::PrintableValue,
+    writer::{Attrs, WriteXml, XmlWriter},
+    yes_no::YesNo,
 };
 use serde::{de::Visitor, Deserialize, Serialize};
 
@@ -51,6 +57,37 @@ pub struct PartGroup {
     pub number: Option<String>,
 }
 
+impl WriteXml for GroupDisplay {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new().opt("print-object", &self.print_object));
+        w.text_element("display-text", &self.display_text);
+        w.opt_text_element("accidental-text", &self.accidental_text);
+        w.end(name);
+    }
+}
+
+impl WriteXml for PartGroup {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .add("type", &self.r#type)
+            .opt("number", &self.number);
+
+        w.start(name, attrs);
+        w.opt_text_element("group-name", &self.group_name);
+        w.opt_element("group-name-display", &self.group_name_display);
+        w.opt_text_element("group-abbreviation", &self.group_abbreviation);
+        w.opt_element(
+            "group-abbreviation-display",
+            &self.group_abbreviation_display,
+        );
+        w.opt_text_element("group-symbol", &self.group_symbol);
+        w.opt_text_element("group-barline", &self.group_barline);
+        w.opt_text_element("footnote", &self.footnote);
+        w.opt_element("level", &self.level);
+        w.end(name);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::PartGroup;
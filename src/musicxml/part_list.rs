@@ -1,4 +1,9 @@
-use super::{part_group::PartGroup, printable_value::PrintableValue, score_part::ScorePart};
+use super::{
+    part_group::PartGroup,
+    printable_value::PrintableValue,
+    score_part::ScorePart,
+    writer::{Attrs, WriteXml, XmlWriter},
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -19,6 +24,24 @@ pub struct PartList {
     pub parts: Vec<PartListContent>,
 }
 
+impl WriteXml for PartListContent {
+    fn write_xml(&self, w: &mut XmlWriter, _name: &str) {
+        match self {
+            PartListContent::PartGroup(group) => w.element("part-group", group),
+            PartListContent::ScorePart(part) => w.element("score-part", part),
+            PartListContent::PartName(name) => w.element("part-name", name),
+        }
+    }
+}
+
+impl WriteXml for PartList {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.elements("", &self.parts);
+        w.end(name);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::musicxml::{part_list::PartListContent, score_part::ScorePartContent};
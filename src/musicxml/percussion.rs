@@ -0,0 +1,120 @@
+use serde::{de::IgnoredAny, Deserialize, Serialize};
+
+use super::writer::{Attrs, WriteXml, XmlWriter};
+use crate::prelude::*;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone)]
+pub struct Stick {
+    #[serde(rename = "stick-type", default = "String::default")]
+    pub stick_type: String,
+
+    #[serde(rename = "stick-material", default = "String::default")]
+    pub stick_material: String,
+}
+
+/// The pictogram of a `<percussion>` direction. The text is the pictogram name,
+/// e.g. "snare drum" for `<membrane>` or "soft" for `<beater>`.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub enum PercussionType {
+    Glass(String),
+    Metal(String),
+    Wood(String),
+    Pitched(String),
+    Membrane(String),
+    Effect(String),
+    Timpani,
+    Beater(String),
+    Stick(Stick),
+    StickLocation(String),
+    OtherPercussion(String),
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/percussion/
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(try_from = "PercussionXml")]
+pub struct Percussion {
+    pub content: PercussionType,
+}
+
+#[derive(Deserialize)]
+enum PercussionChild {
+    #[serde(rename = "glass")]
+    Glass(String),
+    #[serde(rename = "metal")]
+    Metal(String),
+    #[serde(rename = "wood")]
+    Wood(String),
+    #[serde(rename = "pitched")]
+    Pitched(String),
+    #[serde(rename = "membrane")]
+    Membrane(String),
+    #[serde(rename = "effect")]
+    Effect(String),
+    #[serde(rename = "timpani")]
+    Timpani(IgnoredAny),
+    #[serde(rename = "beater")]
+    Beater(String),
+    #[serde(rename = "stick")]
+    Stick(Stick),
+    #[serde(rename = "stick-location")]
+    StickLocation(String),
+    #[serde(rename = "other-percussion")]
+    OtherPercussion(String),
+}
+
+#[derive(Deserialize)]
+struct PercussionXml {
+    #[serde(rename = "$value", default = "Vec::default")]
+    children: Vec<PercussionChild>,
+}
+
+impl TryFrom<PercussionXml> for Percussion {
+    type Error = String;
+
+    fn try_from(xml: PercussionXml) -> std::result::Result<Percussion, String> {
+        let child = xml
+            .children
+            .into_iter()
+            .next()
+            .ok_or("empty `percussion`")?;
+        let content = match child {
+            PercussionChild::Glass(s) => PercussionType::Glass(s),
+            PercussionChild::Metal(s) => PercussionType::Metal(s),
+            PercussionChild::Wood(s) => PercussionType::Wood(s),
+            PercussionChild::Pitched(s) => PercussionType::Pitched(s),
+            PercussionChild::Membrane(s) => PercussionType::Membrane(s),
+            PercussionChild::Effect(s) => PercussionType::Effect(s),
+            PercussionChild::Timpani(_) => PercussionType::Timpani,
+            PercussionChild::Beater(s) => PercussionType::Beater(s),
+            PercussionChild::Stick(s) => PercussionType::Stick(s),
+            PercussionChild::StickLocation(s) => PercussionType::StickLocation(s),
+            PercussionChild::OtherPercussion(s) => PercussionType::OtherPercussion(s),
+        };
+        Ok(Percussion { content })
+    }
+}
+
+impl WriteXml for Percussion {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        match &self.content {
+            PercussionType::Glass(s) => w.text_element("glass", s),
+            PercussionType::Metal(s) => w.text_element("metal", s),
+            PercussionType::Wood(s) => w.text_element("wood", s),
+            PercussionType::Pitched(s) => w.text_element("pitched", s),
+            PercussionType::Membrane(s) => w.text_element("membrane", s),
+            PercussionType::Effect(s) => w.text_element("effect", s),
+            PercussionType::Timpani => w.empty("timpani", Attrs::new()),
+            PercussionType::Beater(s) => w.text_element("beater", s),
+            PercussionType::Stick(stick) => {
+                w.start("stick", Attrs::new());
+                w.text_element("stick-type", &stick.stick_type);
+                w.text_element("stick-material", &stick.stick_material);
+                w.end("stick");
+            }
+            PercussionType::StickLocation(s) => w.text_element("stick-location", s),
+            PercussionType::OtherPercussion(s) => w.text_element("other-percussion", s),
+        }
+        w.end(name);
+    }
+}
@@ -0,0 +1,357 @@
+use super::{
+    barline::{EndingType, Repeat},
+    core::RepeatDirection,
+    direction::DirectionType,
+    measure::MeasureContent,
+    part::Part,
+    score_partwise::ScorePartwise,
+    sound::Sound,
+    yes_no::YesNo,
+};
+use std::collections::HashSet;
+
+/// Repeat, ending and jump marks of one measure.
+#[derive(Default)]
+struct Marks<'a> {
+    forward: bool,
+    backward: Option<&'a Repeat>,
+    ending: Option<(Vec<u32>, EndingType)>,
+    ending_stop: bool,
+    sounds: Vec<&'a Sound>,
+    segno: bool,
+    coda: bool,
+}
+
+impl<'a> Marks<'a> {
+    fn of_part(part: &'a Part) -> Vec<Marks<'a>> {
+        part.measures
+            .iter()
+            .map(|measure| {
+                let mut marks = Marks::default();
+                for content in &measure.content {
+                    match content {
+                        MeasureContent::Barline(barline) => {
+                            if let Some(repeat) = &barline.repeat {
+                                match repeat.direction {
+                                    RepeatDirection::Forward => marks.forward = true,
+                                    RepeatDirection::Backward => marks.backward = Some(repeat),
+                                }
+                            }
+                            if let Some(ending) = &barline.ending {
+                                if ending.r#type != EndingType::Start {
+                                    marks.ending_stop = true;
+                                }
+                                if ending.r#type == EndingType::Start || marks.ending.is_none() {
+                                    marks.ending = Some((ending.passes(), ending.r#type));
+                                }
+                            }
+                        }
+                        MeasureContent::Sound(sound) => marks.sounds.push(sound),
+                        MeasureContent::Direction(direction) => {
+                            marks.sounds.extend(&direction.sound);
+                            for directiontype in &direction.directiontypes {
+                                match directiontype {
+                                    DirectionType::Segno => marks.segno = true,
+                                    DirectionType::Coda => marks.coda = true,
+                                    _ => {}
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                marks
+            })
+            .collect()
+    }
+
+    fn sound<T>(&self, f: impl Fn(&'a Sound) -> Option<T>) -> Option<T> {
+        self.sounds.iter().find_map(|s| f(s))
+    }
+
+    fn takes_repeat(&self, jumped: bool) -> Option<&'a Repeat> {
+        self.backward
+            .filter(|r| !jumped || r.after_jump == Some(YesNo::Yes))
+    }
+}
+
+/// The measures under one ending bracket.
+struct Volta {
+    passes: Vec<u32>,
+    first: usize,
+    last: usize,
+
+    /// Last measure of the run of adjacent endings this one belongs to.
+    group_end: usize,
+
+    /// Highest pass number in that run.
+    group_passes: u32,
+}
+
+/// Pairs ending starts with their stops and returns the endings along with the
+/// ending of each measure. A stop without a start, as in some invalid files, makes
+/// an ending of just its own measure.
+fn voltas(marks: &[Marks]) -> (Vec<Volta>, Vec<Option<usize>>) {
+    let mut voltas: Vec<Volta> = vec![];
+    let mut of_measure = vec![None; marks.len()];
+    let mut open: Option<usize> = None;
+
+    for (idx, m) in marks.iter().enumerate() {
+        if let Some((passes, kind)) = &m.ending {
+            if *kind == EndingType::Start || open.is_none() {
+                voltas.push(Volta {
+                    passes: passes.clone(),
+                    first: idx,
+                    last: idx,
+                    group_end: idx,
+                    group_passes: 1,
+                });
+                open = Some(voltas.len() - 1);
+            }
+        }
+        if let Some(v) = open {
+            voltas[v].last = idx;
+            of_measure[idx] = Some(v);
+            if m.ending_stop {
+                open = None;
+            }
+        }
+    }
+
+    let mut group = 0;
+    for v in 0..voltas.len() {
+        if v > 0 && voltas[v].first != voltas[v - 1].last + 1 {
+            group = v;
+        }
+        let end = voltas[v].last;
+        let most = voltas[group..=v]
+            .iter()
+            .flat_map(|volta| volta.passes.iter().copied())
+            .max()
+            .unwrap_or(1);
+        for volta in &mut voltas[group..=v] {
+            volta.group_end = end;
+            volta.group_passes = most;
+        }
+    }
+
+    (voltas, of_measure)
+}
+
+/// The first measure from `from` on with a segno or coda sound of this name. Falls
+/// back to the first unnamed segno or coda sign when no sound carries the name.
+fn target(
+    marks: &[Marks],
+    from: usize,
+    name: &str,
+    named: impl Fn(&Sound) -> Option<&str>,
+    unnamed: impl Fn(&Marks) -> bool,
+) -> Option<usize> {
+    let found = |f: &dyn Fn(&Marks) -> bool| (from..marks.len()).find(|idx| f(&marks[*idx]));
+    found(&|m| m.sounds.iter().any(|s| named(s) == Some(name))).or_else(|| found(&unnamed))
+}
+
+impl Part {
+    /// Measure indices in playing order.
+    ///
+    /// A backward repeat goes back to the last forward repeat, or to the measure after
+    /// the previous repeated section, until it's played `times` passes. Endings are
+    /// played on the passes they list. Da capo and dal segno jumps from `<sound>` are
+    /// taken once, after which a to coda jumps to its coda and fine ends the piece.
+    /// After a jump, only repeats marked `after-jump` are taken and the endings that
+    /// don't repeat are played.
+    pub fn performance_order(&self) -> Vec<usize> {
+        let marks = Marks::of_part(self);
+        let (voltas, volta_of) = voltas(&marks);
+
+        // Guards against jumps and repeats that never settle in invalid files.
+        let passes = marks
+            .iter()
+            .filter_map(|m| m.backward.and_then(|r| r.times))
+            .chain(voltas.iter().map(|v| v.group_passes))
+            .max()
+            .unwrap_or(2)
+            .max(2) as usize;
+        let jumps = marks.iter().map(|m| m.sounds.len()).sum::<usize>();
+        let limit = marks.len() * passes * (jumps + 1);
+
+        let mut order = vec![];
+        let mut idx = 0;
+        let mut start = 0;
+        let mut pass = 1;
+        let mut jumped = false;
+        let mut returning = false;
+        let mut taken: HashSet<usize> = HashSet::new();
+
+        while idx < marks.len() && order.len() < limit {
+            let m = &marks[idx];
+            if m.forward && !returning {
+                start = idx;
+                pass = 1;
+            }
+            returning = false;
+
+            let volta = volta_of[idx].map(|v| &voltas[v]);
+            if let Some(v) = volta.filter(|v| v.first == idx) {
+                let play = match marks[v.last].backward {
+                    Some(r) if jumped && r.after_jump != Some(YesNo::Yes) => false,
+                    None if jumped => true,
+                    _ => v.passes.contains(&pass),
+                };
+                if !play {
+                    idx = v.last + 1;
+                    if v.last == v.group_end {
+                        start = idx;
+                        pass = 1;
+                    }
+                    continue;
+                }
+            }
+
+            order.push(idx);
+
+            if jumped {
+                if m.sound(|s| s.fine.as_ref()).is_some() {
+                    break;
+                }
+                let coda = m.sound(|s| s.tocoda.as_deref()).and_then(|name| {
+                    target(&marks, idx + 1, name, |s| s.coda.as_deref(), |m| m.coda)
+                });
+                if let Some(coda) = coda {
+                    idx = coda;
+                    start = coda;
+                    pass = 1;
+                    continue;
+                }
+            }
+
+            if let Some(repeat) = m.takes_repeat(jumped) {
+                let times = repeat
+                    .times
+                    .unwrap_or_else(|| volta.map_or(2, |v| v.group_passes.max(2)));
+                if pass < times {
+                    pass += 1;
+                    idx = start;
+                    returning = true;
+                    continue;
+                }
+            }
+
+            if !taken.contains(&idx) {
+                let jump = if m.sound(|s| s.dacapo.as_ref()) == Some(&YesNo::Yes) {
+                    Some(0)
+                } else {
+                    m.sound(|s| s.dalsegno.as_deref()).map(|name| {
+                        target(&marks, 0, name, |s| s.segno.as_deref(), |m| m.segno).unwrap_or(0)
+                    })
+                };
+                if let Some(jump) = jump {
+                    taken.insert(idx);
+                    jumped = true;
+                    idx = jump;
+                    start = jump;
+                    pass = 1;
+                    continue;
+                }
+            }
+
+            match volta {
+                Some(v) if v.group_end == idx => {
+                    start = idx + 1;
+                    pass = 1;
+                }
+                None if m.backward.is_some() => {
+                    start = idx + 1;
+                    pass = 1;
+                }
+                _ => {}
+            }
+            idx += 1;
+        }
+
+        order
+    }
+}
+
+impl ScorePartwise {
+    /// Measure indices in playing order, taken from the first part since all parts
+    /// share their repeats and jumps.
+    pub fn performance_order(&self) -> Vec<usize> {
+        self.parts
+            .first()
+            .map(Part::performance_order)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::musicxml::score_partwise::ScorePartwise;
+    use serde_xml_rs::from_str;
+    use std::fs;
+
+    fn order(file: &str) -> Vec<usize> {
+        let xml = fs::read_to_string(format!("resources/xml-test-files/{file}")).unwrap();
+        let item: ScorePartwise = from_str(&xml).unwrap();
+        item.performance_order()
+    }
+
+    fn score(measures: &[&str]) -> ScorePartwise {
+        let measures: String = measures
+            .iter()
+            .enumerate()
+            .map(|(i, content)| format!(r#"<measure number="{}">{content}</measure>"#, i + 1))
+            .collect();
+        let xml = format!(
+            r#"<score-partwise version="4.0">
+                <part-list><score-part id="P1"><part-name>Music</part-name></score-part></part-list>
+                <part id="P1">{measures}</part>
+            </score-partwise>"#
+        );
+        from_str(&xml).unwrap()
+    }
+
+    #[test]
+    fn repeats() {
+        assert_eq!(order("45a-SimpleRepeat.xml"), vec![0, 0, 0, 0, 0, 1]);
+        assert_eq!(
+            order("45c-RepeatMultipleTimes.xml"),
+            [vec![0], [1, 2].repeat(5), [3, 4, 5, 6].repeat(3), vec![7]].concat()
+        );
+        assert_eq!(order("45g-Repeats-NotEnded.xml"), vec![0, 1]);
+    }
+
+    #[test]
+    fn endings() {
+        assert_eq!(order("45b-RepeatWithAlternatives.xml"), vec![0, 1, 0, 2, 3]);
+        assert_eq!(
+            order("45d-Repeats-Nested-Alternatives.xml"),
+            vec![0, 1, 0, 2, 3, 4, 6, 7, 8, 9, 11]
+        );
+        assert_eq!(
+            order("45e-Repeats-Nested-Alternatives.xml"),
+            vec![0, 1, 0, 2, 3, 4, 4, 5, 6, 5, 7, 8, 7, 8, 9]
+        );
+        assert_eq!(order("45f-Repeats-InvalidEndings.xml"), vec![0, 1, 4]);
+    }
+
+    #[test]
+    fn jumps() {
+        let da_capo = score(&[
+            "",
+            r#"<barline location="right"><repeat direction="backward"/></barline>"#,
+            r#"<sound fine="yes"/>"#,
+            r#"<sound dacapo="yes"/>"#,
+        ]);
+        assert_eq!(da_capo.performance_order(), vec![0, 1, 0, 1, 2, 3, 0, 1, 2]);
+
+        let dal_segno = score(&[
+            "",
+            r#"<sound segno="segno"/>"#,
+            r#"<sound tocoda="coda"/>"#,
+            r#"<sound dalsegno="segno"/>"#,
+            r#"<sound coda="coda"/>"#,
+        ]);
+        assert_eq!(dal_segno.performance_order(), vec![0, 1, 2, 3, 1, 2, 4]);
+    }
+}
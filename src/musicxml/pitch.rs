@@ -5,6 +5,7 @@ use super::harmony::{Pitch, Step};
 
 pub fn parse_option_pitch(el: Node) -> Option<Pitch> {
     let mut step: Step = Step::A;
+    let mut alter: Option<f32> = None;
     let mut octave: u8 = 0;
     for child in el.children() {
         let child_name = child.tag_name().name();
@@ -16,6 +17,14 @@ pub fn parse_option_pitch(el: Node) -> Option<Pitch> {
                         step = Step::from_str(t.trim()).unwrap();
                     }
                 }
+                "alter" => {
+                    let text = child.text();
+                    if let Some(x) = text {
+                        if let Ok(d) = x.trim().parse() {
+                            alter = Some(d);
+                        }
+                    }
+                }
                 "octave" => {
                     let text = child.text();
                     if let Some(x) = text {
@@ -29,5 +38,9 @@ pub fn parse_option_pitch(el: Node) -> Option<Pitch> {
             _ => {}
         }
     }
-    Some(Pitch { step, octave })
+    Some(Pitch {
+        step,
+        alter,
+        octave,
+    })
 }
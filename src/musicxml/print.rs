@@ -3,11 +3,23 @@ use serde::{Deserialize, Serialize};
 use std::str;
 
 use super::{
-    attributes::Attributes, barline::Barline, core::Duration, direction::Direction,
-    harmony::Harmony, left_right_middle::LeftRightMiddle, level::Level,
-    measure_layout::MeasureLayout, measure_numbering_value::MeasureNumberingValue, note::Note,
-    page_layout::PageLayout, part_display::PartDisplay, printable_value::PrintableValue,
-    staff_layout::StaffLayout, system_layout::SystemLayout, yes_no::YesNo,
+    attributes::Attributes,
+    barline::Barline,
+    core::Duration,
+    direction::Direction,
+    harmony::Harmony,
+    left_right_middle::LeftRightMiddle,
+    level::Level,
+    measure_layout::MeasureLayout,
+    measure_numbering_value::MeasureNumberingValue,
+    note::Note,
+    page_layout::PageLayout,
+    part_display::PartDisplay,
+    printable_value::PrintableValue,
+    staff_layout::StaffLayout,
+    system_layout::SystemLayout,
+    writer::{Attrs, WriteXml, XmlWriter},
+    yes_no::YesNo,
 };
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -51,3 +63,25 @@ pub struct Print {
     #[serde(rename = "staff-spacing", default = "Option::default")]
     pub staff_spacing: Option<f32>,
 }
+
+impl WriteXml for Print {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .opt("blank-page", &self.blank_page)
+            .opt("id", &self.id)
+            .opt("new-page", &self.new_page)
+            .opt("new-system", &self.new_system)
+            .opt("page-number", &self.page_number)
+            .opt("staff-spacing", &self.staff_spacing);
+
+        w.start(name, attrs);
+        w.opt_element("page-layout", &self.page_layout);
+        w.opt_element("system-layout", &self.system_layout);
+        w.opt_element("staff-layout", &self.staff_layout);
+        w.opt_element("measure-layout", &self.measure_layout);
+        w.opt_element("measure-numbering", &self.measure_numbering);
+        w.opt_element("part-name-display", &self.part_name_display);
+        w.opt_element("part-abbreviation-display", &self.part_abbreviation_display);
+        w.end(name);
+    }
+}
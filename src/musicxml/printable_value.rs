@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
 
-use super::{core::Placement, yes_no::YesNo};
+use super::{
+    core::Placement,
+    writer::{Attrs, WriteXml, XmlWriter},
+    yes_no::YesNo,
+};
 
 // https://www.w3.org/2021/06/musicxml40/musicxml-reference/data-types/text-direction/
 #[derive(Serialize, Deserialize, Debug, Default, PartialEq, PartialOrd, Clone)]
@@ -25,6 +29,7 @@ pub enum TextDirection {
 #[serde(rename = "enclosure-shape")]
 pub enum EnclosureShape {
     #[default]
+    #[serde(rename = "none")]
     None,
 
     #[serde(rename = "rectangle")]
@@ -255,6 +260,57 @@ where
     pub content: T,
 }
 
+impl<T> PrintableValue<T>
+where
+    T: Default,
+{
+    /// The print attributes of this value, without its content.
+    pub fn attrs(&self) -> Attrs {
+        Attrs::new()
+            .opt("color", &self.color)
+            .opt("default-x", &self.default_x)
+            .opt("default-y", &self.default_y)
+            .opt("dir", &self.text_direction)
+            .opt("enclosure", &self.enclosure)
+            .opt("font-family", &self.font_family)
+            .opt("font-size", &self.font_size)
+            .opt("font-weight", &self.font_weight)
+            .opt("font-style", &self.font_style)
+            .opt("halign", &self.halign)
+            .opt("justify", &self.justify)
+            .opt("letter-spacing", &self.letter_spacing)
+            .opt("line-height", &self.line_height)
+            .opt("line-through", &self.line_through)
+            .opt("overline", &self.overline)
+            .opt("relative-x", &self.relative_x)
+            .opt("relative-y", &self.relative_y)
+            .opt("rotation", &self.rotation)
+            .opt("underline", &self.underline)
+            .opt("valign", &self.valign)
+            .opt("xml:lang", &self.xml_lang)
+            .opt("xml:space", &self.xml_space)
+            .opt("text", &self.text)
+            .opt("location", &self.location)
+            .opt("print-object", &self.print_object)
+            .opt("bracket-degrees", &self.bracket_degrees)
+            .opt("parentheses-degrees", &self.parentheses_degrees)
+            .opt("stack-degrees", &self.stack_degrees)
+            .opt("use-symbols", &self.use_symbols)
+            .opt("alternate", &self.alternate)
+            .opt("placement", &self.placement)
+            .opt("substitution", &self.substitution)
+    }
+}
+
+impl<T> WriteXml for PrintableValue<T>
+where
+    T: Default + Serialize,
+{
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.text_element_with(name, self.attrs(), &self.content);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_xml_rs::from_str;
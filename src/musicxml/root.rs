@@ -1,4 +1,9 @@
-use super::{harmony::Step, printable_value::PrintableValue};
+use super::{
+    harmony::Step,
+    interval::Interval,
+    printable_value::PrintableValue,
+    writer::{Attrs, WriteXml, XmlWriter},
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Default)]
@@ -7,7 +12,30 @@ pub struct Root {
     pub step: PrintableValue<Step>,
 
     #[serde(rename = "root-alter", default = "Option::default")]
-    pub alter: Option<PrintableValue<u8>>,
+    pub alter: Option<PrintableValue<f32>>,
+}
+
+impl Root {
+    /// Moves the root by `interval`, keeping its spelling correct and its formatting.
+    pub fn transpose(&mut self, interval: &Interval) {
+        let alter = self.alter.as_ref().map_or(0.0, |a| a.content);
+        let (step, alter) = self.step.content.transpose(alter, interval);
+        self.step.content = step;
+        if alter != 0.0 || self.alter.is_some() {
+            self.alter
+                .get_or_insert_with(PrintableValue::default)
+                .content = alter;
+        }
+    }
+}
+
+impl WriteXml for Root {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.element("root-step", &self.step);
+        w.opt_element("root-alter", &self.alter);
+        w.end(name);
+    }
 }
 
 #[cfg(test)]
@@ -28,6 +56,6 @@ mod tests {
         let item: Root = from_str(xml).unwrap();
 
         assert_eq!(item.step.content, Step::C);
-        assert_eq!(item.alter.unwrap().content, 1);
+        assert_eq!(item.alter.unwrap().content, 1.0);
     }
 }
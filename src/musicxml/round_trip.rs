@@ -0,0 +1,369 @@
+use super::{parse_document, parse_str};
+use crate::error::MusicXmlError;
+use crate::prelude::*;
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+/// What got lost when a document was parsed and written back out. Paths look like
+/// `score-partwise/part/measure/note/notations` for elements and
+/// `score-partwise/part/measure/note@default-x` for attributes, and map to the number
+/// of occurrences that are missing from the written document. `changed_values` uses
+/// the same paths for text content and attribute values that were written back
+/// differently.
+#[derive(Debug, Default)]
+pub struct RoundTrip {
+    pub dropped_elements: BTreeMap<String, usize>,
+    pub dropped_attributes: BTreeMap<String, usize>,
+    pub changed_values: BTreeMap<String, usize>,
+}
+
+impl RoundTrip {
+    pub fn is_lossless(&self) -> bool {
+        self.dropped_elements.is_empty()
+            && self.dropped_attributes.is_empty()
+            && self.changed_values.is_empty()
+    }
+}
+
+/// Parses `xml`, writes it back out and parses the result again. The returned diff
+/// lists everything from `xml` that did not survive the trip.
+pub fn round_trip(xml: &str) -> std::result::Result<RoundTrip, MusicXmlError> {
+    let written = parse_str(xml)?.to_xml();
+    parse_str(&written)?;
+    diff(xml, &written)
+}
+
+/// Compares two documents and reports the elements and attributes of `original` that
+/// are missing from `written`, and the text content and attribute values that were
+/// written back differently. Children of a dropped element and attributes of a dropped
+/// element are not reported separately. Values are compared per path regardless of
+/// element order, and numbers compare by value, so `1.0` and `1` are the same.
+pub fn diff(original: &str, written: &str) -> std::result::Result<RoundTrip, MusicXmlError> {
+    let orig = Paths::of(&parse_document(original)?);
+    let kept = Paths::of(&parse_document(written)?);
+
+    let dropped_elements = missing(&orig.elements, &kept.elements);
+    let dropped_attributes: BTreeMap<String, usize> = missing(&orig.attributes, &kept.attributes)
+        .into_iter()
+        .filter(|(path, _)| match path.split_once('@') {
+            Some((element, _)) => !dropped_elements.contains_key(element),
+            None => true,
+        })
+        .collect();
+
+    // A value that went missing along with its element or attribute is a drop already.
+    let changed_values = orig
+        .values
+        .iter()
+        .filter_map(|(path, values)| {
+            let mut written = kept.values.get(path).cloned().unwrap_or_default();
+            let lost = values
+                .iter()
+                .filter(|value| match written.iter().position(|w| w == *value) {
+                    Some(i) => {
+                        written.swap_remove(i);
+                        false
+                    }
+                    None => true,
+                })
+                .count();
+            let element = path.split_once('@').map_or(path.as_str(), |(e, _)| e);
+            let dropped = if element.len() < path.len() {
+                dropped_attributes.get(path).copied().unwrap_or(0)
+                    + dropped_elements.get(element).copied().unwrap_or(0)
+            } else {
+                dropped_elements.get(path).copied().unwrap_or(0)
+            };
+            (lost > dropped).then(|| (path.clone(), lost - dropped))
+        })
+        .collect();
+
+    // Only keep the outermost dropped element of every subtree.
+    let dropped_elements = dropped_elements
+        .iter()
+        .filter(|(path, _)| match path.rsplit_once('/') {
+            Some((parent, _)) => !dropped_elements.contains_key(parent),
+            None => true,
+        })
+        .map(|(path, count)| (path.clone(), *count))
+        .collect();
+
+    Ok(RoundTrip {
+        dropped_elements,
+        dropped_attributes,
+        changed_values,
+    })
+}
+
+/// Occurrences of every element and attribute path of a document, and the values
+/// found at them.
+#[derive(Default)]
+struct Paths {
+    elements: BTreeMap<String, usize>,
+    attributes: BTreeMap<String, usize>,
+    values: BTreeMap<String, Vec<Value>>,
+}
+
+impl Paths {
+    fn of(doc: &Document) -> Paths {
+        let mut paths = Paths::default();
+
+        for node in doc.descendants().filter(|n| n.is_element()) {
+            let mut names: Vec<&str> = node
+                .ancestors()
+                .filter(|n| n.is_element())
+                .map(|n| n.tag_name().name())
+                .collect();
+            names.reverse();
+            let path = names.join("/");
+
+            for attribute in node.attributes() {
+                let attribute_path = format!("{}@{}", path, attribute.name());
+                *paths.attributes.entry(attribute_path.clone()).or_insert(0) += 1;
+                paths
+                    .values
+                    .entry(attribute_path)
+                    .or_default()
+                    .push(Value::of(attribute.value()));
+            }
+
+            let text: String = node
+                .children()
+                .filter(|n| n.is_text())
+                .filter_map(|n| n.text())
+                .collect();
+            if !text.trim().is_empty() {
+                paths
+                    .values
+                    .entry(path.clone())
+                    .or_default()
+                    .push(Value::of(&text));
+            }
+            *paths.elements.entry(path).or_insert(0) += 1;
+        }
+
+        paths
+    }
+}
+
+/// Text content or an attribute value, read as a number where it is one.
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Number(f64),
+    Text(String),
+}
+
+impl Value {
+    fn of(text: &str) -> Value {
+        let text = text.trim();
+        match text.parse() {
+            Ok(number) => Value::Number(number),
+            Err(_) => Value::Text(text.to_string()),
+        }
+    }
+}
+
+fn missing(
+    original: &BTreeMap<String, usize>,
+    written: &BTreeMap<String, usize>,
+) -> BTreeMap<String, usize> {
+    original
+        .iter()
+        .filter_map(|(path, count)| {
+            let kept = written.get(path).copied().unwrap_or(0);
+            (*count > kept).then(|| (path.clone(), count - kept))
+        })
+        .collect()
+}
+
+/// The result of round-tripping every MusicXML file of a directory.
+#[derive(Debug, Default)]
+pub struct Coverage {
+    pub files: Vec<(PathBuf, RoundTrip)>,
+    /// Files the model cannot read.
+    pub failures: Vec<(PathBuf, String)>,
+    /// Files that parse, but whose written form does not parse again.
+    pub rewrite_failures: Vec<(PathBuf, String)>,
+}
+
+impl Coverage {
+    /// Round-trips all uncompressed MusicXML files (`.xml`, `.musicxml`) in `dir`.
+    /// Files that fail are collected rather than aborting the run.
+    pub fn of_dir<P: AsRef<Path>>(dir: P) -> std::result::Result<Coverage, MusicXmlError> {
+        let mut paths: Vec<PathBuf> = fs::read_dir(dir)?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| {
+                p.extension().is_some_and(|e| {
+                    e.eq_ignore_ascii_case("xml") || e.eq_ignore_ascii_case("musicxml")
+                })
+            })
+            .collect();
+        paths.sort();
+
+        let mut coverage = Coverage::default();
+        for path in paths {
+            let xml = fs::read_to_string(&path)?;
+            let written = match parse_str(&xml) {
+                Ok(score) => score.to_xml(),
+                Err(e) => {
+                    coverage.failures.push((path, e.to_string()));
+                    continue;
+                }
+            };
+
+            match parse_str(&written).and_then(|_| diff(&xml, &written)) {
+                Ok(rt) => coverage.files.push((path, rt)),
+                Err(e) => coverage.rewrite_failures.push((path, e.to_string())),
+            }
+        }
+
+        Ok(coverage)
+    }
+
+    pub fn lossless(&self) -> usize {
+        self.files.iter().filter(|(_, rt)| rt.is_lossless()).count()
+    }
+
+    /// Element and attribute names (`note`, `note@default-x`) the model does not capture,
+    /// with the number of files they were dropped from.
+    pub fn uncaptured(&self) -> BTreeMap<String, usize> {
+        let mut names: BTreeMap<String, usize> = BTreeMap::new();
+
+        for (_, rt) in &self.files {
+            let mut seen: Vec<String> = rt
+                .dropped_elements
+                .keys()
+                .chain(rt.dropped_attributes.keys())
+                .map(|path| path.rsplit('/').next().unwrap_or(path).to_string())
+                .collect();
+            seen.sort();
+            seen.dedup();
+
+            for name in seen {
+                *names.entry(name).or_insert(0) += 1;
+            }
+        }
+
+        names
+    }
+}
+
+impl fmt::Display for Coverage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = self.files.len() + self.failures.len() + self.rewrite_failures.len();
+        writeln!(
+            f,
+            "{} files: {} lossless, {} lossy, {} not readable, {} not readable after writing",
+            total,
+            self.lossless(),
+            self.files.len() - self.lossless(),
+            self.failures.len(),
+            self.rewrite_failures.len()
+        )?;
+
+        for (path, error) in &self.failures {
+            writeln!(f, "\nFAILED {}\n  {}", path.display(), error)?;
+        }
+        for (path, error) in &self.rewrite_failures {
+            writeln!(f, "\nFAILED AFTER WRITING {}\n  {}", path.display(), error)?;
+        }
+
+        for (path, rt) in self.files.iter().filter(|(_, rt)| !rt.is_lossless()) {
+            writeln!(f, "\n{}", path.display())?;
+            for (element, count) in &rt.dropped_elements {
+                writeln!(f, "  dropped element {} ({}x)", element, count)?;
+            }
+            for (attribute, count) in &rt.dropped_attributes {
+                writeln!(f, "  dropped attribute {} ({}x)", attribute, count)?;
+            }
+            for (path, count) in &rt.changed_values {
+                writeln!(f, "  changed value {} ({}x)", path, count)?;
+            }
+        }
+
+        let mut uncaptured: Vec<(String, usize)> = self.uncaptured().into_iter().collect();
+        uncaptured.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        writeln!(f, "\nNot captured by the model (files affected):")?;
+        for (name, files) in uncaptured {
+            writeln!(f, "  {:<40} {}", name, files)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, round_trip, Coverage};
+
+    #[test]
+    fn diff_reports_outermost_drops() {
+        let original = r#"
+            <measure number="1" width="120">
+                <note default-x="10">
+                    <pitch><step>C</step><octave>4</octave></pitch>
+                    <notations><slur type="start"/></notations>
+                </note>
+            </measure>"#;
+        let written = r#"
+            <measure number="1">
+                <note>
+                    <pitch><step>C</step><octave>4</octave></pitch>
+                </note>
+            </measure>"#;
+
+        let rt = diff(original, written).unwrap();
+
+        assert!(!rt.is_lossless());
+        assert_eq!(rt.dropped_elements.len(), 1);
+        assert_eq!(rt.dropped_elements["measure/note/notations"], 1);
+        assert_eq!(rt.dropped_attributes.len(), 2);
+        assert_eq!(rt.dropped_attributes["measure@width"], 1);
+        assert_eq!(rt.dropped_attributes["measure/note@default-x"], 1);
+    }
+
+    #[test]
+    fn diff_reports_changed_values() {
+        let original = r#"
+            <measure number="1" width="120.0">
+                <note><type>quarter</type><duration>2</duration></note>
+                <note><type>eighth</type><duration>1</duration></note>
+            </measure>"#;
+        let written = r#"
+            <measure number="1" width="120">
+                <note><type>eighth</type><duration>1</duration></note>
+                <note><type>eighth</type><duration>1</duration></note>
+            </measure>"#;
+
+        let rt = diff(original, written).unwrap();
+
+        assert!(!rt.is_lossless());
+        assert!(rt.dropped_elements.is_empty());
+        assert!(rt.dropped_attributes.is_empty());
+        assert_eq!(rt.changed_values.len(), 2);
+        assert_eq!(rt.changed_values["measure/note/type"], 1);
+        assert_eq!(rt.changed_values["measure/note/duration"], 1);
+    }
+
+    #[test]
+    fn hello_world_is_lossless() {
+        let xml = std::fs::read_to_string("xml-files/hello-world.xml").unwrap();
+        assert!(round_trip(&xml).unwrap().is_lossless());
+    }
+
+    #[test]
+    fn test_suite() {
+        let coverage = Coverage::of_dir("resources/xml-test-files").unwrap();
+        let total =
+            coverage.files.len() + coverage.failures.len() + coverage.rewrite_failures.len();
+
+        assert!(total > 140);
+        // Fidelity floor, raise it as the model learns more elements.
+        assert!(coverage.lossless() >= 106, "{}", coverage);
+        assert!(coverage.rewrite_failures.is_empty(), "{}", coverage);
+    }
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+use super::harmony::Step;
+use super::writer::{Attrs, WriteXml, XmlWriter};
+use crate::prelude::*;
+
+/// The tuning of one string.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone)]
+pub struct Accord {
+    #[serde(default = "u8::default")]
+    pub string: u8,
+
+    #[serde(rename = "tuning-step", default = "Step::default")]
+    pub tuning_step: Step,
+
+    #[serde(rename = "tuning-alter", default = "Option::default")]
+    pub tuning_alter: Option<f32>,
+
+    #[serde(rename = "tuning-octave", default = "u8::default")]
+    pub tuning_octave: u8,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/scordatura/
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone)]
+#[serde(from = "ScordaturaXml")]
+pub struct Scordatura {
+    pub accords: Vec<Accord>,
+}
+
+#[derive(Deserialize)]
+enum ScordaturaChild {
+    #[serde(rename = "accord")]
+    Accord(Accord),
+}
+
+#[derive(Deserialize)]
+struct ScordaturaXml {
+    #[serde(rename = "$value", default = "Vec::default")]
+    children: Vec<ScordaturaChild>,
+}
+
+impl From<ScordaturaXml> for Scordatura {
+    fn from(xml: ScordaturaXml) -> Self {
+        Scordatura {
+            accords: xml
+                .children
+                .into_iter()
+                .map(|ScordaturaChild::Accord(a)| a)
+                .collect(),
+        }
+    }
+}
+
+impl WriteXml for Accord {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new().add("string", &self.string));
+        w.text_element("tuning-step", &self.tuning_step);
+        w.opt_text_element("tuning-alter", &self.tuning_alter);
+        w.text_element("tuning-octave", &self.tuning_octave);
+        w.end(name);
+    }
+}
+
+impl WriteXml for Scordatura {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.elements("accord", &self.accords);
+        w.end(name);
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 
+use crate::musicxml::writer::{Attrs, WriteXml, XmlWriter};
 use crate::prelude::*;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -13,3 +14,14 @@ pub struct ScoreInstrument {
     #[serde(rename = "instrument-sound", default = "String::default")]
     pub instrument_sound: String,
 }
+
+impl WriteXml for ScoreInstrument {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new().add("id", &self.id));
+        w.text_element("instrument-name", &self.instrument_name);
+        if !self.instrument_sound.is_empty() {
+            w.text_element("instrument-sound", &self.instrument_sound);
+        }
+        w.end(name);
+    }
+}
@@ -6,7 +6,9 @@ use crate::musicxml::{
 use crate::prelude::*;
 
 use super::identification::Identification;
+use super::note::Note;
 use super::part_group::GroupDisplay;
+use super::writer::{Attrs, WriteXml, XmlWriter};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Player {
@@ -53,11 +55,105 @@ pub struct ScorePart {
     pub content : Vec<ScorePartContent>
 }
 
+/// The instrument a percussion note is played on, resolved through the part's
+/// `<score-instrument>` and `<midi-instrument>` entries.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PercussionInstrument {
+    pub id: String,
+    pub name: String,
+    /// MIDI key number from 0 to 127, i.e. `<midi-unpitched>` minus one.
+    pub midi_key: Option<u8>,
+}
+
+impl ScorePart {
+    pub fn part_name(&self) -> Option<&str> {
+        self.content.iter().find_map(|c| match c {
+            ScorePartContent::PartName(name) => Some(name.as_str()),
+            _ => None,
+        })
+    }
+
+    pub fn score_instruments(&self) -> impl Iterator<Item = &ScoreInstrument> {
+        self.content.iter().filter_map(|c| match c {
+            ScorePartContent::ScoreInstrument(i) => Some(i),
+            _ => None,
+        })
+    }
+
+    pub fn midi_instruments(&self) -> impl Iterator<Item = &MidiInstrument> {
+        self.content.iter().filter_map(|c| match c {
+            ScorePartContent::MidiInstrument(i) => Some(i),
+            _ => None,
+        })
+    }
+
+    /// Resolves the instrument of an unpitched note. Notes without an `<instrument>`
+    /// reference use the part's instrument if it only has one.
+    pub fn percussion_instrument(&self, note: &Note) -> Option<PercussionInstrument> {
+        note.unpitched.as_ref()?;
+
+        let score_instrument = match note.instruments.first() {
+            Some(reference) => self.score_instruments().find(|i| i.id == reference.id)?,
+            None => {
+                let mut instruments = self.score_instruments();
+                match (instruments.next(), instruments.next()) {
+                    (Some(only), None) => only,
+                    _ => return None,
+                }
+            }
+        };
+
+        let midi_key = self
+            .midi_instruments()
+            .find(|i| i.id == score_instrument.id)
+            .and_then(|i| i.midi_unpitched)
+            .map(|key| key.saturating_sub(1));
+
+        Some(PercussionInstrument {
+            id: score_instrument.id.clone(),
+            name: score_instrument.instrument_name.clone(),
+            midi_key,
+        })
+    }
+}
+
+impl WriteXml for Player {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new().add("id", &self.id));
+        w.text_element("player-name", &self.player_name);
+        w.end(name);
+    }
+}
+
+impl WriteXml for ScorePartContent {
+    fn write_xml(&self, w: &mut XmlWriter, _name: &str) {
+        match self {
+            ScorePartContent::ScoreInstrument(i) => w.element("score-instrument", i),
+            ScorePartContent::Player(p) => w.element("player", p),
+            ScorePartContent::MidiDevice(d) => w.element("midi-device", d),
+            ScorePartContent::MidiInstrument(i) => w.element("midi-instrument", i),
+            ScorePartContent::PartName(n) => w.text_element("part-name", n),
+            ScorePartContent::PartNameDisplay(d) => w.element("part-name-display", d),
+            ScorePartContent::PartAbbreviation(a) => w.text_element("part-abbreviation", a),
+        }
+    }
+}
+
+impl WriteXml for ScorePart {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new().add("id", &self.id));
+        w.opt_element("identification", &self.identification);
+        w.elements("", &self.content);
+        w.end(name);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_xml_rs::from_str;
 
-    use super::ScorePart;
+    use super::{PercussionInstrument, ScorePart};
+    use crate::musicxml::note::Note;
 
     #[test]
     fn score_part_1() {
@@ -149,4 +245,60 @@ mod tests {
 
         let item : ScorePart = from_str(xml).unwrap();
     }
+
+    #[test]
+    fn percussion_instrument() {
+        let xml = r#"
+            <score-part id="P1">
+                <part-name>Drums</part-name>
+                <score-instrument id="P1-I36">
+                    <instrument-name>Bass Drum 1</instrument-name>
+                </score-instrument>
+                <score-instrument id="P1-I39">
+                    <instrument-name>Snare</instrument-name>
+                </score-instrument>
+                <midi-instrument id="P1-I36">
+                    <midi-channel>10</midi-channel>
+                    <midi-unpitched>36</midi-unpitched>
+                </midi-instrument>
+                <midi-instrument id="P1-I39">
+                    <midi-channel>10</midi-channel>
+                    <midi-unpitched>39</midi-unpitched>
+                </midi-instrument>
+            </score-part>
+        "#;
+        let item: ScorePart = from_str(xml).unwrap();
+
+        let xml = r#"
+            <note>
+                <unpitched>
+                    <display-step>C</display-step>
+                    <display-octave>5</display-octave>
+                </unpitched>
+                <duration>1</duration>
+                <instrument id="P1-I39"/>
+                <voice>1</voice>
+                <type>eighth</type>
+            </note>"#;
+        let note: Note = from_str(xml).unwrap();
+
+        assert_eq!(
+            item.percussion_instrument(&note),
+            Some(PercussionInstrument {
+                id: "P1-I39".to_string(),
+                name: "Snare".to_string(),
+                midi_key: Some(38),
+            })
+        );
+
+        // Without a reference the instrument is ambiguous.
+        let xml = r#"
+            <note>
+                <unpitched/>
+                <duration>1</duration>
+                <type>eighth</type>
+            </note>"#;
+        let note: Note = from_str(xml).unwrap();
+        assert_eq!(item.percussion_instrument(&note), None);
+    }
 }
@@ -7,7 +7,8 @@ use crate::musicxml::{
 use crate::prelude::*;
 
 use super::part::Part;
-use super::part_list::PartList;
+use super::part_list::{PartList, PartListContent};
+use super::writer::{Attrs, WriteXml, XmlWriter, MUSICXML_VERSION, PARTWISE_DOCTYPE};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ScorePartwise {
@@ -39,9 +40,50 @@ pub struct ScorePartwise {
     pub credits: Vec<Credit>,
 }
 
+impl ScorePartwise {
+    /// Looks up the part-list entry of the part with the given `id`.
+    pub fn score_part(&self, id: &str) -> Option<&ScorePart> {
+        self.part_list.parts.iter().find_map(|p| match p {
+            PartListContent::ScorePart(score_part) if score_part.id == id => Some(score_part),
+            _ => None,
+        })
+    }
+
+    /// Writes the score as a MusicXML 4.0 partwise document.
+    pub fn to_xml(&self) -> String {
+        let mut w = XmlWriter::document(PARTWISE_DOCTYPE);
+        self.write_xml(&mut w, "score-partwise");
+        w.finish()
+    }
+}
+
+impl WriteXml for ScorePartwise {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let version = if self.version.is_empty() {
+            MUSICXML_VERSION
+        } else {
+            &self.version
+        };
+
+        w.start(name, Attrs::new().add("version", version));
+        w.opt_element("work", &self.work);
+        w.opt_text_element("movement-number", &self.movement_number);
+        w.opt_text_element("movement-title", &self.movement_title);
+        w.opt_element("identification", &self.identification);
+        w.opt_element("defaults", &self.defaults);
+        w.elements("credit", &self.credits);
+        w.element("part-list", &self.part_list);
+        w.elements("part", &self.parts);
+        w.end(name);
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::musicxml::{part_list::PartListContent, score_part::ScorePartContent};
+    use crate::musicxml::{
+        harmony::Step, measure::MeasureContent, part_list::PartListContent,
+        score_part::ScorePartContent,
+    };
 
     use super::ScorePartwise;
     use serde_xml_rs::from_str;
@@ -101,7 +143,17 @@ mod tests {
     #[test]
     fn score_partwise_4() {
         let xml = fs::read_to_string("resources/xml-test-files/01d-Pitches-Microtones.xml").unwrap();
-        let item: ScorePartwise = from_str(&xml).unwrap();        
+        let item: ScorePartwise = from_str(&xml).unwrap();
+
+        let alters: Vec<Option<f32>> = item.parts[0].measures[0]
+            .content
+            .iter()
+            .filter_map(|c| match c {
+                MeasureContent::Note(n) => n.pitch.as_ref().map(|p| p.alter),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(alters, vec![Some(-1.5), Some(-0.5), Some(0.5), Some(1.5)]);
     }
 
     #[test]
@@ -116,6 +168,26 @@ mod tests {
         let item: ScorePartwise = from_str(&xml).unwrap();        
     }
 
+    #[test]
+    fn percussion() {
+        let xml = fs::read_to_string("resources/xml-test-files/73a-Percussion.xml").unwrap();
+        let item: ScorePartwise = from_str(&xml).unwrap();
+
+        let unpitched: Vec<_> = item.parts[1]
+            .measures
+            .iter()
+            .flat_map(|m| m.content.iter())
+            .filter_map(|c| match c {
+                MeasureContent::Note(n) => n.unpitched.clone(),
+                _ => None,
+            })
+            .collect();
+
+        assert!(!unpitched.is_empty());
+        assert_eq!(unpitched[0].display_step, Some(Step::E));
+        assert_eq!(unpitched[0].display_octave, Some(5));
+    }
+
     // This is an export from MuseScore.
     #[test]
     fn my_bonnie() {
@@ -0,0 +1,347 @@
+use serde::{Deserialize, Serialize};
+
+use crate::musicxml::{
+    credit::Credit, defaults::Defaults, identification::Identification, work::Work,
+};
+use crate::prelude::*;
+
+use super::measure::{Measure, MeasureContent};
+use super::part::Part;
+use super::part_list::PartList;
+use super::score_partwise::ScorePartwise;
+use super::writer::{Attrs, WriteXml, XmlWriter, MUSICXML_VERSION, TIMEWISE_DOCTYPE};
+use super::yes_no::YesNo;
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/part-timewise/
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct TimewisePart {
+    #[serde(default = "String::default")]
+    pub id: String,
+
+    #[serde(rename = "$value", default = "Vec::default")]
+    pub content: Vec<MeasureContent>,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/measure-timewise/
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct TimewiseMeasure {
+    #[serde(default = "String::default")]
+    pub number: String,
+
+    #[serde(default = "Option::default")]
+    pub id: Option<String>,
+
+    #[serde(default = "Option::default")]
+    pub implicit: Option<YesNo>,
+
+    #[serde(rename = "non-controlling", default = "Option::default")]
+    pub non_controlling: Option<YesNo>,
+
+    #[serde(default = "Option::default")]
+    pub text: Option<String>,
+
+    #[serde(default = "Option::default")]
+    pub width: Option<f32>,
+
+    #[serde(rename = "part", default = "Vec::default")]
+    pub parts: Vec<TimewisePart>,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/score-timewise/
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScoreTimewise {
+    #[serde(default = "Option::default")]
+    pub work: Option<Work>,
+
+    #[serde(rename = "movement-number", default = "Option::default")]
+    pub movement_number: Option<String>,
+
+    #[serde(rename = "movement-title", default = "Option::default")]
+    pub movement_title: Option<String>,
+
+    #[serde(default = "String::default")]
+    pub version: String,
+
+    #[serde(rename = "part-list", default = "PartList::default")]
+    pub part_list: PartList,
+
+    #[serde(rename = "measure", default = "Vec::default")]
+    pub measures: Vec<TimewiseMeasure>,
+
+    #[serde(default = "Option::default")]
+    pub identification: Option<Identification>,
+
+    #[serde(default = "Option::default")]
+    pub defaults: Option<Defaults>,
+
+    #[serde(default = "Vec::default")]
+    pub credits: Vec<Credit>,
+}
+
+impl ScorePartwise {
+    /// Converts the score to its timewise form, like the official `parttime.xsl`.
+    /// Measures are matched across parts by `number`, and the measure attributes
+    /// are taken from the first part that contains the measure. Unlike the
+    /// stylesheet, measures missing from the first part are kept, after the others.
+    pub fn to_timewise(self) -> ScoreTimewise {
+        let mut measures: Vec<TimewiseMeasure> = vec![];
+
+        for part in self.parts {
+            for measure in part.measures {
+                // A part that repeats a number fills the next measure with it.
+                let idx = measures.iter().position(|m| {
+                    m.number == measure.number && m.parts.iter().all(|p| p.id != part.id)
+                });
+                let idx = match idx {
+                    Some(idx) => idx,
+                    None => {
+                        measures.push(TimewiseMeasure {
+                            number: measure.number,
+                            id: measure.id,
+                            implicit: measure.implicit,
+                            non_controlling: measure.non_controlling,
+                            text: measure.text,
+                            width: measure.width,
+                            parts: vec![],
+                        });
+                        measures.len() - 1
+                    }
+                };
+
+                measures[idx].parts.push(TimewisePart {
+                    id: part.id.clone(),
+                    content: measure.content,
+                });
+            }
+        }
+
+        ScoreTimewise {
+            work: self.work,
+            movement_number: self.movement_number,
+            movement_title: self.movement_title,
+            version: self.version,
+            part_list: self.part_list,
+            measures,
+            identification: self.identification,
+            defaults: self.defaults,
+            credits: self.credits,
+        }
+    }
+}
+
+impl ScoreTimewise {
+    /// Converts the score to its partwise form, like the official `timepart.xsl`.
+    /// Parts are ordered by their first appearance and every part measure receives
+    /// the attributes of the timewise measure it came from.
+    pub fn to_partwise(self) -> ScorePartwise {
+        let mut parts: Vec<Part> = vec![];
+
+        for measure in self.measures {
+            for part in measure.parts {
+                let idx = match parts.iter().position(|p| p.id == part.id) {
+                    Some(idx) => idx,
+                    None => {
+                        parts.push(Part {
+                            id: part.id,
+                            measures: vec![],
+                        });
+                        parts.len() - 1
+                    }
+                };
+
+                parts[idx].measures.push(Measure {
+                    number: measure.number.clone(),
+                    id: measure.id.clone(),
+                    implicit: measure.implicit.clone(),
+                    non_controlling: measure.non_controlling.clone(),
+                    text: measure.text.clone(),
+                    width: measure.width,
+                    content: part.content,
+                });
+            }
+        }
+
+        ScorePartwise {
+            work: self.work,
+            movement_number: self.movement_number,
+            movement_title: self.movement_title,
+            version: self.version,
+            part_list: self.part_list,
+            parts,
+            identification: self.identification,
+            defaults: self.defaults,
+            credits: self.credits,
+        }
+    }
+
+    /// Writes the score as a MusicXML 4.0 timewise document.
+    pub fn to_xml(&self) -> String {
+        let mut w = XmlWriter::document(TIMEWISE_DOCTYPE);
+        self.write_xml(&mut w, "score-timewise");
+        w.finish()
+    }
+}
+
+impl WriteXml for TimewisePart {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new().add("id", &self.id));
+        w.elements("", &self.content);
+        w.end(name);
+    }
+}
+
+impl WriteXml for TimewiseMeasure {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .add("number", &self.number)
+            .opt("id", &self.id)
+            .opt("implicit", &self.implicit)
+            .opt("non-controlling", &self.non_controlling)
+            .opt("text", &self.text)
+            .opt("width", &self.width);
+
+        w.start(name, attrs);
+        w.elements("part", &self.parts);
+        w.end(name);
+    }
+}
+
+impl WriteXml for ScoreTimewise {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let version = if self.version.is_empty() {
+            MUSICXML_VERSION
+        } else {
+            &self.version
+        };
+
+        w.start(name, Attrs::new().add("version", version));
+        w.opt_element("work", &self.work);
+        w.opt_text_element("movement-number", &self.movement_number);
+        w.opt_text_element("movement-title", &self.movement_title);
+        w.opt_element("identification", &self.identification);
+        w.opt_element("defaults", &self.defaults);
+        w.elements("credit", &self.credits);
+        w.element("part-list", &self.part_list);
+        w.elements("measure", &self.measures);
+        w.end(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScoreTimewise;
+    use crate::musicxml::{measure::MeasureContent, score_partwise::ScorePartwise};
+    use serde_xml_rs::from_str;
+    use std::fs;
+
+    #[test]
+    fn score_timewise() {
+        let xml = fs::read_to_string("xml-files/hello-world-timewise.xml").unwrap();
+        let item: ScoreTimewise = from_str(&xml).unwrap();
+
+        assert_eq!(item.version, "4.0".to_string());
+        assert_eq!(item.measures.len(), 1);
+        assert_eq!(item.measures[0].number, "1".to_string());
+        assert_eq!(item.measures[0].parts.len(), 1);
+        assert_eq!(item.measures[0].parts[0].id, "P1".to_string());
+        assert_eq!(item.measures[0].parts[0].content.len(), 2);
+    }
+
+    #[test]
+    fn to_partwise() {
+        let xml = r#"
+            <score-timewise version="4.0">
+                <part-list>
+                    <score-part id="P1"><part-name>Flute</part-name></score-part>
+                    <score-part id="P2"><part-name>Oboe</part-name></score-part>
+                </part-list>
+                <measure number="1" width="120">
+                    <part id="P1">
+                        <note><rest/><duration>4</duration><type>whole</type></note>
+                    </part>
+                    <part id="P2">
+                        <note><rest/><duration>4</duration><type>whole</type></note>
+                    </part>
+                </measure>
+                <measure number="2">
+                    <part id="P1">
+                        <note><rest/><duration>4</duration><type>whole</type></note>
+                    </part>
+                    <part id="P2">
+                        <note><rest/><duration>2</duration><type>half</type></note>
+                        <note><rest/><duration>2</duration><type>half</type></note>
+                    </part>
+                </measure>
+            </score-timewise>"#;
+        let item: ScoreTimewise = from_str(xml).unwrap();
+        let partwise = item.to_partwise();
+
+        assert_eq!(partwise.parts.len(), 2);
+        assert_eq!(partwise.parts[0].id, "P1".to_string());
+        assert_eq!(partwise.parts[1].id, "P2".to_string());
+        assert_eq!(partwise.parts[1].measures.len(), 2);
+        assert_eq!(partwise.parts[1].measures[0].width, Some(120.0));
+        assert_eq!(partwise.parts[1].measures[1].number, "2".to_string());
+        assert_eq!(partwise.parts[1].measures[1].content.len(), 2);
+    }
+
+    #[test]
+    fn round_trip() {
+        let xml = fs::read_to_string("resources/xml-test-files/01b-Pitches-Intervals.xml").unwrap();
+        let item: ScorePartwise = from_str(&xml).unwrap();
+        let measures = item.parts[0].measures.len();
+        let notes = item.parts[0].measures[0].content.len();
+
+        let timewise = item.to_timewise();
+        assert_eq!(timewise.measures.len(), measures);
+        assert_eq!(timewise.measures[0].parts[0].content.len(), notes);
+
+        let partwise = timewise.to_partwise();
+        assert_eq!(partwise.parts.len(), 1);
+        assert_eq!(partwise.parts[0].measures.len(), measures);
+        assert_eq!(partwise.parts[0].measures[0].number, "1".to_string());
+        assert_eq!(partwise.parts[0].measures[0].content.len(), notes);
+        assert!(matches!(
+            partwise.parts[0].measures[0].content[0],
+            MeasureContent::Attributes(_)
+        ));
+    }
+
+    #[test]
+    fn to_timewise_by_number() {
+        let xml = r#"
+            <score-partwise version="4.0">
+                <part-list>
+                    <score-part id="P1"><part-name>Flute</part-name></score-part>
+                    <score-part id="P2"><part-name>Oboe</part-name></score-part>
+                </part-list>
+                <part id="P1">
+                    <measure number="1"></measure>
+                    <measure number="2"></measure>
+                </part>
+                <part id="P2">
+                    <measure number="2"></measure>
+                    <measure number="3"></measure>
+                </part>
+            </score-partwise>"#;
+        let item: ScorePartwise = from_str(xml).unwrap();
+        let timewise = item.to_timewise();
+
+        let parts: Vec<(&str, Vec<&str>)> = timewise
+            .measures
+            .iter()
+            .map(|m| {
+                let ids = m.parts.iter().map(|p| p.id.as_str()).collect();
+                (m.number.as_str(), ids)
+            })
+            .collect();
+        assert_eq!(
+            parts,
+            vec![
+                ("1", vec!["P1"]),
+                ("2", vec!["P1", "P2"]),
+                ("3", vec!["P2"])
+            ]
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+
+use super::offset::Offset;
+use super::writer::{Attrs, WriteXml, XmlWriter};
+use super::yes_no::YesNo;
+use crate::prelude::*;
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/sound/
+/// Playback information, either inside a `<direction>` or directly in a measure.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct Sound {
+    /// Quarter notes per minute.
+    #[serde(default = "Option::default")]
+    pub tempo: Option<f32>,
+
+    /// Loudness as a percentage of the default forte, which is MIDI velocity 90.
+    #[serde(default = "Option::default")]
+    pub dynamics: Option<f32>,
+
+    /// Jump back to the start of the piece.
+    #[serde(default = "Option::default")]
+    pub dacapo: Option<YesNo>,
+
+    /// Marks the target of a dal segno, named by `dalsegno`.
+    #[serde(default = "Option::default")]
+    pub segno: Option<String>,
+
+    /// Jump back to the segno with this name.
+    #[serde(default = "Option::default")]
+    pub dalsegno: Option<String>,
+
+    /// Marks the target of a to coda, named by `tocoda`.
+    #[serde(default = "Option::default")]
+    pub coda: Option<String>,
+
+    /// Jump forward to the coda with this name.
+    #[serde(default = "Option::default")]
+    pub tocoda: Option<String>,
+
+    /// Where the piece ends after a jump. The value is ignored, but some files
+    /// use it for the number of divisions into the measure.
+    #[serde(default = "Option::default")]
+    pub fine: Option<String>,
+
+    /// Divisions into the measure for segno, dalsegno, coda and tocoda.
+    #[serde(default = "Option::default")]
+    pub divisions: Option<f32>,
+
+    #[serde(rename = "forward-repeat", default = "Option::default")]
+    pub forward_repeat: Option<YesNo>,
+
+    /// Passes of a repeat the sound applies to, like "1,3".
+    #[serde(rename = "time-only", default = "Option::default")]
+    pub time_only: Option<String>,
+
+    #[serde(default = "Option::default")]
+    pub pizzicato: Option<YesNo>,
+
+    #[serde(default = "Option::default")]
+    pub pan: Option<f32>,
+
+    #[serde(default = "Option::default")]
+    pub elevation: Option<f32>,
+
+    #[serde(rename = "damper-pedal", default = "Option::default")]
+    pub damper_pedal: Option<String>,
+
+    #[serde(rename = "soft-pedal", default = "Option::default")]
+    pub soft_pedal: Option<String>,
+
+    #[serde(rename = "sostenuto-pedal", default = "Option::default")]
+    pub sostenuto_pedal: Option<String>,
+
+    #[serde(default = "Option::default")]
+    pub offset: Option<Offset>,
+}
+
+impl WriteXml for Sound {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .opt("tempo", &self.tempo)
+            .opt("dynamics", &self.dynamics)
+            .opt("dacapo", &self.dacapo)
+            .opt("segno", &self.segno)
+            .opt("dalsegno", &self.dalsegno)
+            .opt("coda", &self.coda)
+            .opt("tocoda", &self.tocoda)
+            .opt("divisions", &self.divisions)
+            .opt("forward-repeat", &self.forward_repeat)
+            .opt("fine", &self.fine)
+            .opt("time-only", &self.time_only)
+            .opt("pizzicato", &self.pizzicato)
+            .opt("pan", &self.pan)
+            .opt("elevation", &self.elevation)
+            .opt("damper-pedal", &self.damper_pedal)
+            .opt("soft-pedal", &self.soft_pedal)
+            .opt("sostenuto-pedal", &self.sostenuto_pedal);
+        match &self.offset {
+            Some(offset) => {
+                w.start(name, attrs);
+                w.element("offset", offset);
+                w.end(name);
+            }
+            None => w.empty(name, attrs),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sound;
+    use crate::musicxml::yes_no::YesNo;
+    use serde_xml_rs::from_str;
+
+    #[test]
+    fn sound() {
+        let xml = r#"<sound tempo="84" dynamics="54.44"/>"#;
+        let item: Sound = from_str(xml).unwrap();
+
+        assert_eq!(item.tempo, Some(84.0));
+        assert_eq!(item.dynamics, Some(54.44));
+    }
+
+    #[test]
+    fn jumps() {
+        let xml = r#"<sound dacapo="yes" tocoda="coda" time-only="2"><offset>2</offset></sound>"#;
+        let item: Sound = from_str(xml).unwrap();
+
+        assert_eq!(item.dacapo, Some(YesNo::Yes));
+        assert_eq!(item.tocoda.as_deref(), Some("coda"));
+        assert_eq!(item.time_only.as_deref(), Some("2"));
+        assert_eq!(item.offset.map(|o| o.content), Some(2.0));
+    }
+}
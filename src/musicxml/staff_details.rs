@@ -0,0 +1,188 @@
+use serde::{de::IgnoredAny, Deserialize, Serialize};
+
+use super::{
+    staff_tuning::StaffTuning,
+    writer::{Attrs, WriteXml, XmlWriter},
+    yes_no::YesNo,
+};
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/staff-details/
+/// The kind of staff, its number of lines and, for tablature, the tuning of its
+/// strings. Later `<staff-details>` only change the elements they contain.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Clone)]
+#[serde(from = "StaffDetailsXml")]
+pub struct StaffDetails {
+    /// The staff this applies to, or 0 for the first staff.
+    pub number: u8,
+
+    /// numbers or letters for the frets of a tablature staff.
+    pub show_frets: Option<String>,
+    pub print_object: Option<YesNo>,
+
+    /// ossia, editorial, cue, alternate or regular.
+    pub staff_type: Option<String>,
+    pub staff_lines: Option<u8>,
+
+    /// The open strings from the lowest line up.
+    pub staff_tunings: Vec<StaffTuning>,
+    pub capo: Option<u8>,
+}
+
+#[derive(Deserialize)]
+enum StaffDetailsChild {
+    #[serde(rename = "staff-type")]
+    StaffType(String),
+    #[serde(rename = "staff-lines")]
+    StaffLines(u8),
+    #[serde(rename = "line-detail")]
+    LineDetail(IgnoredAny),
+    #[serde(rename = "staff-tuning")]
+    StaffTuning(StaffTuning),
+    #[serde(rename = "capo")]
+    Capo(u8),
+    #[serde(rename = "staff-size")]
+    StaffSize(IgnoredAny),
+}
+
+#[derive(Deserialize)]
+struct StaffDetailsXml {
+    #[serde(default = "u8::default")]
+    number: u8,
+
+    #[serde(rename = "show-frets", default = "Option::default")]
+    show_frets: Option<String>,
+
+    #[serde(rename = "print-object", default = "Option::default")]
+    print_object: Option<YesNo>,
+
+    #[serde(rename = "$value", default = "Vec::default")]
+    children: Vec<StaffDetailsChild>,
+}
+
+impl From<StaffDetailsXml> for StaffDetails {
+    fn from(xml: StaffDetailsXml) -> Self {
+        let mut item = StaffDetails {
+            number: xml.number,
+            show_frets: xml.show_frets,
+            print_object: xml.print_object,
+            ..StaffDetails::default()
+        };
+        for child in xml.children {
+            match child {
+                StaffDetailsChild::StaffType(t) => item.staff_type = Some(t),
+                StaffDetailsChild::StaffLines(l) => item.staff_lines = Some(l),
+                StaffDetailsChild::StaffTuning(t) => item.staff_tunings.push(t),
+                StaffDetailsChild::Capo(c) => item.capo = Some(c),
+                _ => {}
+            }
+        }
+        item
+    }
+}
+
+impl StaffDetails {
+    /// A tablature staff with a line for each string of `tuning`.
+    pub fn tablature(number: u8, tuning: &[StaffTuning]) -> StaffDetails {
+        StaffDetails {
+            number,
+            staff_lines: Some(tuning.len() as u8),
+            staff_tunings: tuning.to_vec(),
+            ..StaffDetails::default()
+        }
+    }
+
+    /// Takes over what `newer` sets and keeps the rest.
+    pub fn update(&mut self, newer: &StaffDetails) {
+        if newer.show_frets.is_some() {
+            self.show_frets.clone_from(&newer.show_frets);
+        }
+        if newer.print_object.is_some() {
+            self.print_object.clone_from(&newer.print_object);
+        }
+        if newer.staff_type.is_some() {
+            self.staff_type.clone_from(&newer.staff_type);
+        }
+        if newer.staff_lines.is_some() {
+            self.staff_lines = newer.staff_lines;
+        }
+        if !newer.staff_tunings.is_empty() {
+            self.staff_tunings.clone_from(&newer.staff_tunings);
+        }
+        if newer.capo.is_some() {
+            self.capo = newer.capo;
+        }
+    }
+
+    /// The open string of tablature string `string`, which counts from 1 for the
+    /// highest like `<string>` does.
+    pub fn string_tuning(&self, string: u8) -> Option<&StaffTuning> {
+        let lines = self.staff_tunings.len() as u8;
+        let line = lines.checked_sub(string)? + 1;
+        self.staff_tunings
+            .iter()
+            .find(|t| t.line == line)
+            .or_else(|| self.staff_tunings.get(line as usize - 1))
+    }
+}
+
+impl WriteXml for StaffDetails {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .add_if(self.number != 0, "number", &self.number)
+            .opt("show-frets", &self.show_frets)
+            .opt("print-object", &self.print_object);
+        w.start(name, attrs);
+        w.opt_text_element("staff-type", &self.staff_type);
+        w.opt_text_element("staff-lines", &self.staff_lines);
+        w.elements("staff-tuning", &self.staff_tunings);
+        w.opt_text_element("capo", &self.capo);
+        w.end(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StaffDetails;
+    use crate::musicxml::harmony::Step;
+    use serde_xml_rs::from_str;
+
+    #[test]
+    fn staff_details() {
+        let xml = r#"
+            <staff-details number="2" show-frets="letters">
+                <staff-lines>4</staff-lines>
+                <staff-tuning line="1">
+                    <tuning-step>E</tuning-step>
+                    <tuning-octave>1</tuning-octave>
+                </staff-tuning>
+                <staff-tuning line="2">
+                    <tuning-step>A</tuning-step>
+                    <tuning-octave>1</tuning-octave>
+                </staff-tuning>
+                <staff-tuning line="3">
+                    <tuning-step>D</tuning-step>
+                    <tuning-octave>2</tuning-octave>
+                </staff-tuning>
+                <staff-tuning line="4">
+                    <tuning-step>G</tuning-step>
+                    <tuning-octave>2</tuning-octave>
+                </staff-tuning>
+                <capo>3</capo>
+            </staff-details>"#;
+        let mut item: StaffDetails = from_str(xml).unwrap();
+
+        assert_eq!(item.number, 2);
+        assert_eq!(item.show_frets, Some("letters".to_string()));
+        assert_eq!(item.staff_lines, Some(4));
+        assert_eq!(item.capo, Some(3));
+        assert_eq!(item.string_tuning(1).unwrap().step, Step::G);
+        assert_eq!(item.string_tuning(4).unwrap().step, Step::E);
+        assert!(item.string_tuning(5).is_none());
+
+        let lines: StaffDetails =
+            from_str("<staff-details><staff-lines>5</staff-lines></staff-details>").unwrap();
+        item.update(&lines);
+        assert_eq!(item.staff_lines, Some(5));
+        assert_eq!(item.staff_tunings.len(), 4);
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use super::writer::{Attrs, WriteXml, XmlWriter};
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct StaffLayout {
     #[serde(rename = "staff-distance", default = "Option::default")]
@@ -8,3 +10,11 @@ pub struct StaffLayout {
     #[serde(default = "Option::default")]
     pub number: Option<u8>,
 }
+
+impl WriteXml for StaffLayout {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new().opt("number", &self.number));
+        w.opt_text_element("staff-distance", &self.staff_distance);
+        w.end(name);
+    }
+}
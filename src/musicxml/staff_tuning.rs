@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    harmony::{Pitch, Step},
+    writer::{Attrs, WriteXml, XmlWriter},
+};
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/staff-tuning/
+/// The open pitch of one string of a tablature staff.
+#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd, Default, Clone)]
+pub struct StaffTuning {
+    /// The staff line of the string, counted from 1 for the lowest.
+    #[serde(default = "u8::default")]
+    pub line: u8,
+
+    #[serde(rename = "tuning-step")]
+    pub step: Step,
+
+    #[serde(rename = "tuning-alter", default = "Option::default")]
+    pub alter: Option<f32>,
+
+    #[serde(rename = "tuning-octave")]
+    pub octave: u8,
+}
+
+impl StaffTuning {
+    pub fn new(line: u8, step: Step, octave: u8) -> StaffTuning {
+        StaffTuning {
+            line,
+            step,
+            alter: None,
+            octave,
+        }
+    }
+
+    /// E2 A2 D3 G3 B3 E4, from the lowest string.
+    pub fn standard_guitar() -> Vec<StaffTuning> {
+        [
+            (Step::E, 2),
+            (Step::A, 2),
+            (Step::D, 3),
+            (Step::G, 3),
+            (Step::B, 3),
+            (Step::E, 4),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, (step, octave))| StaffTuning::new(i as u8 + 1, step, octave))
+        .collect()
+    }
+
+    /// The pitch of the open string.
+    pub fn pitch(&self) -> Pitch {
+        Pitch {
+            step: self.step.clone(),
+            alter: self.alter,
+            octave: self.octave,
+        }
+    }
+}
+
+impl WriteXml for StaffTuning {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new().add_if(self.line > 0, "line", &self.line));
+        w.text_element("tuning-step", &self.step);
+        w.opt_text_element("tuning-alter", &self.alter);
+        w.text_element("tuning-octave", &self.octave);
+        w.end(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StaffTuning;
+    use crate::musicxml::harmony::Step;
+    use serde_xml_rs::from_str;
+
+    #[test]
+    fn staff_tuning() {
+        let xml = r#"
+            <staff-tuning line="3">
+                <tuning-step>F</tuning-step>
+                <tuning-alter>1</tuning-alter>
+                <tuning-octave>3</tuning-octave>
+            </staff-tuning>"#;
+        let item: StaffTuning = from_str(xml).unwrap();
+
+        assert_eq!(item.line, 3);
+        assert_eq!(item.step, Step::F);
+        assert_eq!(item.pitch().midi_number(), 54.0);
+
+        let standard = StaffTuning::standard_guitar();
+        assert_eq!(standard[0].pitch().midi_number(), 40.0);
+        assert_eq!(standard[5].line, 6);
+    }
+}
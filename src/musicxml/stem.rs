@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::core::DirectionUD;
+use super::writer::{Attrs, WriteXml, XmlWriter};
 
 #[derive(Debug, PartialEq, Serialize, Deserialize, Default)]
 pub struct Stem {
@@ -22,3 +23,15 @@ pub struct Stem {
     #[serde(rename = "relative-y", default = "Option::default")]
     pub relative_y: Option<String>,
 }
+
+impl WriteXml for Stem {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .opt("color", &self.color)
+            .opt("default-x", &self.default_x)
+            .opt("default-y", &self.default_y)
+            .opt("relative-x", &self.relative_x)
+            .opt("relative-y", &self.relative_y);
+        w.text_element_with(name, attrs, &self.content);
+    }
+}
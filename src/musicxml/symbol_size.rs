@@ -1,7 +1,7 @@
 use super::yes_no::YesNo;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, Default, PartialEq, PartialOrd)]
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, PartialOrd, Clone)]
 pub enum SymbolSize {
     #[serde(rename = "cue")]
     #[default]
@@ -1,5 +1,6 @@
 use super::{
     printable_value::{FontStyle, FontWeight, LeftCenterRight, Valign},
+    writer::{Attrs, WriteXml, XmlWriter},
     yes_no::YesNo,
 };
 use serde::{Deserialize, Serialize};
@@ -43,3 +44,22 @@ pub struct SystemDivider {
     #[serde(rename = "valign", default = "Option::default")]
     pub valign: Option<Valign>,
 }
+
+impl WriteXml for SystemDivider {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .opt("color", &self.color)
+            .opt("default-x", &self.default_x)
+            .opt("default-y", &self.default_y)
+            .opt("font-family", &self.font_family)
+            .opt("font-size", &self.font_size)
+            .opt("font-style", &self.font_style)
+            .opt("font-weight", &self.font_weight)
+            .opt("halign", &self.halign)
+            .opt("print-object", &self.print_object)
+            .opt("relative-x", &self.relative_x)
+            .opt("relative-y", &self.relative_y)
+            .opt("valign", &self.valign);
+        w.empty(name, attrs);
+    }
+}
@@ -1,7 +1,14 @@
 use super::{
-    attributes::Attributes, barline::Barline, core::Duration, direction::Direction,
-    harmony::Harmony, left_right_middle::LeftRightMiddle, level::Level, note::Note,
+    attributes::Attributes,
+    barline::Barline,
+    core::Duration,
+    direction::Direction,
+    harmony::Harmony,
+    left_right_middle::LeftRightMiddle,
+    level::Level,
+    note::Note,
     system_divider::SystemDivider,
+    writer::{Attrs, WriteXml, XmlWriter},
 };
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -39,6 +46,35 @@ pub struct SystemLayout {
     pub system_dividers: Option<SystemDividers>,
 }
 
+impl WriteXml for SystemMargins {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.text_element("left-margin", &self.left_margin);
+        w.text_element("right-margin", &self.right_margin);
+        w.end(name);
+    }
+}
+
+impl WriteXml for SystemDividers {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.element("left-divider", &self.left_divider);
+        w.element("right-divider", &self.right_divider);
+        w.end(name);
+    }
+}
+
+impl WriteXml for SystemLayout {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.opt_element("system-margins", &self.system_margins);
+        w.opt_text_element("system-distance", &self.system_distance);
+        w.opt_text_element("top-system-distance", &self.top_system_distance);
+        w.opt_element("system-dividers", &self.system_dividers);
+        w.end(name);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::SystemLayout;
@@ -0,0 +1,470 @@
+use super::{
+    attribute_cursor::AttributeState,
+    attributes::{Clef, ClefSign},
+    harmony::Pitch,
+    measure::MeasureContent,
+    note::{NotationType, Notations, Note},
+    part::Part,
+    staff_details::StaffDetails,
+    staff_tuning::StaffTuning,
+    technical::{Technical, TechnicalMeta, TechnicalType},
+    theory::PitchName,
+};
+use crate::error::MusicXmlError;
+
+/// Highest fret notes are put on.
+const MAX_FRET: u8 = 24;
+
+/// Widest stretch between the fretted notes of a chord, in frets.
+const HAND_SPAN: u8 = 4;
+
+/// Cost of each fret up the neck, so lower positions win when all else is equal.
+const HEIGHT: f32 = 0.1;
+
+/// Cheapest fingerings of each chord kept for the search.
+const CANDIDATES: usize = 16;
+
+/// A string and fret for each note of a chord.
+struct Fingering {
+    /// String, counted from 1 for the highest, and fret of each note.
+    places: Vec<(u8, u8)>,
+
+    /// The stretch and height of the fingering on its own.
+    cost: f32,
+
+    /// The lowest fretted fret, where the hand is. None when only open strings sound.
+    position: Option<u8>,
+}
+
+impl Fingering {
+    /// Frets the hand moves to play `next` after this one. Frets within its span are
+    /// reached without moving.
+    fn shift(&self, next: &Fingering) -> f32 {
+        match (self.position, next.position) {
+            (Some(from), Some(to)) => from.abs_diff(to).saturating_sub(HAND_SPAN - 1) as f32,
+            _ => 0.0,
+        }
+    }
+}
+
+/// The fingerings of `pitches` on strings tuned to the MIDI note numbers `open`, from
+/// the lowest string up, cheapest first.
+fn fingerings(pitches: &[f32], open: &[f32]) -> Vec<Fingering> {
+    let mut found = vec![];
+    search(pitches, open, &mut vec![], &mut found);
+    found.sort_by(|a, b| a.cost.total_cmp(&b.cost));
+    found.truncate(CANDIDATES);
+    found
+}
+
+/// Tries every free string for the next note of `pitches`, `places` holding the
+/// string index and fret of the notes before it.
+fn search(
+    pitches: &[f32],
+    open: &[f32],
+    places: &mut Vec<(usize, u8)>,
+    found: &mut Vec<Fingering>,
+) {
+    let Some(pitch) = pitches.get(places.len()) else {
+        let fretted = places.iter().map(|(_, fret)| *fret).filter(|f| *f > 0);
+        let (low, high) = (fretted.clone().min(), fretted.clone().max());
+        let span = high.zip(low).map_or(0, |(high, low)| high - low);
+        if span > HAND_SPAN {
+            return;
+        }
+        let height: f32 = fretted.map(|f| f as f32).sum();
+        found.push(Fingering {
+            places: places
+                .iter()
+                .map(|(string, fret)| ((open.len() - string) as u8, *fret))
+                .collect(),
+            cost: span as f32 + HEIGHT * height,
+            position: low,
+        });
+        return;
+    };
+
+    for (string, tuning) in open.iter().enumerate() {
+        let fret = pitch - tuning;
+        let fits = (0.0..=MAX_FRET as f32).contains(&fret) && fret.fract() == 0.0;
+        if !fits || places.iter().any(|(s, _)| *s == string) {
+            continue;
+        }
+        places.push((string, fret as u8));
+        search(pitches, open, places, found);
+        places.pop();
+    }
+}
+
+/// The fingering to take of each chord, cheapest over the whole passage with the
+/// hand shifts between chords counted in.
+fn plan(chords: &[Vec<Fingering>]) -> Vec<usize> {
+    let Some(first) = chords.first() else {
+        return vec![];
+    };
+    let mut costs: Vec<f32> = first.iter().map(|f| f.cost).collect();
+    let mut previous: Vec<Vec<usize>> = vec![];
+
+    for pair in chords.windows(2) {
+        let (before, after) = (&pair[0], &pair[1]);
+        let (next_costs, from): (Vec<f32>, Vec<usize>) = after
+            .iter()
+            .map(|next| {
+                let (best, cost) = before
+                    .iter()
+                    .enumerate()
+                    .map(|(i, f)| (i, costs[i] + f.shift(next)))
+                    .min_by(|a, b| a.1.total_cmp(&b.1))
+                    .expect("every chord has a fingering");
+                (cost + next.cost, best)
+            })
+            .unzip();
+        costs = next_costs;
+        previous.push(from);
+    }
+
+    let mut choice = (0..costs.len())
+        .min_by(|a, b| costs[*a].total_cmp(&costs[*b]))
+        .expect("every chord has a fingering");
+    let mut choices = vec![choice];
+    for from in previous.iter().rev() {
+        choice = from[choice];
+        choices.push(choice);
+    }
+    choices.reverse();
+    choices
+}
+
+/// Replaces the `<string>` and `<fret>` of a note.
+fn set_place(note: &mut Note, string: u8, fret: u8) {
+    let notations = note.notations.get_or_insert_with(Notations::default);
+    let index = match notations
+        .notations
+        .iter()
+        .position(|n| matches!(n, NotationType::Technical(_)))
+    {
+        Some(index) => index,
+        None => {
+            notations
+                .notations
+                .push(NotationType::Technical(Technical::default()));
+            notations.notations.len() - 1
+        }
+    };
+    if let NotationType::Technical(technical) = &mut notations.notations[index] {
+        let text = |value: u8| TechnicalMeta {
+            content: Some(value.to_string()),
+            ..TechnicalMeta::default()
+        };
+        technical
+            .technical
+            .retain(|t| !matches!(t, TechnicalType::String(_) | TechnicalType::Fret(_)));
+        technical
+            .technical
+            .push(TechnicalType::String(text(string)));
+        technical.technical.push(TechnicalType::Fret(text(fret)));
+    }
+}
+
+/// The string and fret of a note, if it has both.
+fn place(note: &Note) -> Option<(u8, u8)> {
+    note.notations
+        .iter()
+        .flat_map(|n| &n.notations)
+        .find_map(|n| match n {
+            NotationType::Technical(t) => Some((t.string()?, t.fret()?)),
+            _ => None,
+        })
+}
+
+/// Semitones from the written to the sounding pitch of a staff.
+fn transposition(state: &AttributeState, staff: u8) -> f32 {
+    state
+        .transpose(staff)
+        .map_or(0.0, |t| t.interval().chromatic as f32)
+}
+
+impl Part {
+    /// Puts every pitched note on a string and fret of `tuning`, given from the lowest
+    /// string up, turns the clefs into TAB clefs and adds the tuning to the first
+    /// staff details. Each chord, or single note, gets a fingering within a span of
+    /// four frets, and the hand stays as low and moves as little as it can over the
+    /// whole part. Fails without changing the part when a note is out of range.
+    pub fn to_tablature(&mut self, tuning: &[StaffTuning]) -> Result<(), MusicXmlError> {
+        let open: Vec<f32> = tuning.iter().map(|t| t.pitch().midi_number()).collect();
+        let mut state = AttributeState::default();
+        let mut chords: Vec<Vec<(usize, usize)>> = vec![];
+        let mut options = vec![];
+        let mut pitches = vec![];
+
+        for (m, measure) in self.measures.iter().enumerate() {
+            for (c, content) in measure.content.iter().enumerate() {
+                match content {
+                    MeasureContent::Attributes(attributes) => state.apply(attributes),
+                    MeasureContent::Note(note) => {
+                        let Some(pitch) = note.pitch.as_ref().filter(|_| !note.rest) else {
+                            continue;
+                        };
+                        if !note.chord || pitches.is_empty() {
+                            if !pitches.is_empty() {
+                                options.push(fingerings(&pitches, &open));
+                            }
+                            chords.push(vec![]);
+                            pitches.clear();
+                        }
+                        chords.last_mut().unwrap().push((m, c));
+                        pitches
+                            .push(pitch.midi_number() + transposition(&state, note.staff.max(1)));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if !pitches.is_empty() {
+            options.push(fingerings(&pitches, &open));
+        }
+        if let Some(i) = options.iter().position(|o| o.is_empty()) {
+            let names: Vec<String> = chords[i]
+                .iter()
+                .filter_map(|(m, c)| match &self.measures[*m].content[*c] {
+                    MeasureContent::Note(note) => note.pitch.as_ref(),
+                    _ => None,
+                })
+                .map(|p| format!("{}{}", PitchName::from(p), p.octave))
+                .collect();
+            return Err(MusicXmlError::Generic(format!(
+                "{} in measure {} cannot be played in this tuning",
+                names.join(" "),
+                self.measures[chords[i][0].0].number
+            )));
+        }
+
+        for ((chord, fingerings), choice) in chords.iter().zip(&options).zip(plan(&options)) {
+            for ((m, c), (string, fret)) in chord.iter().zip(&fingerings[choice].places) {
+                if let MeasureContent::Note(note) = &mut self.measures[*m].content[*c] {
+                    set_place(note, *string, *fret);
+                }
+            }
+        }
+
+        let mut first = true;
+        let attributes = self
+            .measures
+            .iter_mut()
+            .flat_map(|m| &mut m.content)
+            .filter_map(|c| match c {
+                MeasureContent::Attributes(a) => Some(a),
+                _ => None,
+            });
+        for attributes in attributes {
+            for clef in &mut attributes.clefs {
+                *clef = Clef {
+                    sign: ClefSign::Tab,
+                    line: 5,
+                    octave_change: 0,
+                    number: clef.number,
+                };
+            }
+            if first {
+                let details = StaffDetails::tablature(0, tuning);
+                match attributes.staff_details.iter_mut().find(|d| d.number <= 1) {
+                    Some(existing) => existing.update(&details),
+                    None => attributes.staff_details.push(details),
+                }
+                first = false;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets the pitch of every note with a `<string>` and `<fret>` from the tuning and
+    /// capo of its staff, spelled in the key in effect, and turns TAB clefs into
+    /// treble clefs an octave down. Staves without a tuning are taken to be a guitar in
+    /// standard tuning. The strings and frets are kept.
+    pub fn to_standard_notation(&mut self) -> Result<(), MusicXmlError> {
+        let guitar = StaffDetails::tablature(0, &StaffTuning::standard_guitar());
+        let mut state = AttributeState::default();
+
+        for measure in &mut self.measures {
+            for content in &mut measure.content {
+                match content {
+                    MeasureContent::Attributes(attributes) => {
+                        state.apply(attributes);
+                        for clef in &mut attributes.clefs {
+                            if clef.sign == ClefSign::Tab {
+                                clef.sign = ClefSign::G;
+                                clef.line = 2;
+                                clef.octave_change = -1;
+                            }
+                        }
+                        for details in &mut attributes.staff_details {
+                            details.staff_lines = None;
+                        }
+                    }
+                    MeasureContent::Note(note) => {
+                        let Some((string, fret)) = place(note).filter(|_| !note.rest) else {
+                            continue;
+                        };
+                        let staff = note.staff.max(1);
+                        let details = state
+                            .staff_details(staff)
+                            .filter(|d| !d.staff_tunings.is_empty())
+                            .unwrap_or(&guitar);
+                        let Some(tuning) = details.string_tuning(string) else {
+                            return Err(MusicXmlError::Generic(format!(
+                                "measure {} has a note on string {string} of {}",
+                                measure.number,
+                                details.staff_tunings.len()
+                            )));
+                        };
+                        let sounding = tuning.pitch().midi_number()
+                            + details.capo.unwrap_or(0) as f32
+                            + fret as f32;
+                        let written = (sounding - transposition(&state, staff)).round();
+                        let fifths = state.key(staff).map_or(0, |k| k.fifths);
+                        note.pitch = Some(Pitch::from_midi_number(written as u8, fifths));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::place;
+    use crate::musicxml::attributes::ClefSign;
+    use crate::musicxml::measure::MeasureContent;
+    use crate::musicxml::note::Note;
+    use crate::musicxml::part::Part;
+    use crate::musicxml::score_partwise::ScorePartwise;
+    use crate::musicxml::staff_tuning::StaffTuning;
+    use crate::musicxml::timeline::Rational;
+    use serde_xml_rs::from_str;
+    use std::fs;
+
+    fn read(file: &str) -> ScorePartwise {
+        let xml = fs::read_to_string(format!("resources/xml-test-files/{file}")).unwrap();
+        from_str(&xml).unwrap()
+    }
+
+    fn notes(part: &Part) -> impl Iterator<Item = &Note> {
+        part.measures
+            .iter()
+            .flat_map(|m| &m.content)
+            .filter_map(|c| match c {
+                MeasureContent::Note(n) if !n.rest => Some(n),
+                _ => None,
+            })
+    }
+
+    fn midi_numbers(part: &Part) -> Vec<f32> {
+        notes(part)
+            .filter_map(|n| n.pitch.as_ref())
+            .map(|p| p.midi_number())
+            .collect()
+    }
+
+    #[test]
+    fn standard_notation() {
+        for (file, part) in [
+            ("71e-TabStaves.xml", 1),
+            ("71e-TabStaves.xml", 7),
+            ("my_bonnie.xml", 1),
+        ] {
+            let expected = midi_numbers(&read(file).parts[part]);
+            let mut converted = read(file).parts.remove(part);
+            for measure in &mut converted.measures {
+                for content in &mut measure.content {
+                    if let MeasureContent::Note(note) = content {
+                        note.pitch = None;
+                    }
+                }
+            }
+            converted.to_standard_notation().unwrap();
+            assert_eq!(midi_numbers(&converted), expected);
+        }
+
+        let mut bass = read("my_bonnie.xml").parts.remove(1);
+        let mut cursor = bass.attribute_cursor();
+        assert_eq!(
+            cursor.at(0, Rational::default()).tuning(1).unwrap().len(),
+            4
+        );
+
+        bass.to_standard_notation().unwrap();
+        let mut cursor = bass.attribute_cursor();
+        let clef = cursor.at(0, Rational::default()).clef(1).unwrap();
+        assert_eq!(clef.sign, ClefSign::G);
+        assert_eq!(clef.octave_change, -1);
+    }
+
+    fn tablature(xml: &str) -> Vec<(u8, u8)> {
+        let mut part: Part = from_str(xml).unwrap();
+        part.to_tablature(&StaffTuning::standard_guitar()).unwrap();
+        notes(&part).filter_map(place).collect()
+    }
+
+    #[test]
+    fn fingering() {
+        let note = |step: &str, octave: u8, chord: bool| {
+            let chord = if chord { "<chord/>" } else { "" };
+            format!(
+                "<note>{chord}<pitch><step>{step}</step><octave>{octave}</octave></pitch><duration>1</duration></note>"
+            )
+        };
+        let part = |notes: Vec<String>| {
+            format!(
+                r#"<part id="P1"><measure number="1"><attributes><clef><sign>G</sign><line>2</line></clef></attributes>{}</measure></part>"#,
+                notes.concat()
+            )
+        };
+
+        let scale = ["C", "D", "E", "F", "G"]
+            .iter()
+            .map(|s| note(s, 4, false))
+            .collect();
+        assert_eq!(
+            tablature(&part(scale)),
+            vec![(2, 1), (2, 3), (1, 0), (1, 1), (1, 3)]
+        );
+
+        let chord = [("C", 3), ("E", 3), ("G", 3), ("C", 4), ("E", 4)]
+            .iter()
+            .enumerate()
+            .map(|(i, (s, o))| note(s, *o, i > 0))
+            .collect();
+        assert_eq!(
+            tablature(&part(chord)),
+            vec![(5, 3), (4, 2), (3, 0), (2, 1), (1, 0)]
+        );
+
+        let high = [("A", 4), ("C", 5), ("E", 5)]
+            .iter()
+            .map(|(s, o)| note(s, *o, false))
+            .collect();
+        assert_eq!(tablature(&part(high)), vec![(1, 5), (1, 8), (1, 12)]);
+
+        let mut low: Part = from_str(&part(vec![note("D", 2, false)])).unwrap();
+        assert!(low.to_tablature(&StaffTuning::standard_guitar()).is_err());
+        assert!(notes(&low).all(|n| place(n).is_none()));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut part = read("71e-TabStaves.xml").parts.remove(0);
+        let expected = midi_numbers(&part);
+        let tuning = StaffTuning::standard_guitar();
+        part.to_tablature(&tuning).unwrap();
+
+        let mut cursor = part.attribute_cursor();
+        let state = cursor.at(0, Rational::default());
+        assert_eq!(state.clef(1).unwrap().sign, ClefSign::Tab);
+        assert_eq!(state.tuning(1), Some(tuning.as_slice()));
+
+        part.to_standard_notation().unwrap();
+        assert_eq!(midi_numbers(&part), expected);
+    }
+}
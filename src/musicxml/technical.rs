@@ -0,0 +1,656 @@
+use crate::musicxml::core::Placement;
+use crate::musicxml::writer::{Attrs, WriteXml, XmlWriter};
+use crate::prelude::*;
+use serde::{de::IgnoredAny, Deserialize, Serialize};
+use std::str::FromStr;
+use strum_macros::EnumString;
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/technical/
+/// Attributes shared by the simple technical elements. `content` holds the text of
+/// `<fingering>`, `<fret>`, `<string>`, `<pluck>`, `<hammer-on>`, `<pull-off>`,
+/// `<tap>` and the like.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Default)]
+pub struct TechnicalMeta {
+    #[serde(rename = "$value", default = "Option::default")]
+    pub content: Option<String>,
+
+    /// start or stop for `<hammer-on>` and `<pull-off>`.
+    #[serde(default = "Option::default")]
+    pub r#type: Option<String>,
+
+    #[serde(default = "Option::default")]
+    pub number: Option<u8>,
+
+    #[serde(default = "Option::default")]
+    pub placement: Option<Placement>,
+
+    #[serde(default = "Option::default")]
+    pub substitution: Option<String>,
+
+    #[serde(default = "Option::default")]
+    pub alternate: Option<String>,
+
+    /// left or right for `<tap>`.
+    #[serde(default = "Option::default")]
+    pub hand: Option<String>,
+
+    #[serde(default = "Option::default")]
+    pub color: Option<String>,
+
+    #[serde(default = "Option::default", rename = "default-x")]
+    pub default_x: Option<f32>,
+
+    #[serde(default = "Option::default", rename = "default-y")]
+    pub default_y: Option<f32>,
+
+    #[serde(default = "Option::default", rename = "relative-x")]
+    pub relative_x: Option<f32>,
+
+    #[serde(default = "Option::default", rename = "relative-y")]
+    pub relative_y: Option<f32>,
+
+    #[serde(default = "Option::default")]
+    pub smufl: Option<String>,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/harmonic/
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Default)]
+#[serde(from = "HarmonicXml")]
+pub struct Harmonic {
+    pub natural: bool,
+    pub artificial: bool,
+    pub base_pitch: bool,
+    pub touching_pitch: bool,
+    pub sounding_pitch: bool,
+    pub print_object: Option<String>,
+    pub placement: Option<Placement>,
+}
+
+#[derive(Deserialize)]
+enum HarmonicChild {
+    #[serde(rename = "natural")]
+    Natural(IgnoredAny),
+    #[serde(rename = "artificial")]
+    Artificial(IgnoredAny),
+    #[serde(rename = "base-pitch")]
+    BasePitch(IgnoredAny),
+    #[serde(rename = "touching-pitch")]
+    TouchingPitch(IgnoredAny),
+    #[serde(rename = "sounding-pitch")]
+    SoundingPitch(IgnoredAny),
+}
+
+#[derive(Deserialize)]
+struct HarmonicXml {
+    #[serde(rename = "print-object", default = "Option::default")]
+    print_object: Option<String>,
+
+    #[serde(default = "Option::default")]
+    placement: Option<Placement>,
+
+    #[serde(rename = "$value", default = "Vec::default")]
+    children: Vec<HarmonicChild>,
+}
+
+impl From<HarmonicXml> for Harmonic {
+    fn from(xml: HarmonicXml) -> Self {
+        let mut item = Harmonic {
+            print_object: xml.print_object,
+            placement: xml.placement,
+            ..Harmonic::default()
+        };
+        for child in xml.children {
+            match child {
+                HarmonicChild::Natural(_) => item.natural = true,
+                HarmonicChild::Artificial(_) => item.artificial = true,
+                HarmonicChild::BasePitch(_) => item.base_pitch = true,
+                HarmonicChild::TouchingPitch(_) => item.touching_pitch = true,
+                HarmonicChild::SoundingPitch(_) => item.sounding_pitch = true,
+            }
+        }
+        item
+    }
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/bend/
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Default)]
+#[serde(from = "BendXml")]
+pub struct Bend {
+    /// Semitones of the bend, negative for a bend down.
+    pub alter: f32,
+    pub pre_bend: bool,
+    pub release: bool,
+    pub with_bar: Option<String>,
+    pub shape: Option<String>,
+    pub accelerate: Option<String>,
+    pub beats: Option<f32>,
+    pub first_beat: Option<f32>,
+    pub last_beat: Option<f32>,
+}
+
+#[derive(Deserialize)]
+enum BendChild {
+    #[serde(rename = "bend-alter")]
+    BendAlter(f32),
+    #[serde(rename = "pre-bend")]
+    PreBend(IgnoredAny),
+    #[serde(rename = "release")]
+    Release(IgnoredAny),
+    #[serde(rename = "with-bar")]
+    WithBar(String),
+}
+
+#[derive(Deserialize)]
+struct BendXml {
+    #[serde(default = "Option::default")]
+    shape: Option<String>,
+
+    #[serde(default = "Option::default")]
+    accelerate: Option<String>,
+
+    #[serde(default = "Option::default")]
+    beats: Option<f32>,
+
+    #[serde(rename = "first-beat", default = "Option::default")]
+    first_beat: Option<f32>,
+
+    #[serde(rename = "last-beat", default = "Option::default")]
+    last_beat: Option<f32>,
+
+    #[serde(rename = "$value", default = "Vec::default")]
+    children: Vec<BendChild>,
+}
+
+impl From<BendXml> for Bend {
+    fn from(xml: BendXml) -> Self {
+        let mut item = Bend {
+            shape: xml.shape,
+            accelerate: xml.accelerate,
+            beats: xml.beats,
+            first_beat: xml.first_beat,
+            last_beat: xml.last_beat,
+            ..Bend::default()
+        };
+        for child in xml.children {
+            match child {
+                BendChild::BendAlter(alter) => item.alter = alter,
+                BendChild::PreBend(_) => item.pre_bend = true,
+                BendChild::Release(_) => item.release = true,
+                BendChild::WithBar(bar) => item.with_bar = Some(bar),
+            }
+        }
+        item
+    }
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/hole-closed/
+/// yes, no or half, for `<hole-closed>` and `<harmon-closed>`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Default)]
+pub struct Closed {
+    #[serde(rename = "$value", default = "String::default")]
+    pub content: String,
+
+    #[serde(default = "Option::default")]
+    pub location: Option<String>,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/hole/
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Default)]
+pub struct Hole {
+    #[serde(rename = "hole-type", default = "Option::default")]
+    pub hole_type: Option<String>,
+
+    #[serde(rename = "hole-closed", default = "Closed::default")]
+    pub hole_closed: Closed,
+
+    #[serde(rename = "hole-shape", default = "Option::default")]
+    pub hole_shape: Option<String>,
+
+    #[serde(default = "Option::default")]
+    pub placement: Option<Placement>,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/arrow/
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Default)]
+#[serde(from = "ArrowXml")]
+pub struct Arrow {
+    pub direction: Option<String>,
+    pub style: Option<String>,
+    pub arrowhead: bool,
+    pub circular: Option<String>,
+    pub placement: Option<Placement>,
+}
+
+#[derive(Deserialize)]
+enum ArrowChild {
+    #[serde(rename = "arrow-direction")]
+    Direction(String),
+    #[serde(rename = "arrow-style")]
+    Style(String),
+    #[serde(rename = "arrowhead")]
+    Arrowhead(IgnoredAny),
+    #[serde(rename = "circular-arrow")]
+    Circular(String),
+}
+
+#[derive(Deserialize)]
+struct ArrowXml {
+    #[serde(default = "Option::default")]
+    placement: Option<Placement>,
+
+    #[serde(rename = "$value", default = "Vec::default")]
+    children: Vec<ArrowChild>,
+}
+
+impl From<ArrowXml> for Arrow {
+    fn from(xml: ArrowXml) -> Self {
+        let mut item = Arrow {
+            placement: xml.placement,
+            ..Arrow::default()
+        };
+        for child in xml.children {
+            match child {
+                ArrowChild::Direction(d) => item.direction = Some(d),
+                ArrowChild::Style(s) => item.style = Some(s),
+                ArrowChild::Arrowhead(_) => item.arrowhead = true,
+                ArrowChild::Circular(c) => item.circular = Some(c),
+            }
+        }
+        item
+    }
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/harmon-mute/
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Default)]
+pub struct HarmonMute {
+    #[serde(rename = "harmon-closed", default = "Closed::default")]
+    pub harmon_closed: Closed,
+
+    #[serde(default = "Option::default")]
+    pub placement: Option<Placement>,
+}
+
+#[derive(Debug, EnumString, PartialEq, Clone, Serialize, Deserialize)]
+pub enum TechnicalType {
+    #[strum(serialize = "up-bow")]
+    #[serde(rename = "up-bow")]
+    UpBow(TechnicalMeta),
+
+    #[strum(serialize = "down-bow")]
+    #[serde(rename = "down-bow")]
+    DownBow(TechnicalMeta),
+
+    #[strum(disabled)]
+    #[serde(rename = "harmonic")]
+    Harmonic(Harmonic),
+
+    #[strum(serialize = "open-string")]
+    #[serde(rename = "open-string")]
+    OpenString(TechnicalMeta),
+
+    #[strum(serialize = "thumb-position")]
+    #[serde(rename = "thumb-position")]
+    ThumbPosition(TechnicalMeta),
+
+    #[strum(serialize = "fingering")]
+    #[serde(rename = "fingering")]
+    Fingering(TechnicalMeta),
+
+    #[strum(serialize = "pluck")]
+    #[serde(rename = "pluck")]
+    Pluck(TechnicalMeta),
+
+    #[strum(serialize = "double-tongue")]
+    #[serde(rename = "double-tongue")]
+    DoubleTongue(TechnicalMeta),
+
+    #[strum(serialize = "triple-tongue")]
+    #[serde(rename = "triple-tongue")]
+    TripleTongue(TechnicalMeta),
+
+    #[strum(serialize = "stopped")]
+    #[serde(rename = "stopped")]
+    Stopped(TechnicalMeta),
+
+    #[strum(serialize = "snap-pizzicato")]
+    #[serde(rename = "snap-pizzicato")]
+    SnapPizzicato(TechnicalMeta),
+
+    #[strum(serialize = "fret")]
+    #[serde(rename = "fret")]
+    Fret(TechnicalMeta),
+
+    #[strum(serialize = "string")]
+    #[serde(rename = "string")]
+    String(TechnicalMeta),
+
+    #[strum(serialize = "hammer-on")]
+    #[serde(rename = "hammer-on")]
+    HammerOn(TechnicalMeta),
+
+    #[strum(serialize = "pull-off")]
+    #[serde(rename = "pull-off")]
+    PullOff(TechnicalMeta),
+
+    #[strum(disabled)]
+    #[serde(rename = "bend")]
+    Bend(Bend),
+
+    #[strum(serialize = "tap")]
+    #[serde(rename = "tap")]
+    Tap(TechnicalMeta),
+
+    #[strum(serialize = "heel")]
+    #[serde(rename = "heel")]
+    Heel(TechnicalMeta),
+
+    #[strum(serialize = "toe")]
+    #[serde(rename = "toe")]
+    Toe(TechnicalMeta),
+
+    #[strum(serialize = "fingernails")]
+    #[serde(rename = "fingernails")]
+    Fingernails(TechnicalMeta),
+
+    #[strum(disabled)]
+    #[serde(rename = "hole")]
+    Hole(Hole),
+
+    #[strum(disabled)]
+    #[serde(rename = "arrow")]
+    Arrow(Arrow),
+
+    #[strum(serialize = "handbell")]
+    #[serde(rename = "handbell")]
+    Handbell(TechnicalMeta),
+
+    #[strum(serialize = "brass-bend")]
+    #[serde(rename = "brass-bend")]
+    BrassBend(TechnicalMeta),
+
+    #[strum(serialize = "flip")]
+    #[serde(rename = "flip")]
+    Flip(TechnicalMeta),
+
+    #[strum(serialize = "smear")]
+    #[serde(rename = "smear")]
+    Smear(TechnicalMeta),
+
+    #[strum(serialize = "open")]
+    #[serde(rename = "open")]
+    Open(TechnicalMeta),
+
+    #[strum(serialize = "half-muted")]
+    #[serde(rename = "half-muted")]
+    HalfMuted(TechnicalMeta),
+
+    #[strum(disabled)]
+    #[serde(rename = "harmon-mute")]
+    HarmonMute(HarmonMute),
+
+    #[strum(serialize = "golpe")]
+    #[serde(rename = "golpe")]
+    Golpe(TechnicalMeta),
+
+    #[strum(serialize = "other-technical")]
+    #[serde(rename = "other-technical")]
+    OtherTechnical(TechnicalMeta),
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Default)]
+pub struct Technical {
+    #[serde(rename = "$value", default = "Vec::default")]
+    pub technical: Vec<TechnicalType>,
+}
+
+impl Technical {
+    /// The fret of a tablature note.
+    pub fn fret(&self) -> Option<u8> {
+        self.technical.iter().find_map(|t| match t {
+            TechnicalType::Fret(m) => m.content.as_deref()?.trim().parse().ok(),
+            _ => None,
+        })
+    }
+
+    /// The string of a tablature note, 1 being the highest.
+    pub fn string(&self) -> Option<u8> {
+        self.technical.iter().find_map(|t| match t {
+            TechnicalType::String(m) => m.content.as_deref()?.trim().parse().ok(),
+            _ => None,
+        })
+    }
+
+    /// Fingerings in document order, substitutions and alternates included.
+    pub fn fingerings(&self) -> Vec<&TechnicalMeta> {
+        self.technical
+            .iter()
+            .filter_map(|t| match t {
+                TechnicalType::Fingering(m) => Some(m),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl WriteXml for TechnicalMeta {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .opt("type", &self.r#type)
+            .opt("number", &self.number)
+            .opt("placement", &self.placement)
+            .opt("substitution", &self.substitution)
+            .opt("alternate", &self.alternate)
+            .opt("hand", &self.hand)
+            .opt("color", &self.color)
+            .opt("default-x", &self.default_x)
+            .opt("default-y", &self.default_y)
+            .opt("relative-x", &self.relative_x)
+            .opt("relative-y", &self.relative_y)
+            .opt("smufl", &self.smufl);
+        match &self.content {
+            Some(content) => w.text_element_with(name, attrs, content),
+            None => w.empty(name, attrs),
+        }
+    }
+}
+
+impl WriteXml for Harmonic {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .opt("print-object", &self.print_object)
+            .opt("placement", &self.placement);
+        w.start(name, attrs);
+        if self.natural {
+            w.empty("natural", Attrs::new());
+        }
+        if self.artificial {
+            w.empty("artificial", Attrs::new());
+        }
+        if self.base_pitch {
+            w.empty("base-pitch", Attrs::new());
+        }
+        if self.touching_pitch {
+            w.empty("touching-pitch", Attrs::new());
+        }
+        if self.sounding_pitch {
+            w.empty("sounding-pitch", Attrs::new());
+        }
+        w.end(name);
+    }
+}
+
+impl WriteXml for Bend {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .opt("shape", &self.shape)
+            .opt("accelerate", &self.accelerate)
+            .opt("beats", &self.beats)
+            .opt("first-beat", &self.first_beat)
+            .opt("last-beat", &self.last_beat);
+        w.start(name, attrs);
+        w.text_element("bend-alter", &self.alter);
+        if self.pre_bend {
+            w.empty("pre-bend", Attrs::new());
+        } else if self.release {
+            w.empty("release", Attrs::new());
+        }
+        w.opt_text_element("with-bar", &self.with_bar);
+        w.end(name);
+    }
+}
+
+impl WriteXml for Closed {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new().opt("location", &self.location);
+        w.text_element_with(name, attrs, &self.content);
+    }
+}
+
+impl WriteXml for Hole {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new().opt("placement", &self.placement));
+        w.opt_text_element("hole-type", &self.hole_type);
+        w.element("hole-closed", &self.hole_closed);
+        w.opt_text_element("hole-shape", &self.hole_shape);
+        w.end(name);
+    }
+}
+
+impl WriteXml for Arrow {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new().opt("placement", &self.placement));
+        w.opt_text_element("arrow-direction", &self.direction);
+        w.opt_text_element("arrow-style", &self.style);
+        if self.arrowhead {
+            w.empty("arrowhead", Attrs::new());
+        }
+        w.opt_text_element("circular-arrow", &self.circular);
+        w.end(name);
+    }
+}
+
+impl WriteXml for HarmonMute {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new().opt("placement", &self.placement));
+        w.element("harmon-closed", &self.harmon_closed);
+        w.end(name);
+    }
+}
+
+impl WriteXml for TechnicalType {
+    fn write_xml(&self, w: &mut XmlWriter, _name: &str) {
+        match self {
+            TechnicalType::UpBow(m) => w.element("up-bow", m),
+            TechnicalType::DownBow(m) => w.element("down-bow", m),
+            TechnicalType::Harmonic(h) => w.element("harmonic", h),
+            TechnicalType::OpenString(m) => w.element("open-string", m),
+            TechnicalType::ThumbPosition(m) => w.element("thumb-position", m),
+            TechnicalType::Fingering(m) => w.element("fingering", m),
+            TechnicalType::Pluck(m) => w.element("pluck", m),
+            TechnicalType::DoubleTongue(m) => w.element("double-tongue", m),
+            TechnicalType::TripleTongue(m) => w.element("triple-tongue", m),
+            TechnicalType::Stopped(m) => w.element("stopped", m),
+            TechnicalType::SnapPizzicato(m) => w.element("snap-pizzicato", m),
+            TechnicalType::Fret(m) => w.element("fret", m),
+            TechnicalType::String(m) => w.element("string", m),
+            TechnicalType::HammerOn(m) => w.element("hammer-on", m),
+            TechnicalType::PullOff(m) => w.element("pull-off", m),
+            TechnicalType::Bend(b) => w.element("bend", b),
+            TechnicalType::Tap(m) => w.element("tap", m),
+            TechnicalType::Heel(m) => w.element("heel", m),
+            TechnicalType::Toe(m) => w.element("toe", m),
+            TechnicalType::Fingernails(m) => w.element("fingernails", m),
+            TechnicalType::Hole(h) => w.element("hole", h),
+            TechnicalType::Arrow(a) => w.element("arrow", a),
+            TechnicalType::Handbell(m) => w.element("handbell", m),
+            TechnicalType::BrassBend(m) => w.element("brass-bend", m),
+            TechnicalType::Flip(m) => w.element("flip", m),
+            TechnicalType::Smear(m) => w.element("smear", m),
+            TechnicalType::Open(m) => w.element("open", m),
+            TechnicalType::HalfMuted(m) => w.element("half-muted", m),
+            TechnicalType::HarmonMute(h) => w.element("harmon-mute", h),
+            TechnicalType::Golpe(m) => w.element("golpe", m),
+            TechnicalType::OtherTechnical(m) => w.element("other-technical", m),
+        }
+    }
+}
+
+impl WriteXml for Technical {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.elements("", &self.technical);
+        w.end(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Technical, TechnicalType};
+    use crate::musicxml::{
+        measure::MeasureContent, note::NotationType, score_partwise::ScorePartwise,
+    };
+    use serde_xml_rs::from_str;
+    use std::fs;
+
+    #[test]
+    fn technical() {
+        let xml = r#"<technical>
+            <harmonic><natural/><touching-pitch/></harmonic>
+            <fingering>5</fingering>
+            <fingering substitution="yes">3</fingering>
+            <hammer-on number="1" type="start">H</hammer-on>
+            <bend><bend-alter>-1</bend-alter><release/><with-bar>scoop</with-bar></bend>
+            <string>3</string>
+            <fret>12</fret>
+        </technical>"#;
+        let item: Technical = from_str(xml).unwrap();
+
+        match &item.technical[0] {
+            TechnicalType::Harmonic(h) => assert!(h.natural && h.touching_pitch && !h.artificial),
+            other => panic!("unexpected {other:?}"),
+        }
+        let fingerings = item.fingerings();
+        assert_eq!(fingerings.len(), 2);
+        assert_eq!(fingerings[1].substitution.as_deref(), Some("yes"));
+        match &item.technical[3] {
+            TechnicalType::HammerOn(m) => {
+                assert_eq!(m.r#type.as_deref(), Some("start"));
+                assert_eq!(m.content.as_deref(), Some("H"));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        match &item.technical[4] {
+            TechnicalType::Bend(b) => {
+                assert_eq!(b.alter, -1.0);
+                assert!(b.release && !b.pre_bend);
+                assert_eq!(b.with_bar.as_deref(), Some("scoop"));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        assert_eq!(item.string(), Some(3));
+        assert_eq!(item.fret(), Some(12));
+    }
+
+    #[test]
+    fn tab_staves() {
+        let xml = fs::read_to_string("resources/xml-test-files/71e-TabStaves.xml").unwrap();
+        let item: ScorePartwise = from_str(&xml).unwrap();
+
+        let frets: Vec<(u8, u8)> = item.parts[7]
+            .measures
+            .iter()
+            .flat_map(|m| &m.content)
+            .filter_map(|c| match c {
+                MeasureContent::Note(n) => n.notations.as_ref(),
+                _ => None,
+            })
+            .flat_map(|n| &n.notations)
+            .filter_map(|n| match n {
+                NotationType::Technical(t) => Some((t.string()?, t.fret()?)),
+                _ => None,
+            })
+            .collect();
+        assert!(!frets.is_empty());
+        assert!(frets.contains(&(3, 24)));
+    }
+}
@@ -0,0 +1,496 @@
+use super::{
+    attributes::{Key, KeyMode},
+    degree::{Degree, DegreeTypeValue},
+    harmony::{Harmony, HarmonyItem, HarmonyKind, Pitch, Step},
+    interval::{Interval, Quality},
+};
+use std::fmt;
+
+/// A step with an alteration but no octave, like B♭ or F♯.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Default)]
+pub struct PitchName {
+    pub step: Step,
+
+    /// Chromatic alteration in semitones, as in `<alter>`.
+    pub alter: f32,
+}
+
+impl PitchName {
+    pub fn new(step: Step, alter: f32) -> PitchName {
+        PitchName { step, alter }
+    }
+
+    /// 0 for C up to 11 for B, with microtonal alterations rounded.
+    pub fn pitch_class(&self) -> u8 {
+        (self.step.semitones() as i32 + self.alter.round() as i32).rem_euclid(12) as u8
+    }
+
+    /// The name `interval` above, or below for a negative interval.
+    pub fn transpose(&self, interval: &Interval) -> PitchName {
+        let (step, alter) = self.step.transpose(self.alter, interval);
+        PitchName { step, alter }
+    }
+
+    /// The pitch of this name in `octave`.
+    pub fn with_octave(&self, octave: u8) -> Pitch {
+        Pitch {
+            step: self.step.clone(),
+            alter: (self.alter != 0.0).then_some(self.alter),
+            octave,
+        }
+    }
+}
+
+impl From<&Pitch> for PitchName {
+    fn from(pitch: &Pitch) -> Self {
+        PitchName::new(pitch.step.clone(), pitch.alter.unwrap_or(0.0))
+    }
+}
+
+/// Names like "C", "Bb" or "F##", with one "#" or "b" per semitone.
+impl fmt::Display for PitchName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let semitones = self.alter.round() as i32;
+        let accidental = if semitones < 0 { "b" } else { "#" };
+        write!(
+            f,
+            "{:?}{}",
+            self.step,
+            accidental.repeat(semitones.unsigned_abs() as usize)
+        )
+    }
+}
+
+impl Pitch {
+    /// 0 for C up to 11 for B, regardless of octave.
+    pub fn pitch_class(&self) -> u8 {
+        PitchName::from(self).pitch_class()
+    }
+}
+
+/// A set of the twelve pitch classes, regardless of spelling and octave.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PitchClassSet(u16);
+
+impl PitchClassSet {
+    pub fn new() -> PitchClassSet {
+        PitchClassSet(0)
+    }
+
+    pub fn insert(&mut self, pitch_class: u8) {
+        self.0 |= 1 << (pitch_class % 12);
+    }
+
+    pub fn contains(&self, pitch_class: u8) -> bool {
+        self.0 & (1 << (pitch_class % 12)) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The pitch classes in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..12).filter(|pc| self.contains(*pc))
+    }
+
+    /// Every pitch class moved up by `semitones`, or down if negative.
+    pub fn transpose(&self, semitones: i32) -> PitchClassSet {
+        self.iter()
+            .map(|pc| (pc as i32 + semitones).rem_euclid(12) as u8)
+            .collect()
+    }
+
+    /// The semitones `other` must be moved up to give this set, if any, like 2 from
+    /// a C major to a D major triad.
+    pub fn transposition_of(&self, other: &PitchClassSet) -> Option<u8> {
+        (0..12).find(|semitones| other.transpose(*semitones as i32) == *self)
+    }
+}
+
+impl FromIterator<u8> for PitchClassSet {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        let mut set = PitchClassSet::new();
+        for pitch_class in iter {
+            set.insert(pitch_class);
+        }
+        set
+    }
+}
+
+/// A step of the scale of a key, counted from 1 for the tonic, with its alteration
+/// against the key. In D major, C is the lowered seventh degree.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct ScaleDegree {
+    pub degree: u8,
+    pub alter: f32,
+}
+
+/// Degrees like "5", "b7" or "#4".
+impl fmt::Display for ScaleDegree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let semitones = self.alter.round() as i32;
+        let accidental = if semitones < 0 { "b" } else { "#" };
+        write!(
+            f,
+            "{}{}",
+            accidental.repeat(semitones.unsigned_abs() as usize),
+            self.degree
+        )
+    }
+}
+
+impl Key {
+    /// The first note of the scale of the key and its mode, where no mode counts as
+    /// major. Non-traditional keys have no tonic of their own and are taken as on C.
+    pub fn tonic(&self) -> PitchName {
+        if !self.alterations.is_empty() {
+            return PitchName::new(Step::C, self.alter(&Step::C));
+        }
+        let mode = match self.mode {
+            KeyMode::None | KeyMode::Mayjor | KeyMode::Ionian => 0,
+            KeyMode::Dorian => 1,
+            KeyMode::Phrygian => 2,
+            KeyMode::Lydian => 3,
+            KeyMode::Mixolydian => 4,
+            KeyMode::Minor | KeyMode::Aeolian => 5,
+            KeyMode::Locrian => 6,
+        };
+        let step = Step::from_index(4 * self.fifths as i32 + mode);
+        let alter = self.alter(&step);
+        PitchName::new(step, alter)
+    }
+
+    /// The seven notes of the scale from the tonic, as the key signature alters them.
+    pub fn scale(&self) -> Vec<PitchName> {
+        let tonic = self.tonic().step.index();
+        (0..7)
+            .map(|i| {
+                let step = Step::from_index(tonic + i);
+                let alter = self.alter(&step);
+                PitchName::new(step, alter)
+            })
+            .collect()
+    }
+
+    /// The scale degree of a step altered by `alter`.
+    pub fn scale_degree(&self, step: &Step, alter: f32) -> ScaleDegree {
+        let degree = (step.index() - self.tonic().step.index()).rem_euclid(7) + 1;
+        ScaleDegree {
+            degree: degree as u8,
+            alter: alter - self.alter(step),
+        }
+    }
+}
+
+impl HarmonyKind {
+    /// The intervals above the root of the chord this kind names, root included, as
+    /// given in the MusicXML definition of each kind. Other chords only have a root, and
+    /// `none` has no notes at all.
+    pub fn intervals(&self) -> Vec<Interval> {
+        let names: &[&str] = match self {
+            HarmonyKind::Major | HarmonyKind::Neapolitan => &["P1", "M3", "P5"],
+            HarmonyKind::Minor => &["P1", "m3", "P5"],
+            HarmonyKind::Augmented => &["P1", "M3", "A5"],
+            HarmonyKind::Diminished => &["P1", "m3", "d5"],
+            HarmonyKind::Dominant => &["P1", "M3", "P5", "m7"],
+            HarmonyKind::MajorSeventh => &["P1", "M3", "P5", "M7"],
+            HarmonyKind::MinorSeventh => &["P1", "m3", "P5", "m7"],
+            HarmonyKind::DiminishedSeventh => &["P1", "m3", "d5", "d7"],
+            HarmonyKind::HalfDiminishedSeventh => &["P1", "m3", "d5", "m7"],
+            HarmonyKind::AugmentedSeventh => &["P1", "M3", "A5", "m7"],
+            HarmonyKind::MajorMinorSeventh => &["P1", "m3", "P5", "M7"],
+            HarmonyKind::MajorSixth => &["P1", "M3", "P5", "M6"],
+            HarmonyKind::MinorSixth => &["P1", "m3", "P5", "M6"],
+            HarmonyKind::DominantNinth => &["P1", "M3", "P5", "m7", "M9"],
+            HarmonyKind::MajorNinth => &["P1", "M3", "P5", "M7", "M9"],
+            HarmonyKind::MinorNinth => &["P1", "m3", "P5", "m7", "M9"],
+            HarmonyKind::Dominant11th => &["P1", "M3", "P5", "m7", "M9", "P11"],
+            HarmonyKind::Major11th => &["P1", "M3", "P5", "M7", "M9", "P11"],
+            HarmonyKind::Minor11th => &["P1", "m3", "P5", "m7", "M9", "P11"],
+            HarmonyKind::Dominant13th => &["P1", "M3", "P5", "m7", "M9", "P11", "M13"],
+            HarmonyKind::Major13th => &["P1", "M3", "P5", "M7", "M9", "P11", "M13"],
+            HarmonyKind::Minor13th => &["P1", "m3", "P5", "m7", "M9", "P11", "M13"],
+            HarmonyKind::SuspendedSecond => &["P1", "M2", "P5"],
+            HarmonyKind::SuspendedFourth => &["P1", "P4", "P5"],
+            HarmonyKind::Italian => &["P1", "M3", "A6"],
+            HarmonyKind::French => &["P1", "M3", "A4", "A6"],
+            HarmonyKind::German => &["P1", "M3", "P5", "A6"],
+            HarmonyKind::Power => &["P1", "P5"],
+            HarmonyKind::Tristan => &["P1", "A4", "A6", "A9"],
+            HarmonyKind::Pedal | HarmonyKind::Other => &["P1"],
+            HarmonyKind::None => &[],
+        };
+        names
+            .iter()
+            .map(|name| name.parse().expect("chord intervals are valid names"))
+            .collect()
+    }
+}
+
+impl Degree {
+    /// The interval an added degree puts above the root. Added degrees count from a
+    /// dominant chord, so a 7 is a minor seventh and the others are major or perfect.
+    pub fn interval(&self) -> Option<Interval> {
+        let number = self.value.content as u32;
+        let quality = match number % 7 {
+            0 => Quality::Minor,
+            1 | 4 | 5 => Quality::Perfect,
+            _ => Quality::Major,
+        };
+        let base = Interval::named(quality, number)?;
+        let alter = self.alter.content.round() as i32;
+        Some(Interval::new(base.diatonic, base.chromatic + alter))
+    }
+}
+
+/// Applies the additions, alterations and subtractions of `degrees` to the chord of
+/// `intervals`. Alterations move the tone the chord already has, and they and
+/// subtractions of tones the chord doesn't have, like a 9 in a triad, are ignored.
+fn apply_degrees(mut intervals: Vec<Interval>, degrees: &[&Degree]) -> Vec<Interval> {
+    for degree in degrees {
+        let Some(interval) = degree.interval() else {
+            continue;
+        };
+        let same = |i: &Interval| i.diatonic == interval.diatonic;
+        match degree.degree_type.content {
+            DegreeTypeValue::Add => {
+                intervals.retain(|i| !same(i));
+                intervals.push(interval);
+            }
+            DegreeTypeValue::Alter => {
+                if let Some(existing) = intervals.iter_mut().find(|i| same(i)) {
+                    existing.chromatic += degree.alter.content.round() as i32;
+                }
+            }
+            DegreeTypeValue::Subtract => intervals.retain(|i| !same(i)),
+        }
+    }
+    intervals.sort_by_key(|i| (i.diatonic, i.chromatic));
+    intervals
+}
+
+impl Harmony {
+    /// The root of the chord, or `None` for chords written as numerals.
+    pub fn root(&self) -> Option<PitchName> {
+        self.items.iter().find_map(|item| match item {
+            HarmonyItem::Root(root) => Some(PitchName::new(
+                root.step.content.clone(),
+                root.alter.as_ref().map_or(0.0, |a| a.content),
+            )),
+            _ => None,
+        })
+    }
+
+    /// The bass note when it differs from the root.
+    pub fn bass(&self) -> Option<PitchName> {
+        self.items.iter().find_map(|item| match item {
+            HarmonyItem::Bass(bass) => Some(PitchName::new(
+                bass.step.content.clone(),
+                bass.alter.as_ref().map_or(0.0, |a| a.content),
+            )),
+            _ => None,
+        })
+    }
+
+    /// The intervals of the chord above its root, from its kind and degrees.
+    pub fn intervals(&self) -> Vec<Interval> {
+        let kind = self.items.iter().find_map(|item| match item {
+            HarmonyItem::Kind(kind) => Some(&kind.content),
+            _ => None,
+        });
+        let degrees: Vec<&Degree> = self
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                HarmonyItem::Degree(degree) => Some(degree),
+                _ => None,
+            })
+            .collect();
+        apply_degrees(
+            kind.map(HarmonyKind::intervals).unwrap_or_default(),
+            &degrees,
+        )
+    }
+
+    /// The notes of the chord from its root upwards, without the bass.
+    pub fn pitch_names(&self) -> Vec<PitchName> {
+        let Some(root) = self.root() else {
+            return vec![];
+        };
+        self.intervals()
+            .iter()
+            .map(|interval| root.transpose(interval))
+            .collect()
+    }
+
+    /// The pitch classes of the chord, bass included.
+    pub fn pitch_classes(&self) -> PitchClassSet {
+        self.pitch_names()
+            .iter()
+            .chain(&self.bass())
+            .map(PitchName::pitch_class)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PitchClassSet, PitchName};
+    use crate::musicxml::attributes::{Key, KeyMode};
+    use crate::musicxml::harmony::{Harmony, HarmonyKind, Step};
+    use crate::musicxml::measure::MeasureContent;
+    use crate::musicxml::score_partwise::ScorePartwise;
+    use serde_xml_rs::from_str;
+    use std::fs;
+
+    fn names(names: &[PitchName]) -> Vec<String> {
+        names.iter().map(ToString::to_string).collect()
+    }
+
+    fn key(fifths: i8, mode: KeyMode) -> Key {
+        Key {
+            fifths,
+            mode,
+            ..Key::default()
+        }
+    }
+
+    #[test]
+    fn scale_degrees() {
+        let d_major = key(2, KeyMode::Mayjor);
+        assert_eq!(d_major.tonic(), PitchName::new(Step::D, 0.0));
+        assert_eq!(
+            names(&d_major.scale()),
+            vec!["D", "E", "F#", "G", "A", "B", "C#"]
+        );
+        assert_eq!(d_major.scale_degree(&Step::F, 1.0).to_string(), "3");
+        assert_eq!(d_major.scale_degree(&Step::C, 0.0).to_string(), "b7");
+
+        let a_minor = key(0, KeyMode::Minor);
+        assert_eq!(a_minor.tonic(), PitchName::new(Step::A, 0.0));
+        assert_eq!(a_minor.scale_degree(&Step::G, 1.0).to_string(), "#7");
+
+        assert_eq!(key(-3, KeyMode::Minor).tonic().to_string(), "C");
+        assert_eq!(key(-2, KeyMode::Dorian).tonic().to_string(), "C");
+        assert_eq!(key(-6, KeyMode::None).tonic().to_string(), "Gb");
+    }
+
+    #[test]
+    fn pitch_class_sets() {
+        let c_major: PitchClassSet = [0, 4, 7].into_iter().collect();
+        let d_major: PitchClassSet = [2, 6, 9, 14].into_iter().collect();
+
+        assert_eq!(d_major.len(), 3);
+        assert!(d_major.contains(6));
+        assert!(!d_major.contains(7));
+        assert_eq!(d_major.iter().collect::<Vec<_>>(), vec![2, 6, 9]);
+        assert_eq!(d_major.transposition_of(&c_major), Some(2));
+        assert_eq!(
+            c_major.transpose(-1).iter().collect::<Vec<_>>(),
+            vec![3, 6, 11]
+        );
+
+        let c_minor: PitchClassSet = [0, 3, 7].into_iter().collect();
+        assert_eq!(c_minor.transposition_of(&c_major), None);
+        assert!(PitchClassSet::new().is_empty());
+    }
+
+    #[test]
+    fn kinds() {
+        let spell = |root: PitchName, kind: HarmonyKind| {
+            let tones: Vec<PitchName> =
+                kind.intervals().iter().map(|i| root.transpose(i)).collect();
+            names(&tones)
+        };
+        let c = PitchName::new(Step::C, 0.0);
+        let a_flat = PitchName::new(Step::A, -1.0);
+
+        assert_eq!(
+            spell(c.clone(), HarmonyKind::HalfDiminishedSeventh),
+            vec!["C", "Eb", "Gb", "Bb"]
+        );
+        assert_eq!(
+            spell(c.clone(), HarmonyKind::Tristan),
+            vec!["C", "F#", "A#", "D#"]
+        );
+        assert_eq!(
+            spell(a_flat.clone(), HarmonyKind::Italian),
+            vec!["Ab", "C", "F#"]
+        );
+        assert_eq!(
+            spell(a_flat, HarmonyKind::French),
+            vec!["Ab", "C", "D", "F#"]
+        );
+        assert_eq!(spell(c, HarmonyKind::Other), vec!["C"]);
+    }
+
+    #[test]
+    fn chords() {
+        let xml = r#"
+            <harmony>
+                <root><root-step>B</root-step><root-alter>-1</root-alter></root>
+                <kind>dominant</kind>
+                <bass><bass-step>D</bass-step></bass>
+                <degree>
+                    <degree-value>11</degree-value>
+                    <degree-alter>1</degree-alter>
+                    <degree-type>add</degree-type>
+                </degree>
+                <degree>
+                    <degree-value>5</degree-value>
+                    <degree-alter>0</degree-alter>
+                    <degree-type>subtract</degree-type>
+                </degree>
+            </harmony>"#;
+        let item: Harmony = from_str(xml).unwrap();
+
+        assert_eq!(names(&item.pitch_names()), vec!["Bb", "D", "Ab", "E"]);
+        assert_eq!(
+            item.pitch_classes().iter().collect::<Vec<_>>(),
+            vec![2, 4, 8, 10]
+        );
+
+        let xml = fs::read_to_string("resources/xml-test-files/71a-Chordnames.xml").unwrap();
+        let score: ScorePartwise = from_str(&xml).unwrap();
+        let chords: Vec<Vec<String>> = score.parts[0]
+            .measures
+            .iter()
+            .flat_map(|m| &m.content)
+            .filter_map(|c| match c {
+                MeasureContent::Harmony(harmony) => Some(names(&harmony.pitch_names())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(chords[0], vec!["C", "E", "G"]);
+        assert!(chords.iter().all(|chord| !chord.is_empty()));
+    }
+
+    #[test]
+    fn altered_degrees() {
+        let altered = |kind: &str, value: u8, alter: i8| {
+            let xml = format!(
+                r#"
+                <harmony>
+                    <root><root-step>C</root-step></root>
+                    <kind>{kind}</kind>
+                    <degree>
+                        <degree-value>{value}</degree-value>
+                        <degree-alter>{alter}</degree-alter>
+                        <degree-type>alter</degree-type>
+                    </degree>
+                </harmony>"#
+            );
+            let item: Harmony = from_str(&xml).unwrap();
+            names(&item.pitch_names())
+        };
+
+        assert_eq!(altered("major-seventh", 7, -1), vec!["C", "E", "G", "Bb"]);
+        assert_eq!(altered("minor", 3, 1), vec!["C", "E", "G"]);
+        assert_eq!(altered("dominant", 5, 1), vec!["C", "E", "G#", "Bb"]);
+    }
+}
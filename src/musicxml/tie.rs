@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+use super::note::StartStop;
+use super::writer::{Attrs, WriteXml, XmlWriter};
+use crate::prelude::*;
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/tie/
+/// The sounding side of a tie. The notated side is `NotationType::Tied`.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone)]
+pub struct Tie {
+    #[serde(default = "StartStop::default")]
+    pub r#type: StartStop,
+
+    #[serde(rename = "time-only", default = "Option::default")]
+    pub time_only: Option<String>,
+}
+
+impl WriteXml for Tie {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .add("type", &self.r#type)
+            .opt("time-only", &self.time_only);
+        w.empty(name, attrs);
+    }
+}
@@ -0,0 +1,36 @@
+use super::note::StartStop;
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumString;
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/data-types/tied-type/
+/// Where a `<tied>` notation is drawn. `continue` only shapes a tie that is both
+/// stopped and started at the note, and `let-ring` marks a note to be left undamped
+/// rather than joined to the next one.
+#[derive(Debug, EnumString, PartialEq, Serialize, Deserialize, Default, PartialOrd, Clone)]
+pub enum TiedType {
+    #[strum(serialize = "start")]
+    #[serde(rename = "start")]
+    #[default]
+    Start,
+
+    #[strum(serialize = "stop")]
+    #[serde(rename = "stop")]
+    Stop,
+
+    #[strum(serialize = "continue")]
+    #[serde(rename = "continue")]
+    Continue,
+
+    #[strum(serialize = "let-ring")]
+    #[serde(rename = "let-ring")]
+    LetRing,
+}
+
+impl From<StartStop> for TiedType {
+    fn from(value: StartStop) -> Self {
+        match value {
+            StartStop::Start => TiedType::Start,
+            StartStop::Stop => TiedType::Stop,
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use serde::{de::IgnoredAny, Deserialize, Serialize};
+
+use super::core::DurationType;
+use super::timeline::Rational;
+use super::writer::{Attrs, WriteXml, XmlWriter};
+use crate::prelude::*;
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/time-modification/
+/// Tuplet ratio of a note: `actual_notes` take the time of `normal_notes`. For nested
+/// tuplets the ratio is the product of all enclosing tuplets.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone)]
+#[serde(from = "TimeModificationXml")]
+pub struct TimeModification {
+    pub actual_notes: u32,
+    pub normal_notes: u32,
+    pub normal_type: Option<DurationType>,
+    pub normal_dots: usize,
+}
+
+impl TimeModification {
+    /// The factor applied to the notated value, `normal_notes / actual_notes`.
+    pub fn ratio(&self) -> Rational {
+        if self.actual_notes == 0 || self.normal_notes == 0 {
+            return Rational::from_integer(1);
+        }
+        Rational::new(self.normal_notes as i64, self.actual_notes as i64)
+    }
+}
+
+#[derive(Deserialize)]
+enum TimeModificationChild {
+    #[serde(rename = "actual-notes")]
+    ActualNotes(u32),
+    #[serde(rename = "normal-notes")]
+    NormalNotes(u32),
+    #[serde(rename = "normal-type")]
+    NormalType(DurationType),
+    #[serde(rename = "normal-dot")]
+    NormalDot(IgnoredAny),
+}
+
+#[derive(Deserialize)]
+struct TimeModificationXml {
+    #[serde(rename = "$value", default = "Vec::default")]
+    children: Vec<TimeModificationChild>,
+}
+
+impl From<TimeModificationXml> for TimeModification {
+    fn from(xml: TimeModificationXml) -> Self {
+        let mut item = TimeModification::default();
+        for child in xml.children {
+            match child {
+                TimeModificationChild::ActualNotes(n) => item.actual_notes = n,
+                TimeModificationChild::NormalNotes(n) => item.normal_notes = n,
+                TimeModificationChild::NormalType(t) => item.normal_type = Some(t),
+                TimeModificationChild::NormalDot(_) => item.normal_dots += 1,
+            }
+        }
+        item
+    }
+}
+
+impl WriteXml for TimeModification {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.text_element("actual-notes", &self.actual_notes);
+        w.text_element("normal-notes", &self.normal_notes);
+        if let Some(normal_type) = &self.normal_type {
+            w.text_element("normal-type", normal_type);
+            for _ in 0..self.normal_dots {
+                w.empty("normal-dot", Attrs::new());
+            }
+        }
+        w.end(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TimeModification;
+    use crate::musicxml::{core::DurationType, timeline::Rational};
+    use serde_xml_rs::from_str;
+
+    #[test]
+    fn time_modification() {
+        let xml = r#"
+            <time-modification>
+                <actual-notes>3</actual-notes>
+                <normal-notes>2</normal-notes>
+                <normal-type>quarter</normal-type>
+                <normal-dot/>
+            </time-modification>"#;
+        let item: TimeModification = from_str(xml).unwrap();
+
+        assert_eq!(item.actual_notes, 3);
+        assert_eq!(item.normal_type, Some(DurationType::Quarter));
+        assert_eq!(item.normal_dots, 1);
+        assert_eq!(item.ratio(), Rational::new(2, 3));
+    }
+}
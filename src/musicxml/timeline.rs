@@ -0,0 +1,572 @@
+use super::{
+    attributes::Attributes, direction::Direction, measure::MeasureContent, note::Note,
+    offset::Offset, part::Part, sound::Sound,
+};
+use num_rational::Ratio;
+use std::collections::BTreeMap;
+
+/// An exact musical time or length, counted in whole notes.
+pub type Rational = Ratio<i64>;
+
+/// A note, direction or sound placed in time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timed<'a, T> {
+    pub event: &'a T,
+
+    /// Index of the measure in `Part::measures`.
+    pub measure: usize,
+
+    /// Onset from the start of the part, in whole notes.
+    pub onset: Rational,
+
+    /// Notated duration in whole notes. Zero for directions and grace notes.
+    pub duration: Rational,
+
+    /// Divisions per quarter note in effect at the event.
+    pub divisions: i64,
+}
+
+impl<T> Timed<'_, T> {
+    /// The onset expressed in the divisions in effect at the event.
+    pub fn onset_divisions(&self) -> Rational {
+        self.onset * 4 * self.divisions
+    }
+
+    /// The duration expressed in the divisions in effect at the event.
+    pub fn duration_divisions(&self) -> Rational {
+        self.duration * 4 * self.divisions
+    }
+
+    /// Onset plus duration, in whole notes.
+    pub fn end(&self) -> Rational {
+        self.onset + self.duration
+    }
+}
+
+/// Where a measure starts and how long it actually is, in whole notes. The length
+/// is the furthest point any voice reaches, so pickup measures are short.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeasureSpan {
+    pub onset: Rational,
+    pub duration: Rational,
+}
+
+/// Performed onset and length of a note, in whole notes, once grace notes have
+/// taken their time from a neighbouring note.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sounding {
+    pub onset: Rational,
+    pub duration: Rational,
+}
+
+impl Sounding {
+    pub fn end(&self) -> Rational {
+        self.onset + self.duration
+    }
+}
+
+/// Onsets and durations of the notes, directions, sounds and attributes of one part.
+#[derive(Debug, Default)]
+pub struct Timeline<'a> {
+    pub notes: Vec<Timed<'a, Note>>,
+    pub directions: Vec<Timed<'a, Direction>>,
+
+    /// Sounds of directions and sounds placed directly in a measure, moved by any
+    /// `<offset>` that affects playback.
+    pub sounds: Vec<Timed<'a, Sound>>,
+
+    pub attributes: Vec<Timed<'a, Attributes>>,
+
+    pub measures: Vec<MeasureSpan>,
+}
+
+impl<'a> Timeline<'a> {
+    /// Walks the measures of `part`, applying `<divisions>` changes, `<backup>`,
+    /// `<forward>` and `<chord/>`. Divisions default to 1 until the first
+    /// `<divisions>` element. Grace notes take no time, and a note without a
+    /// `<duration>` falls back to its type, dots and time modification.
+    pub fn of_part(part: &'a Part) -> Timeline<'a> {
+        let mut timeline = Timeline::default();
+        let mut divisions: i64 = 1;
+        let mut measure_onset = Rational::from_integer(0);
+
+        for (idx, measure) in part.measures.iter().enumerate() {
+            // Position inside the measure, in whole notes.
+            let mut cursor = Rational::from_integer(0);
+            let mut measure_end = cursor;
+            let mut last_onset = cursor;
+
+            for content in &measure.content {
+                match content {
+                    MeasureContent::Attributes(attributes) => {
+                        if let Some(d) = attributes.divisions.filter(|d| *d > 0) {
+                            divisions = d as i64;
+                        }
+                        timeline.attributes.push(Timed {
+                            event: attributes,
+                            measure: idx,
+                            onset: measure_onset + cursor,
+                            duration: Rational::from_integer(0),
+                            divisions,
+                        });
+                    }
+                    MeasureContent::Note(note) => {
+                        let duration = if note.is_grace() {
+                            Rational::from_integer(0)
+                        } else if note.duration == 0 {
+                            note.notated_duration().unwrap_or_default()
+                        } else {
+                            whole_notes(note.duration, divisions)
+                        };
+                        let onset = if note.chord { last_onset } else { cursor };
+
+                        timeline.notes.push(Timed {
+                            event: note,
+                            measure: idx,
+                            onset: measure_onset + onset,
+                            duration,
+                            divisions,
+                        });
+
+                        if !note.chord {
+                            last_onset = cursor;
+                            cursor += duration;
+                        }
+                    }
+                    MeasureContent::Backup(backup) => {
+                        cursor -= whole_notes(backup.duration, divisions);
+                        if cursor < Rational::from_integer(0) {
+                            cursor = Rational::from_integer(0);
+                        }
+                    }
+                    MeasureContent::Forward(forward) => {
+                        cursor += whole_notes(forward.duration, divisions);
+                    }
+                    MeasureContent::Direction(direction) => {
+                        timeline.directions.push(Timed {
+                            event: direction,
+                            measure: idx,
+                            onset: measure_onset + cursor,
+                            duration: Rational::from_integer(0),
+                            divisions,
+                        });
+                        if let Some(sound) = &direction.sound {
+                            let offset = direction
+                                .offset
+                                .as_ref()
+                                .filter(|o| o.affects_sound())
+                                .map_or(Rational::from_integer(0), |o| {
+                                    offset_whole_notes(o, divisions)
+                                });
+                            timeline.sounds.push(Timed {
+                                event: sound,
+                                measure: idx,
+                                onset: measure_onset
+                                    + cursor
+                                    + offset
+                                    + sound_offset(sound, divisions),
+                                duration: Rational::from_integer(0),
+                                divisions,
+                            });
+                        }
+                    }
+                    MeasureContent::Sound(sound) => {
+                        timeline.sounds.push(Timed {
+                            event: sound,
+                            measure: idx,
+                            onset: measure_onset + cursor + sound_offset(sound, divisions),
+                            duration: Rational::from_integer(0),
+                            divisions,
+                        });
+                    }
+                    _ => {}
+                }
+
+                if cursor > measure_end {
+                    measure_end = cursor;
+                }
+            }
+
+            timeline.measures.push(MeasureSpan {
+                onset: measure_onset,
+                duration: measure_end,
+            });
+            measure_onset += measure_end;
+        }
+
+        timeline
+    }
+
+    /// The notes of one voice, in onset order.
+    pub fn voice(&self, voice: u8) -> Vec<&Timed<'a, Note>> {
+        let mut notes: Vec<&Timed<'a, Note>> = self
+            .notes
+            .iter()
+            .filter(|n| n.event.voice == voice)
+            .collect();
+        notes.sort_by_key(|n| n.onset);
+        notes
+    }
+
+    /// The notes of one staff, in onset order.
+    pub fn staff(&self, staff: u8) -> Vec<&Timed<'a, Note>> {
+        let mut notes: Vec<&Timed<'a, Note>> = self
+            .notes
+            .iter()
+            .filter(|n| n.event.staff == staff)
+            .collect();
+        notes.sort_by_key(|n| n.onset);
+        notes
+    }
+
+    /// Performed times of `notes`, index for index. Grace notes before a note steal
+    /// from it, or from the previous note with `steal-time-previous`. Without
+    /// steal-time a slashed grace note lasts a 32nd and an appoggiatura takes half
+    /// the following note. Grace notes never take more than the note they steal from.
+    pub fn sounding(&self) -> Vec<Sounding> {
+        let mut sounding: Vec<Sounding> = self
+            .notes
+            .iter()
+            .map(|n| Sounding {
+                onset: n.onset,
+                duration: n.duration,
+            })
+            .collect();
+
+        // Chords of each voice, in document order, as index lists.
+        let mut voices: BTreeMap<(u8, u8), Vec<Vec<usize>>> = BTreeMap::new();
+        for (idx, timed) in self.notes.iter().enumerate() {
+            let key = (timed.event.staff, timed.event.voice);
+            let chords = voices.entry(key).or_default();
+            match chords.last_mut() {
+                Some(chord) if timed.event.chord => chord.push(idx),
+                _ => chords.push(vec![idx]),
+            }
+        }
+
+        for chords in voices.values() {
+            let mut previous: Option<&Vec<usize>> = None;
+            let mut graces: Vec<&Vec<usize>> = vec![];
+            for chord in chords {
+                if self.notes[chord[0]].event.is_grace() {
+                    graces.push(chord);
+                    continue;
+                }
+                if !graces.is_empty() {
+                    self.steal_time(&mut sounding, &graces, previous, Some(chord));
+                    graces.clear();
+                }
+                previous = Some(chord);
+            }
+            if !graces.is_empty() {
+                self.steal_time(&mut sounding, &graces, previous, None);
+            }
+        }
+
+        sounding
+    }
+
+    fn steal_time(
+        &self,
+        sounding: &mut [Sounding],
+        graces: &[&Vec<usize>],
+        previous: Option<&Vec<usize>>,
+        following: Option<&Vec<usize>>,
+    ) {
+        let grace = |chord: &Vec<usize>| self.notes[chord[0]].event.grace.clone().unwrap();
+        let first = grace(graces[0]);
+        let from_previous = match (previous, following) {
+            (Some(_), None) => true,
+            (Some(_), Some(_)) => first.steal_time_previous.is_some(),
+            _ => false,
+        };
+        let Some(target) = (if from_previous { previous } else { following }) else {
+            return;
+        };
+        let available = sounding[target[0]].duration;
+
+        let mut lengths: Vec<Rational> = graces
+            .iter()
+            .map(|chord| {
+                let g = grace(chord);
+                let percent = if from_previous {
+                    g.steal_time_previous
+                } else {
+                    g.steal_time_following
+                };
+                let divisions = self.notes[chord[0]].divisions;
+                match (percent, g.make_time) {
+                    (Some(p), _) => available * Rational::new((p * 100.0).round() as i64, 10000),
+                    (None, Some(m)) => decimal_whole_notes(m, divisions),
+                    (None, None) if g.is_slashed() => Rational::new(1, 32),
+                    (None, None) => available / (2 * graces.len() as i64),
+                }
+            })
+            .collect();
+
+        let total: Rational = lengths.iter().sum();
+        if total > available && total > Rational::from_integer(0) {
+            let scale = available / total;
+            lengths.iter_mut().for_each(|l| *l *= scale);
+        }
+        let total: Rational = lengths.iter().sum();
+
+        let mut onset = if from_previous {
+            let end = sounding[target[0]].end();
+            for idx in target {
+                sounding[*idx].duration -= total;
+            }
+            end - total
+        } else {
+            let start = sounding[target[0]].onset;
+            for idx in target {
+                sounding[*idx].onset += total;
+                sounding[*idx].duration -= total;
+            }
+            start
+        };
+        for (chord, length) in graces.iter().zip(lengths) {
+            for idx in chord.iter() {
+                sounding[*idx] = Sounding {
+                    onset,
+                    duration: length,
+                };
+            }
+            onset += length;
+        }
+    }
+
+    /// Total length of the part in whole notes.
+    pub fn duration(&self) -> Rational {
+        self.measures
+            .last()
+            .map(|m| m.onset + m.duration)
+            .unwrap_or_default()
+    }
+}
+
+impl Part {
+    pub fn timeline(&self) -> Timeline<'_> {
+        Timeline::of_part(self)
+    }
+}
+
+fn whole_notes(duration: usize, divisions: i64) -> Rational {
+    Rational::new(duration as i64, 4 * divisions)
+}
+
+/// Whole notes of a decimal number of divisions, taken as the exact fraction it
+/// was written as rather than as a binary float.
+fn decimal_whole_notes(value: f32, divisions: i64) -> Rational {
+    Rational::approximate_float(value).unwrap_or_default() / (4 * divisions)
+}
+
+fn offset_whole_notes(offset: &Offset, divisions: i64) -> Rational {
+    decimal_whole_notes(offset.content, divisions)
+}
+
+/// A sound's own `<offset>` always applies to playback.
+fn sound_offset(sound: &Sound, divisions: i64) -> Rational {
+    sound
+        .offset
+        .as_ref()
+        .map_or(Rational::from_integer(0), |o| {
+            offset_whole_notes(o, divisions)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decimal_whole_notes, Rational};
+    use crate::musicxml::{part::Part, score_partwise::ScorePartwise};
+    use serde_xml_rs::from_str;
+    use std::fs;
+
+    #[test]
+    fn decimal_divisions() {
+        assert_eq!(decimal_whole_notes(2.5, 1), Rational::new(5, 8));
+        assert_eq!(decimal_whole_notes(0.1, 2), Rational::new(1, 80));
+        assert_eq!(decimal_whole_notes(-3.0, 4), Rational::new(-3, 16));
+    }
+
+    #[test]
+    fn division_change() {
+        let xml =
+            fs::read_to_string("resources/xml-test-files/03c-Rhythm-DivisionChange.xml").unwrap();
+        let item: ScorePartwise = from_str(&xml).unwrap();
+        let timeline = item.parts[0].timeline();
+
+        let onsets: Vec<Rational> = timeline.notes.iter().map(|n| n.onset).collect();
+        let quarter = Rational::new(1, 4);
+        assert_eq!(
+            onsets,
+            vec![
+                Rational::from(0),
+                quarter,
+                quarter * 2,
+                quarter * 3,
+                quarter * 4,
+                quarter * 6,
+            ]
+        );
+        assert!(timeline.notes.iter().take(4).all(|n| n.duration == quarter));
+
+        assert_eq!(timeline.notes[2].divisions, 8);
+        assert_eq!(timeline.notes[2].onset_divisions(), Rational::from(16));
+        assert_eq!(timeline.notes[2].duration_divisions(), Rational::from(8));
+        assert_eq!(timeline.notes[5].divisions, 38);
+        assert_eq!(timeline.notes[5].duration_divisions(), Rational::from(76));
+
+        assert_eq!(timeline.measures.len(), 2);
+        assert_eq!(timeline.measures[1].onset, Rational::from(1));
+        assert_eq!(timeline.duration(), Rational::from(2));
+    }
+
+    #[test]
+    fn backup_forward_and_chords() {
+        let xml = r#"
+            <part id="P1">
+                <measure number="0" implicit="yes">
+                    <attributes><divisions>2</divisions></attributes>
+                    <note><rest/><duration>2</duration><voice>1</voice><type>quarter</type></note>
+                </measure>
+                <measure number="1">
+                    <note>
+                        <pitch><step>C</step><octave>4</octave></pitch>
+                        <duration>4</duration><voice>1</voice><type>half</type>
+                    </note>
+                    <note>
+                        <chord/>
+                        <pitch><step>E</step><octave>4</octave></pitch>
+                        <duration>4</duration><voice>1</voice><type>half</type>
+                    </note>
+                    <note>
+                        <pitch><step>D</step><octave>4</octave></pitch>
+                        <duration>1</duration><voice>1</voice><type>eighth</type>
+                    </note>
+                    <backup><duration>5</duration></backup>
+                    <forward><duration>2</duration></forward>
+                    <direction>
+                        <direction-type><words>dolce</words></direction-type>
+                        <sound tempo="60"/>
+                    </direction>
+                    <note>
+                        <pitch><step>G</step><octave>3</octave></pitch>
+                        <duration>6</duration><voice>2</voice><type>half</type><dot/>
+                    </note>
+                </measure>
+            </part>"#;
+        let part: Part = from_str(xml).unwrap();
+        let timeline = part.timeline();
+
+        assert_eq!(timeline.measures[0].duration, Rational::new(1, 4));
+        assert_eq!(timeline.measures[1].onset, Rational::new(1, 4));
+        assert_eq!(timeline.measures[1].duration, Rational::from(1));
+
+        let voice1 = timeline.voice(1);
+        assert_eq!(voice1.len(), 4);
+        assert_eq!(voice1[1].onset, Rational::new(1, 4));
+        assert_eq!(voice1[2].onset, Rational::new(1, 4));
+        assert_eq!(voice1[3].onset, Rational::new(3, 4));
+        assert_eq!(voice1[3].duration, Rational::new(1, 8));
+
+        let voice2 = timeline.voice(2);
+        assert_eq!(voice2.len(), 1);
+        assert_eq!(voice2[0].onset, Rational::new(1, 2));
+        assert_eq!(voice2[0].end(), Rational::new(5, 4));
+
+        assert_eq!(timeline.directions.len(), 1);
+        assert_eq!(timeline.directions[0].onset, Rational::new(1, 2));
+        assert_eq!(timeline.directions[0].onset_divisions(), Rational::from(4));
+        assert_eq!(timeline.sounds.len(), 1);
+        assert_eq!(timeline.sounds[0].event.tempo, Some(60.0));
+        assert_eq!(timeline.sounds[0].onset, Rational::new(1, 2));
+    }
+
+    #[test]
+    fn fractional_offset() {
+        let xml = r#"
+            <part id="P1">
+                <measure number="1">
+                    <attributes><divisions>2</divisions></attributes>
+                    <sound tempo="90"><offset>0.5</offset></sound>
+                    <note>
+                        <pitch><step>C</step><octave>4</octave></pitch>
+                        <duration>8</duration><voice>1</voice><type>whole</type>
+                    </note>
+                </measure>
+            </part>"#;
+        let part: Part = from_str(xml).unwrap();
+        let timeline = part.timeline();
+
+        assert_eq!(timeline.sounds.len(), 1);
+        assert_eq!(timeline.sounds[0].onset, Rational::new(1, 16));
+    }
+
+    #[test]
+    fn nested_tuplets() {
+        let xml = fs::read_to_string("resources/xml-test-files/23d-Tuplets-Nested.xml").unwrap();
+        let item: ScorePartwise = from_str(&xml).unwrap();
+        let timeline = item.parts[0].timeline();
+
+        for timed in &timeline.notes {
+            assert_eq!(Some(timed.duration), timed.event.notated_duration());
+        }
+        assert_eq!(timeline.notes[0].duration, Rational::new(1, 12));
+        assert_eq!(timeline.notes[2].duration, Rational::new(1, 30));
+        assert_eq!(timeline.measures[0].duration, Rational::new(1, 2));
+    }
+
+    #[test]
+    fn grace_notes() {
+        let xml = r#"
+            <part id="P1">
+                <measure number="1">
+                    <attributes><divisions>2</divisions></attributes>
+                    <note>
+                        <pitch><step>C</step><octave>5</octave></pitch>
+                        <duration>4</duration><voice>1</voice><type>half</type>
+                    </note>
+                    <note>
+                        <grace steal-time-previous="25"/>
+                        <pitch><step>D</step><octave>5</octave></pitch>
+                        <voice>1</voice><type>16th</type>
+                    </note>
+                    <note>
+                        <pitch><step>E</step><octave>5</octave></pitch>
+                        <duration>2</duration><voice>1</voice><type>quarter</type>
+                    </note>
+                    <note>
+                        <grace slash="yes"/>
+                        <pitch><step>F</step><octave>5</octave></pitch>
+                        <voice>1</voice><type>eighth</type>
+                    </note>
+                    <note>
+                        <pitch><step>G</step><octave>5</octave></pitch>
+                        <duration>2</duration><voice>1</voice><type>quarter</type>
+                    </note>
+                </measure>
+            </part>"#;
+        let part: Part = from_str(xml).unwrap();
+        let timeline = part.timeline();
+
+        assert!(timeline.notes[1].event.is_grace());
+        assert_eq!(timeline.notes[1].duration, Rational::from(0));
+        assert_eq!(timeline.notes[2].onset, Rational::new(1, 2));
+        assert_eq!(timeline.measures[0].duration, Rational::from(1));
+
+        let sounding = timeline.sounding();
+        assert_eq!(sounding[0].duration, Rational::new(3, 8));
+        assert_eq!(sounding[1].onset, Rational::new(3, 8));
+        assert_eq!(sounding[1].duration, Rational::new(1, 8));
+        assert_eq!(sounding[2].onset, Rational::new(1, 2));
+
+        assert_eq!(sounding[3].onset, Rational::new(3, 4));
+        assert_eq!(sounding[3].duration, Rational::new(1, 32));
+        assert_eq!(
+            sounding[4].onset,
+            Rational::new(3, 4) + Rational::new(1, 32)
+        );
+        assert_eq!(sounding[4].end(), Rational::from(1));
+    }
+}
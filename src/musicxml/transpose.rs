@@ -0,0 +1,432 @@
+use serde::{Deserialize, Serialize};
+
+use super::accidental::Accidental;
+use super::attribute_cursor::AttributeState;
+use super::harmony::HarmonyItem;
+use super::interval::Interval;
+use super::measure::MeasureContent;
+use super::part::Part;
+use super::score_partwise::ScorePartwise;
+use super::writer::{Attrs, WriteXml, XmlWriter};
+use super::yes_no::YesNo;
+use crate::prelude::*;
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/double/
+/// The part is doubled an octave below the sounding pitch, or above.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct Double {
+    #[serde(default = "Option::default")]
+    pub above: Option<YesNo>,
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/transpose/
+/// What to add to the written pitch of a transposing part to get its sounding pitch.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct Transpose {
+    /// The staff the transposition applies to, or 0 for all staves.
+    #[serde(default = "i8::default")]
+    pub number: i8,
+
+    #[serde(default = "Option::default")]
+    pub diatonic: Option<i32>,
+
+    #[serde(default = "f32::default")]
+    pub chromatic: f32,
+
+    #[serde(rename = "octave-change", default = "Option::default")]
+    pub octave_change: Option<i32>,
+
+    #[serde(default = "Option::default")]
+    pub double: Option<Double>,
+}
+
+impl Transpose {
+    /// The interval from written to sounding pitch, octave change included. Without
+    /// `<diatonic>` the steps are estimated from the semitones.
+    pub fn interval(&self) -> Interval {
+        let chromatic = self.chromatic.round() as i32;
+        let diatonic = self
+            .diatonic
+            .unwrap_or_else(|| (chromatic as f32 * 7.0 / 12.0).round() as i32);
+        let octaves = self.octave_change.unwrap_or(0);
+        Interval::new(diatonic + 7 * octaves, chromatic + 12 * octaves)
+    }
+}
+
+impl WriteXml for Transpose {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(
+            name,
+            Attrs::new().add_if(self.number != 0, "number", &self.number),
+        );
+        w.opt_text_element("diatonic", &self.diatonic);
+        w.text_element("chromatic", &self.chromatic);
+        w.opt_text_element("octave-change", &self.octave_change);
+        if let Some(double) = &self.double {
+            w.empty("double", Attrs::new().opt("above", &double.above));
+        }
+        w.end(name);
+    }
+}
+
+impl Part {
+    /// Rewrites the written pitches, key signatures and chord symbols of a transposing
+    /// part to sounding pitch, following the `<transpose>` in effect at each point.
+    /// The `<transpose>` elements are kept, so `to_written_pitch` reverses this.
+    pub fn to_concert_pitch(&mut self) {
+        transpose_part(self, |state, staff| {
+            state.transpose(staff).map(|t| t.interval())
+        });
+    }
+
+    /// Rewrites a part in concert pitch back to the written pitch of its
+    /// `<transpose>` elements.
+    pub fn to_written_pitch(&mut self) {
+        transpose_part(self, |state, staff| {
+            state.transpose(staff).map(|t| t.interval().inverse())
+        });
+    }
+
+    /// Moves the pitches, key signatures and chord symbols of the part by `interval`,
+    /// spelled exactly as given.
+    pub fn transpose(&mut self, interval: &Interval) {
+        transpose_part(self, |_, _| Some(*interval));
+    }
+
+    /// Like `transpose`, but with `interval` spelled enharmonically so that no key
+    /// signature needs more than seven sharps or flats where possible. The steps of
+    /// `interval` may change; notes and keys are moved by the same spelling.
+    pub fn transpose_respelled(&mut self, interval: &Interval) {
+        let interval = interval.respelled_for(&traditional_fifths([&*self]));
+        self.transpose(&interval);
+    }
+}
+
+impl ScorePartwise {
+    /// Moves every part by `interval`, spelled exactly as given.
+    pub fn transpose(&mut self, interval: &Interval) {
+        for part in &mut self.parts {
+            part.transpose(interval);
+        }
+    }
+
+    /// Moves every part by `interval`, respelled once for the keys of all parts like
+    /// `Part::transpose_respelled` does, so the parts stay spelled alike.
+    pub fn transpose_respelled(&mut self, interval: &Interval) {
+        let interval = interval.respelled_for(&traditional_fifths(&self.parts));
+        self.transpose(&interval);
+    }
+}
+
+/// The key signatures of `parts` that count sharps or flats.
+fn traditional_fifths<'a>(parts: impl IntoIterator<Item = &'a Part>) -> Vec<i8> {
+    parts
+        .into_iter()
+        .flat_map(|p| &p.measures)
+        .flat_map(|m| &m.content)
+        .filter_map(|c| match c {
+            MeasureContent::Attributes(a) => Some(a),
+            _ => None,
+        })
+        .flat_map(|a| &a.keys)
+        .filter(|k| k.alterations.is_empty())
+        .map(|k| k.fifths)
+        .collect()
+}
+
+/// Transposes notes, keys and chord symbols, each by the interval `interval` gives
+/// for its staff with the attributes in effect.
+fn transpose_part(part: &mut Part, interval: impl Fn(&AttributeState, u8) -> Option<Interval>) {
+    let mut state = AttributeState::default();
+
+    for measure in &mut part.measures {
+        for content in &mut measure.content {
+            match content {
+                MeasureContent::Attributes(attributes) => {
+                    state.apply(attributes);
+                    for key in &mut attributes.keys {
+                        if let Some(interval) = interval(&state, key.number.max(1) as u8) {
+                            *key = key.transpose(&interval);
+                        }
+                    }
+                }
+                MeasureContent::Note(note) => {
+                    if let (Some(pitch), Some(interval)) =
+                        (&mut note.pitch, interval(&state, note.staff.max(1)))
+                    {
+                        *pitch = pitch.transpose(&interval);
+                        // A shown accidental follows the new spelling.
+                        if let (Some(accidental), Some(content)) = (
+                            &mut note.accidental,
+                            Accidental::for_alter(pitch.alter.unwrap_or(0.0)),
+                        ) {
+                            accidental.content = content;
+                        }
+                    }
+                }
+                MeasureContent::Harmony(harmony) => {
+                    let staff = harmony.items.iter().find_map(|item| match item {
+                        HarmonyItem::Staff(staff) => Some(*staff),
+                        _ => None,
+                    });
+                    let Some(interval) = interval(&state, staff.unwrap_or(1).max(1)) else {
+                        continue;
+                    };
+                    for item in &mut harmony.items {
+                        match item {
+                            HarmonyItem::Root(root) => root.transpose(&interval),
+                            HarmonyItem::Bass(bass) => bass.transpose(&interval),
+                            HarmonyItem::Numeral(numeral) => {
+                                if let Some(key) = &mut numeral.key {
+                                    key.fifths = interval.transpose_fifths(key.fifths);
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Transpose;
+    use crate::musicxml::accidental::Accidental;
+    use crate::musicxml::attributes::Cancel;
+    use crate::musicxml::harmony::{HarmonyItem, Pitch, Step};
+    use crate::musicxml::interval::Interval;
+    use crate::musicxml::measure::MeasureContent;
+    use crate::musicxml::part::Part;
+    use crate::musicxml::score_partwise::ScorePartwise;
+    use serde_xml_rs::from_str;
+    use std::fs;
+
+    fn read(file: &str) -> ScorePartwise {
+        let xml = fs::read_to_string(format!("resources/xml-test-files/{file}")).unwrap();
+        from_str(&xml).unwrap()
+    }
+
+    fn first_pitch(part: &Part, measure: usize) -> Pitch {
+        part.measures[measure]
+            .content
+            .iter()
+            .find_map(|c| match c {
+                MeasureContent::Note(note) => note.pitch.clone(),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    fn fifths(part: &Part, measure: usize) -> i8 {
+        part.measures[measure]
+            .content
+            .iter()
+            .find_map(|c| match c {
+                MeasureContent::Attributes(a) => a.keys.first().map(|k| k.fifths),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn transpose() {
+        let xml = r#"
+            <transpose number="2">
+                <diatonic>-1</diatonic>
+                <chromatic>-2</chromatic>
+                <octave-change>-1</octave-change>
+                <double above="yes"/>
+            </transpose>"#;
+        let transpose: Transpose = from_str(xml).unwrap();
+
+        assert_eq!(transpose.number, 2);
+        assert_eq!(transpose.interval(), Interval::new(-8, -14));
+        assert!(transpose.double.is_some());
+
+        let transpose: Transpose =
+            from_str("<transpose><chromatic>-9</chromatic></transpose>").unwrap();
+        assert_eq!(transpose.interval(), Interval::new(-5, -9));
+    }
+
+    #[test]
+    fn transposing_instruments() {
+        let mut item = read("72a-TransposingInstruments.xml");
+        let written: Vec<Pitch> = item.parts.iter().map(|p| first_pitch(p, 0)).collect();
+
+        for part in &mut item.parts {
+            part.to_concert_pitch();
+        }
+        let fifths: Vec<i8> = item.parts.iter().map(|p| fifths(p, 0)).collect();
+        assert_eq!(fifths, vec![0, 0, 0]);
+
+        let concert = first_pitch(&item.parts[0], 0);
+        assert_eq!(concert.midi_number(), written[0].midi_number() - 2.0);
+
+        for part in &mut item.parts {
+            part.to_written_pitch();
+        }
+        let pitches: Vec<Pitch> = item.parts.iter().map(|p| first_pitch(p, 0)).collect();
+        assert_eq!(pitches, written);
+    }
+
+    #[test]
+    fn transposition_change() {
+        let mut item = read("72c-TransposingInstruments-Change.xml");
+        let part = &mut item.parts[0];
+        part.to_concert_pitch();
+
+        assert_eq!(fifths(part, 0), -2);
+        assert_eq!(fifths(part, 1), -2);
+
+        let pitch = first_pitch(part, 0);
+        assert_eq!(
+            (pitch.step, pitch.alter, pitch.octave),
+            (Step::E, Some(-1.0), 4)
+        );
+        let pitch = first_pitch(part, 2);
+        assert_eq!(
+            (pitch.step, pitch.alter, pitch.octave),
+            (Step::B, Some(-1.0), 3)
+        );
+    }
+
+    fn score(fifths: i8, content: &str) -> ScorePartwise {
+        let xml = format!(
+            r#"<score-partwise version="4.0">
+                <part-list><score-part id="P1"><part-name>Voice</part-name></score-part></part-list>
+                <part id="P1"><measure number="1">
+                    <attributes><divisions>1</divisions><key><fifths>{fifths}</fifths></key></attributes>
+                    {content}
+                </measure></part>
+            </score-partwise>"#
+        );
+        from_str(&xml).unwrap()
+    }
+
+    fn note(step: &str, alter: i8, octave: u8) -> String {
+        format!(
+            "<note><pitch><step>{step}</step><alter>{alter}</alter><octave>{octave}</octave></pitch>\
+             <duration>1</duration><voice>1</voice><type>quarter</type></note>"
+        )
+    }
+
+    fn pitches(item: &ScorePartwise) -> Vec<(Step, f32, u8)> {
+        item.parts[0].measures[0]
+            .content
+            .iter()
+            .filter_map(|c| match c {
+                MeasureContent::Note(note) => note.pitch.clone(),
+                _ => None,
+            })
+            .map(|p| (p.step, p.alter.unwrap_or(0.0), p.octave))
+            .collect()
+    }
+
+    #[test]
+    fn transpose_score() {
+        let harmony = r#"
+            <harmony>
+                <root><root-step>B</root-step><root-alter>-1</root-alter></root>
+                <kind>dominant</kind>
+                <bass><bass-step>D</bass-step></bass>
+            </harmony>"#;
+        let content = [harmony, &note("B", -1, 4), &note("F", 1, 4)].concat();
+        let mut item = score(-2, &content);
+        item.transpose(&Interval::new(2, 4));
+
+        assert_eq!(fifths(&item.parts[0], 0), 2);
+        assert_eq!(pitches(&item), vec![(Step::D, 0.0, 5), (Step::A, 1.0, 4)]);
+
+        let mut cancel = score(1, &note("G", 0, 4));
+        let MeasureContent::Attributes(attributes) = &mut cancel.parts[0].measures[0].content[0]
+        else {
+            panic!("Expected attributes.");
+        };
+        attributes.keys[0].cancel = Some(Cancel {
+            fifths: -2,
+            location: None,
+        });
+        cancel.transpose(&Interval::new(1, 2));
+        let MeasureContent::Attributes(attributes) = &cancel.parts[0].measures[0].content[0] else {
+            panic!("Expected attributes.");
+        };
+        assert_eq!(attributes.keys[0].fifths, 3);
+        assert_eq!(attributes.keys[0].cancel.as_ref().unwrap().fifths, 0);
+
+        let flat = note("B", -1, 4).replace("</type>", "</type><accidental>flat</accidental>");
+        let mut flat = score(0, &flat);
+        flat.transpose(&Interval::new(2, 4));
+        let MeasureContent::Note(note) = &flat.parts[0].measures[0].content[1] else {
+            panic!("Expected a note.");
+        };
+        assert_eq!(
+            note.accidental.as_ref().unwrap().content,
+            Accidental::Natural
+        );
+
+        let MeasureContent::Harmony(harmony) = &item.parts[0].measures[0].content[1] else {
+            panic!("Expected a harmony.");
+        };
+        let HarmonyItem::Root(root) = &harmony.items[0] else {
+            panic!("Expected a root.");
+        };
+        assert_eq!(
+            (
+                root.step.content.clone(),
+                root.alter.clone().unwrap().content
+            ),
+            (Step::D, 0.0)
+        );
+        let HarmonyItem::Bass(bass) = &harmony.items[2] else {
+            panic!("Expected a bass.");
+        };
+        assert_eq!(
+            (
+                bass.step.content.clone(),
+                bass.alter.clone().unwrap().content
+            ),
+            (Step::F, 1.0)
+        );
+    }
+
+    #[test]
+    fn transpose_score_respelled() {
+        let mut item = score(1, &note("G", 0, 4));
+        item.transpose(&Interval::new(0, 1));
+        assert_eq!(fifths(&item.parts[0], 0), 8);
+        assert_eq!(pitches(&item), vec![(Step::G, 1.0, 4)]);
+
+        let mut item = score(1, &note("G", 0, 4));
+        item.transpose_respelled(&Interval::new(0, 1));
+
+        assert_eq!(fifths(&item.parts[0], 0), -4);
+        assert_eq!(pitches(&item), vec![(Step::A, -1.0, 4)]);
+
+        let mut part = score(1, &note("G", 0, 4)).parts.remove(0);
+        part.transpose_respelled(&Interval::new(0, 1));
+        assert_eq!(fifths(&part, 0), -4);
+        assert_eq!(
+            first_pitch(&part, 0),
+            Pitch {
+                step: Step::A,
+                alter: Some(-1.0),
+                octave: 4
+            }
+        );
+
+        // No spelling keeps both C♯ and G♭ major within seven, so the keys stay
+        // theoretical and the notes agree with them.
+        let content = [note("C", 1, 4), note("G", -1, 4)].concat();
+        let mut item = score(7, &content);
+        item.parts
+            .push(score(-6, &note("G", -1, 4)).parts.remove(0));
+        item.transpose_respelled(&Interval::new(2, 4));
+        let keys: Vec<i8> = item.parts.iter().map(|p| fifths(p, 0)).collect();
+        assert_eq!(keys, vec![11, -2]);
+        assert_eq!(pitches(&item), vec![(Step::E, 1.0, 4), (Step::B, -1.0, 4)]);
+        assert_eq!(first_pitch(&item.parts[1], 0).step, Step::B);
+    }
+}
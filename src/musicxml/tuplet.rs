@@ -0,0 +1,144 @@
+use serde::{de::IgnoredAny, Deserialize, Serialize};
+
+use super::core::{DurationType, Placement};
+use super::note::StartStop;
+use super::writer::{Attrs, WriteXml, XmlWriter};
+use super::yes_no::YesNo;
+use crate::prelude::*;
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/tuplet/
+/// The notated bracket of a tuplet. Playback uses the note's `<time-modification>`.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone)]
+pub struct Tuplet {
+    #[serde(default = "StartStop::default")]
+    pub r#type: StartStop,
+
+    #[serde(default = "u8::default")]
+    pub number: u8,
+
+    #[serde(default = "Option::default")]
+    pub bracket: Option<YesNo>,
+
+    #[serde(rename = "show-number", default = "Option::default")]
+    pub show_number: Option<String>,
+
+    #[serde(rename = "show-type", default = "Option::default")]
+    pub show_type: Option<String>,
+
+    #[serde(default = "Option::default")]
+    pub placement: Option<Placement>,
+
+    #[serde(rename = "tuplet-actual", default = "Option::default")]
+    pub tuplet_actual: Option<TupletPortion>,
+
+    #[serde(rename = "tuplet-normal", default = "Option::default")]
+    pub tuplet_normal: Option<TupletPortion>,
+}
+
+impl WriteXml for Tuplet {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        let attrs = Attrs::new()
+            .add("type", &self.r#type)
+            .add_if(self.number != 0, "number", &self.number)
+            .opt("bracket", &self.bracket)
+            .opt("show-number", &self.show_number)
+            .opt("show-type", &self.show_type)
+            .opt("placement", &self.placement);
+        if self.tuplet_actual.is_none() && self.tuplet_normal.is_none() {
+            w.empty(name, attrs);
+            return;
+        }
+        w.start(name, attrs);
+        w.opt_element("tuplet-actual", &self.tuplet_actual);
+        w.opt_element("tuplet-normal", &self.tuplet_normal);
+        w.end(name);
+    }
+}
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/tuplet-actual/
+/// The number and note value shown for one side of a tuplet bracket.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone)]
+#[serde(from = "TupletPortionXml")]
+pub struct TupletPortion {
+    pub tuplet_number: Option<u32>,
+    pub tuplet_type: Option<DurationType>,
+    pub tuplet_dots: usize,
+}
+
+#[derive(Deserialize)]
+enum TupletPortionChild {
+    #[serde(rename = "tuplet-number")]
+    Number(u32),
+    #[serde(rename = "tuplet-type")]
+    Type(DurationType),
+    #[serde(rename = "tuplet-dot")]
+    Dot(IgnoredAny),
+}
+
+#[derive(Deserialize)]
+struct TupletPortionXml {
+    #[serde(rename = "$value", default = "Vec::default")]
+    children: Vec<TupletPortionChild>,
+}
+
+impl From<TupletPortionXml> for TupletPortion {
+    fn from(xml: TupletPortionXml) -> Self {
+        let mut item = TupletPortion::default();
+        for child in xml.children {
+            match child {
+                TupletPortionChild::Number(n) => item.tuplet_number = Some(n),
+                TupletPortionChild::Type(t) => item.tuplet_type = Some(t),
+                TupletPortionChild::Dot(_) => item.tuplet_dots += 1,
+            }
+        }
+        item
+    }
+}
+
+impl WriteXml for TupletPortion {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.opt_text_element("tuplet-number", &self.tuplet_number);
+        w.opt_text_element("tuplet-type", &self.tuplet_type);
+        for _ in 0..self.tuplet_dots {
+            w.empty("tuplet-dot", Attrs::new());
+        }
+        w.end(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Tuplet;
+    use crate::musicxml::{core::DurationType, note::StartStop, yes_no::YesNo};
+    use serde_xml_rs::from_str;
+
+    #[test]
+    fn tuplet() {
+        let xml = r#"
+            <tuplet bracket="yes" number="2" type="start">
+                <tuplet-actual>
+                    <tuplet-number>5</tuplet-number>
+                    <tuplet-type>eighth</tuplet-type>
+                </tuplet-actual>
+                <tuplet-normal>
+                    <tuplet-number>2</tuplet-number>
+                    <tuplet-type>eighth</tuplet-type>
+                    <tuplet-dot/>
+                </tuplet-normal>
+            </tuplet>"#;
+        let item: Tuplet = from_str(xml).unwrap();
+
+        assert_eq!(item.r#type, StartStop::Start);
+        assert_eq!(item.number, 2);
+        assert_eq!(item.bracket, Some(YesNo::Yes));
+
+        let actual = item.tuplet_actual.unwrap();
+        assert_eq!(actual.tuplet_number, Some(5));
+        assert_eq!(actual.tuplet_type, Some(DurationType::Eighth));
+
+        let normal = item.tuplet_normal.unwrap();
+        assert_eq!(normal.tuplet_number, Some(2));
+        assert_eq!(normal.tuplet_dots, 1);
+    }
+}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+use super::harmony::Step;
+use super::writer::{Attrs, WriteXml, XmlWriter};
+use crate::prelude::*;
+
+// https://www.w3.org/2021/06/musicxml40/musicxml-reference/elements/unpitched/
+/// A percussion note. The display step and octave only give the staff position,
+/// the sound comes from the note's instrument.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct Unpitched {
+    #[serde(rename = "display-step", default = "Option::default")]
+    pub display_step: Option<Step>,
+
+    #[serde(rename = "display-octave", default = "Option::default")]
+    pub display_octave: Option<u8>,
+}
+
+impl WriteXml for Unpitched {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        w.opt_text_element("display-step", &self.display_step);
+        w.opt_text_element("display-octave", &self.display_octave);
+        w.end(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Unpitched;
+    use crate::musicxml::harmony::Step;
+    use serde_xml_rs::from_str;
+
+    #[test]
+    fn unpitched() {
+        let xml = r#"
+            <unpitched>
+                <display-step>E</display-step>
+                <display-octave>4</display-octave>
+            </unpitched>"#;
+        let item: Unpitched = from_str(xml).unwrap();
+
+        assert_eq!(item.display_step, Some(Step::E));
+        assert_eq!(item.display_octave, Some(4));
+    }
+}
@@ -0,0 +1,324 @@
+use super::{
+    harmony::{Harmony, HarmonyItem, Pitch},
+    interval::Interval,
+    theory::PitchName,
+};
+
+/// How the notes of a chord symbol are spread over the registers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Voicing {
+    /// Every chord tone once, within an octave above the bass.
+    Close,
+
+    /// The close voicing with the second voice from the top an octave lower, keeping
+    /// a slash or inversion bass at the bottom.
+    Drop2,
+
+    /// Root, third and seventh only, as in jazz piano comping.
+    Shell,
+
+    /// Up to six notes stacked like a barre chord in standard tuning, with the bass
+    /// between E2 and D♯3.
+    Guitar,
+}
+
+/// Lowest MIDI note number of a guitar in standard tuning, E2.
+const GUITAR_LOWEST: f32 = 40.0;
+
+/// The chord tones of a harmony by their function.
+struct Tones {
+    root: Interval,
+
+    /// The third, or the second or fourth of suspended chords.
+    third: Option<Interval>,
+    fifth: Option<Interval>,
+
+    /// The seventh, or the sixth of sixth chords.
+    seventh: Option<Interval>,
+
+    /// Ninths, elevenths and thirteenths, in ascending order.
+    extensions: Vec<Interval>,
+}
+
+impl Tones {
+    fn new(intervals: &[Interval]) -> Tones {
+        let simple = |steps: i32| intervals.iter().find(|i| i.diatonic == steps).copied();
+        let third = simple(2).or_else(|| simple(3)).or_else(|| simple(1));
+        let seventh = simple(6).or_else(|| simple(5));
+        let extensions = intervals
+            .iter()
+            .filter(|i| match i.diatonic {
+                1 | 3 => third.is_some_and(|t| t != **i),
+                5 => seventh.is_some_and(|s| s != **i),
+                steps => steps >= 7,
+            })
+            .copied()
+            .collect();
+
+        Tones {
+            root: Interval::default(),
+            third,
+            fifth: simple(4),
+            seventh,
+            extensions,
+        }
+    }
+
+    /// The chord tone an `<inversion>` puts in the bass: 1 for the third, 2 for the
+    /// fifth and 3 for the seventh.
+    fn inversion(&self, inversion: u8) -> Option<Interval> {
+        match inversion {
+            0 => Some(self.root),
+            1 => self.third,
+            2 => self.fifth,
+            3 => self.seventh,
+            _ => None,
+        }
+    }
+}
+
+/// The interval within the octave, so a ninth becomes a second.
+fn simple(interval: &Interval) -> Interval {
+    let octaves = interval.diatonic.div_euclid(7);
+    Interval::new(
+        interval.diatonic - 7 * octaves,
+        interval.chromatic - 12 * octaves,
+    )
+}
+
+fn octave_up(pitch: &Pitch) -> Pitch {
+    Pitch {
+        octave: pitch.octave + 1,
+        ..pitch.clone()
+    }
+}
+
+/// The highest pitch of this name below `pitch`.
+fn below(name: &PitchName, pitch: &Pitch) -> Pitch {
+    (0..=pitch.octave)
+        .rev()
+        .map(|octave| name.with_octave(octave))
+        .find(|p| p.midi_number() < pitch.midi_number())
+        .unwrap_or_else(|| name.with_octave(0))
+}
+
+/// The lowest pitch of this name above `pitch`.
+fn above(name: &PitchName, pitch: &Pitch) -> Pitch {
+    (pitch.octave.saturating_sub(1)..=9)
+        .map(|octave| name.with_octave(octave))
+        .find(|p| p.midi_number() > pitch.midi_number())
+        .unwrap_or_else(|| name.with_octave(9))
+}
+
+impl Harmony {
+    /// The pitches of the chord in a voicing, from the lowest up, with the root in
+    /// `octave`. A `<bass>` or `<inversion>` puts that note at the bottom. Guitar
+    /// voicings choose their own octave. Chords without a root give no pitches.
+    pub fn realize(&self, voicing: Voicing, octave: u8) -> Vec<Pitch> {
+        let (Some(root), intervals) = (self.root(), self.intervals()) else {
+            return vec![];
+        };
+        if intervals.is_empty() {
+            return vec![];
+        }
+        let tones = Tones::new(&intervals);
+        let inversion = self.items.iter().find_map(|item| match item {
+            HarmonyItem::Inversion(inversion) => Some(inversion.content),
+            _ => None,
+        });
+        let bass = self.bass().or_else(|| {
+            inversion
+                .and_then(|i| tones.inversion(i))
+                .map(|interval| root.transpose(&interval))
+        });
+        let name = |interval: &Interval| root.transpose(interval);
+
+        match voicing {
+            Voicing::Close | Voicing::Drop2 => {
+                let low = root.with_octave(octave);
+                let mut pitches: Vec<Pitch> = intervals
+                    .iter()
+                    .map(|interval| low.transpose(&simple(interval)))
+                    .collect();
+                pitches.sort_by(|a, b| a.midi_number().total_cmp(&b.midi_number()));
+                pitches.dedup_by(|a, b| a.is_enharmonic(b));
+
+                if let Some(bass) = &bass {
+                    let class = bass.pitch_class();
+                    if pitches.iter().any(|p| p.pitch_class() == class) {
+                        while pitches[0].pitch_class() != class {
+                            let lowest = pitches.remove(0);
+                            pitches.push(octave_up(&lowest));
+                        }
+                    } else {
+                        pitches.insert(0, below(bass, &pitches[0]));
+                    }
+                }
+
+                // Only the voices above a bass drop. The bass moves down an octave when
+                // the dropped voice falls below it.
+                let upper = usize::from(bass.is_some());
+                if voicing == Voicing::Drop2 && pitches.len() >= upper + 2 {
+                    let second = pitches.len() - 2;
+                    pitches[second].octave = pitches[second].octave.saturating_sub(1);
+                    if upper == 1 && pitches[second].midi_number() < pitches[0].midi_number() {
+                        pitches[0].octave = pitches[0].octave.saturating_sub(1);
+                    }
+                    pitches.sort_by(|a, b| a.midi_number().total_cmp(&b.midi_number()));
+                }
+                pitches
+            }
+            Voicing::Shell => {
+                let low = root.with_octave(octave);
+                let upper = [tones.third, tones.seventh.or(tones.fifth)];
+                let mut pitches: Vec<Pitch> = upper
+                    .iter()
+                    .flatten()
+                    .map(|interval| low.transpose(&simple(interval)))
+                    .collect();
+                pitches.sort_by(|a, b| a.midi_number().total_cmp(&b.midi_number()));
+
+                let bass = bass.unwrap_or(root);
+                let lowest = pitches.first().unwrap_or(&low);
+                let bass = if bass.pitch_class() == low.pitch_class() {
+                    low.clone()
+                } else {
+                    below(&bass, lowest)
+                };
+                [vec![bass], pitches].concat()
+            }
+            Voicing::Guitar => {
+                // Like an E-shape barre chord, or the root, third, seventh and
+                // extensions from the bass up when there are extensions.
+                let roles: Vec<Option<Interval>> = if tones.third.is_none() {
+                    vec![Some(tones.root), tones.fifth, Some(tones.root)]
+                } else if tones.extensions.is_empty() {
+                    vec![
+                        Some(tones.root),
+                        tones.fifth,
+                        Some(tones.seventh.unwrap_or(tones.root)),
+                        tones.third,
+                        tones.fifth,
+                        Some(tones.root),
+                    ]
+                } else {
+                    [Some(tones.root), tones.third, tones.seventh]
+                        .into_iter()
+                        .flatten()
+                        .chain(tones.extensions.iter().copied())
+                        .map(Some)
+                        .collect()
+                };
+
+                let bass = bass.unwrap_or_else(|| root.clone());
+                let mut lowest = bass.with_octave(2);
+                while lowest.midi_number() < GUITAR_LOWEST {
+                    lowest = octave_up(&lowest);
+                }
+                let mut pitches = vec![lowest];
+                for role in roles.iter().skip(1).flatten().take(5) {
+                    let next = above(&name(role), pitches.last().unwrap());
+                    pitches.push(next);
+                }
+                pitches
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Voicing;
+    use crate::musicxml::harmony::Harmony;
+    use crate::musicxml::theory::PitchName;
+    use serde_xml_rs::from_str;
+
+    fn realize(harmony: &Harmony, voicing: Voicing, octave: u8) -> Vec<String> {
+        harmony
+            .realize(voicing, octave)
+            .iter()
+            .map(|p| format!("{}{}", PitchName::from(p), p.octave))
+            .collect()
+    }
+
+    fn chord(symbol: &str, voicing: Voicing, octave: u8) -> Vec<String> {
+        realize(&symbol.parse().unwrap(), voicing, octave)
+    }
+
+    #[test]
+    fn close() {
+        assert_eq!(chord("C", Voicing::Close, 4), vec!["C4", "E4", "G4"]);
+        assert_eq!(
+            chord("Bb7(#11)", Voicing::Close, 3),
+            vec!["Bb3", "D4", "E4", "F4", "Ab4"]
+        );
+        assert_eq!(
+            chord("Cadd9(no5)", Voicing::Close, 4),
+            vec!["C4", "D4", "E4"]
+        );
+        assert_eq!(chord("C/E", Voicing::Close, 4), vec!["E4", "G4", "C5"]);
+        assert_eq!(
+            chord("C/Bb", Voicing::Close, 4),
+            vec!["Bb3", "C4", "E4", "G4"]
+        );
+
+        let xml = r#"
+            <harmony>
+                <root><root-step>D</root-step></root>
+                <kind>dominant</kind>
+                <inversion>3</inversion>
+            </harmony>"#;
+        let item: Harmony = from_str(xml).unwrap();
+        assert_eq!(
+            realize(&item, Voicing::Close, 4),
+            vec!["C5", "D5", "F#5", "A5"]
+        );
+    }
+
+    #[test]
+    fn drop_2() {
+        assert_eq!(
+            chord("Cmaj7", Voicing::Drop2, 4),
+            vec!["G3", "C4", "E4", "B4"]
+        );
+        assert_eq!(
+            chord("Fm7", Voicing::Drop2, 4),
+            vec!["C4", "F4", "Ab4", "Eb5"]
+        );
+        assert_eq!(chord("C/E", Voicing::Drop2, 4), vec!["E3", "G3", "C5"]);
+        assert_eq!(
+            chord("C7/E", Voicing::Drop2, 4),
+            vec!["E3", "Bb3", "G4", "C5"]
+        );
+        assert_eq!(
+            chord("C/Bb", Voicing::Drop2, 4),
+            vec!["Bb2", "E3", "C4", "G4"]
+        );
+    }
+
+    #[test]
+    fn shell() {
+        assert_eq!(chord("Bb7", Voicing::Shell, 3), vec!["Bb3", "D4", "Ab4"]);
+        assert_eq!(chord("Dm7b5", Voicing::Shell, 3), vec!["D3", "F3", "C4"]);
+        assert_eq!(chord("C6", Voicing::Shell, 3), vec!["C3", "E3", "A3"]);
+        assert_eq!(chord("G7/F", Voicing::Shell, 3), vec!["F3", "B3", "F4"]);
+    }
+
+    #[test]
+    fn guitar() {
+        assert_eq!(
+            chord("E", Voicing::Guitar, 4),
+            vec!["E2", "B2", "E3", "G#3", "B3", "E4"]
+        );
+        assert_eq!(
+            chord("A7", Voicing::Guitar, 4),
+            vec!["A2", "E3", "G3", "C#4", "E4", "A4"]
+        );
+        assert_eq!(
+            chord("C9", Voicing::Guitar, 4),
+            vec!["C3", "E3", "Bb3", "D4"]
+        );
+        assert_eq!(chord("D5", Voicing::Guitar, 4), vec!["D3", "A3", "D4"]);
+        assert!(chord("N.C.", Voicing::Guitar, 4).is_empty());
+    }
+}
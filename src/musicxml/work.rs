@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 
+use crate::musicxml::writer::{Attrs, WriteXml, XmlWriter};
 use crate::prelude::*;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -7,3 +8,13 @@ pub struct Work {
     #[serde(default = "String::default")]
     title: String,
 }
+
+impl WriteXml for Work {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str) {
+        w.start(name, Attrs::new());
+        if !self.title.is_empty() {
+            w.text_element("work-title", &self.title);
+        }
+        w.end(name);
+    }
+}
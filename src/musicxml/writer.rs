@@ -0,0 +1,424 @@
+use crate::error::MusicXmlError;
+use serde::{
+    ser::{Impossible, Serializer},
+    Serialize,
+};
+
+pub const XML_DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>"#;
+
+pub const PARTWISE_DOCTYPE: &str = r#"<!DOCTYPE score-partwise PUBLIC "-//Recordare//DTD MusicXML 4.0 Partwise//EN" "http://www.musicxml.org/dtds/partwise.dtd">"#;
+
+pub const TIMEWISE_DOCTYPE: &str = r#"<!DOCTYPE score-timewise PUBLIC "-//Recordare//DTD MusicXML 4.0 Timewise//EN" "http://www.musicxml.org/dtds/timewise.dtd">"#;
+
+pub const MUSICXML_VERSION: &str = "4.0";
+
+/// Implemented by every model type that can be written back to MusicXML. `name` is
+/// the element name to use, because the same type is used for differently named
+/// elements (e.g. `PrintableValue<String>` for `<words>` and `<footnote>`). Types
+/// that map to a choice of elements pick the name from their variant instead.
+pub trait WriteXml {
+    fn write_xml(&self, w: &mut XmlWriter, name: &str);
+}
+
+/// The attributes of a single element, in the order they are written.
+#[derive(Debug, Default)]
+pub struct Attrs(Vec<(&'static str, String)>);
+
+impl Attrs {
+    pub fn new() -> Attrs {
+        Attrs::default()
+    }
+
+    pub fn add<T: Serialize + ?Sized>(mut self, name: &'static str, value: &T) -> Attrs {
+        self.0.push((name, scalar(value)));
+        self
+    }
+
+    pub fn opt<T: Serialize>(self, name: &'static str, value: &Option<T>) -> Attrs {
+        match value {
+            Some(v) => self.add(name, v),
+            None => self,
+        }
+    }
+
+    pub fn add_if<T: Serialize + ?Sized>(self, cond: bool, name: &'static str, value: &T) -> Attrs {
+        if cond {
+            self.add(name, value)
+        } else {
+            self
+        }
+    }
+
+    pub fn append(mut self, other: Attrs) -> Attrs {
+        self.0.extend(other.0);
+        self
+    }
+}
+
+/// A minimal, indenting XML writer. Elements without content are collapsed to
+/// `<name/>`, elements with text content are kept on a single line.
+#[derive(Debug, Default)]
+pub struct XmlWriter {
+    out: String,
+    depth: usize,
+    open: bool,
+    inline: bool,
+}
+
+impl XmlWriter {
+    pub fn new() -> XmlWriter {
+        XmlWriter::default()
+    }
+
+    /// Starts a document with the XML declaration and the given DOCTYPE.
+    pub fn document(doctype: &str) -> XmlWriter {
+        let mut w = XmlWriter::new();
+        w.out.push_str(XML_DECLARATION);
+        w.out.push('\n');
+        w.out.push_str(doctype);
+        w
+    }
+
+    pub fn start(&mut self, name: &str, attrs: Attrs) {
+        self.close_start_tag();
+        if !self.out.is_empty() {
+            self.out.push('\n');
+        }
+        self.indent();
+        self.out.push('<');
+        self.out.push_str(name);
+        for (key, value) in attrs.0 {
+            self.out.push(' ');
+            self.out.push_str(key);
+            self.out.push_str("=\"");
+            self.out.push_str(&escape(&value));
+            self.out.push('"');
+        }
+        self.open = true;
+        self.inline = false;
+        self.depth += 1;
+    }
+
+    pub fn end(&mut self, name: &str) {
+        self.depth -= 1;
+        if self.open {
+            self.out.push_str("/>");
+            self.open = false;
+        } else {
+            if !self.inline {
+                self.out.push('\n');
+                self.indent();
+            }
+            self.out.push_str("</");
+            self.out.push_str(name);
+            self.out.push('>');
+        }
+        self.inline = false;
+    }
+
+    pub fn text<T: Serialize + ?Sized>(&mut self, value: &T) {
+        self.close_start_tag();
+        self.out.push_str(&escape(&scalar(value)));
+        self.inline = true;
+    }
+
+    pub fn empty(&mut self, name: &str, attrs: Attrs) {
+        self.start(name, attrs);
+        self.end(name);
+    }
+
+    pub fn text_element<T: Serialize + ?Sized>(&mut self, name: &str, value: &T) {
+        self.text_element_with(name, Attrs::new(), value);
+    }
+
+    pub fn text_element_with<T: Serialize + ?Sized>(
+        &mut self,
+        name: &str,
+        attrs: Attrs,
+        value: &T,
+    ) {
+        self.start(name, attrs);
+        self.text(value);
+        self.end(name);
+    }
+
+    pub fn opt_text_element<T: Serialize>(&mut self, name: &str, value: &Option<T>) {
+        if let Some(v) = value {
+            self.text_element(name, v);
+        }
+    }
+
+    pub fn element<T: WriteXml>(&mut self, name: &str, value: &T) {
+        value.write_xml(self, name);
+    }
+
+    pub fn opt_element<T: WriteXml>(&mut self, name: &str, value: &Option<T>) {
+        if let Some(v) = value {
+            v.write_xml(self, name);
+        }
+    }
+
+    pub fn elements<T: WriteXml>(&mut self, name: &str, values: &[T]) {
+        for v in values {
+            v.write_xml(self, name);
+        }
+    }
+
+    pub fn finish(mut self) -> String {
+        self.out.push('\n');
+        self.out
+    }
+
+    fn close_start_tag(&mut self) {
+        if self.open {
+            self.out.push('>');
+            self.open = false;
+        }
+    }
+
+    fn indent(&mut self) {
+        for _ in 0..self.depth {
+            self.out.push_str("  ");
+        }
+    }
+}
+
+fn escape(s: &str) -> String {
+    let mut r = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => r.push_str("&amp;"),
+            '<' => r.push_str("&lt;"),
+            '>' => r.push_str("&gt;"),
+            '"' => r.push_str("&quot;"),
+            // A raw carriage return would be read back as a line feed.
+            '\r' => r.push_str("&#xD;"),
+            _ => r.push(c),
+        }
+    }
+    r
+}
+
+/// Formats a scalar value the way it appears in MusicXML. Enums are written with
+/// their serde names, so `DurationType::Sixteenth` becomes `16th`.
+pub fn scalar<T: Serialize + ?Sized>(value: &T) -> String {
+    value
+        .serialize(ScalarSerializer)
+        .expect("attribute and text values are scalars")
+}
+
+impl serde::ser::Error for MusicXmlError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        MusicXmlError::Generic(msg.to_string())
+    }
+}
+
+struct ScalarSerializer;
+
+impl Serializer for ScalarSerializer {
+    type Ok = String;
+    type Error = MusicXmlError;
+    type SerializeSeq = Impossible<String, MusicXmlError>;
+    type SerializeTuple = Impossible<String, MusicXmlError>;
+    type SerializeTupleStruct = Impossible<String, MusicXmlError>;
+    type SerializeTupleVariant = Impossible<String, MusicXmlError>;
+    type SerializeMap = Impossible<String, MusicXmlError>;
+    type SerializeStruct = Impossible<String, MusicXmlError>;
+    type SerializeStructVariant = Impossible<String, MusicXmlError>;
+
+    fn serialize_bool(self, v: bool) -> Result<String, MusicXmlError> {
+        Ok(if v { "yes" } else { "no" }.to_string())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<String, MusicXmlError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<String, MusicXmlError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<String, MusicXmlError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<String, MusicXmlError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<String, MusicXmlError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<String, MusicXmlError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<String, MusicXmlError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<String, MusicXmlError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<String, MusicXmlError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<String, MusicXmlError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_char(self, v: char) -> Result<String, MusicXmlError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<String, MusicXmlError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<String, MusicXmlError> {
+        Err(serde::ser::Error::custom("bytes are not a scalar"))
+    }
+
+    fn serialize_none(self) -> Result<String, MusicXmlError> {
+        Ok(String::new())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<String, MusicXmlError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<String, MusicXmlError> {
+        Ok(String::new())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String, MusicXmlError> {
+        Ok(String::new())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<String, MusicXmlError> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, MusicXmlError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, MusicXmlError> {
+        Err(serde::ser::Error::custom(
+            "newtype variants are not a scalar",
+        ))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, MusicXmlError> {
+        Err(serde::ser::Error::custom("sequences are not a scalar"))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, MusicXmlError> {
+        Err(serde::ser::Error::custom("tuples are not a scalar"))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, MusicXmlError> {
+        Err(serde::ser::Error::custom("tuple structs are not a scalar"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, MusicXmlError> {
+        Err(serde::ser::Error::custom("tuple variants are not a scalar"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, MusicXmlError> {
+        Err(serde::ser::Error::custom("maps are not a scalar"))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, MusicXmlError> {
+        Err(serde::ser::Error::custom("structs are not a scalar"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, MusicXmlError> {
+        Err(serde::ser::Error::custom(
+            "struct variants are not a scalar",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{scalar, Attrs, XmlWriter};
+    use crate::musicxml::core::DurationType;
+
+    #[test]
+    fn scalars() {
+        assert_eq!(scalar(&DurationType::Sixteenth), "16th".to_string());
+        assert_eq!(scalar(&-55.0f32), "-55".to_string());
+        assert_eq!(scalar(&309.95f32), "309.95".to_string());
+        assert_eq!(scalar("A & B"), "A & B".to_string());
+    }
+
+    #[test]
+    fn writer() {
+        let mut w = XmlWriter::new();
+        w.start("measure", Attrs::new().add("number", "1"));
+        w.start("note", Attrs::new().opt("default-x", &Some(12.5f32)));
+        w.empty("rest", Attrs::new());
+        w.text_element("duration", &4);
+        w.end("note");
+        w.text_element_with(
+            "words",
+            Attrs::new().add("xml:lang", "de"),
+            "<Tempo> & \"Ruhig\"",
+        );
+        w.text_element("part-name", "Long\r\nName");
+        w.end("measure");
+
+        assert_eq!(
+            w.finish(),
+            r#"<measure number="1">
+  <note default-x="12.5">
+    <rest/>
+    <duration>4</duration>
+  </note>
+  <words xml:lang="de">&lt;Tempo&gt; &amp; &quot;Ruhig&quot;</words>
+  <part-name>Long&#xD;
+Name</part-name>
+</measure>
+"#
+        );
+    }
+}